@@ -20,16 +20,21 @@
  */
 
 use std::{
+	collections::BTreeMap,
 	fmt::Write,
-	fs::{create_dir, remove_dir_all, File},
+	fs::{create_dir_all, remove_dir_all, File},
 	io::Write as IoWrite,
 	path::PathBuf,
 };
 
-use anyhow::Result;
-use clap::{Parser, Subcommand, ValueEnum};
-use dropin_compiler_recipes::parser::parse_model;
-use prost::Message;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use dropin_compiler_recipes::{fold::fold_model, parser::parse_model};
+use dropin_target::{host::WasmTarget, Options, OutputFile};
+
+use crate::registry::Registry;
+
+mod registry;
 
 #[derive(Parser)]
 #[command(
@@ -46,20 +51,20 @@ struct Args {
 enum Commands {
 	Compile {
 		#[arg(name = "compilation target")]
-		target: Target,
+		target: String,
 		path: PathBuf,
 		#[arg(long, short)]
 		output: Option<PathBuf>,
+		#[arg(long)]
+		compact: bool,
+		#[arg(long)]
+		source_map: bool,
+		// WebAssembly components adding or replacing targets
+		#[arg(long = "plugin")]
+		plugins: Vec<PathBuf>,
 	},
 }
 
-#[derive(ValueEnum, Clone, Debug, PartialEq)]
-pub enum Target {
-	Flutter,
-	// Typescript,
-	// Wasm,
-}
-
 fn main() -> Result<()> {
 	let args = Args::parse();
 
@@ -68,29 +73,71 @@ fn main() -> Result<()> {
 			path,
 			target,
 			output,
+			compact,
+			source_map,
+			plugins,
 		} => {
-			let ir = parse_model(&path)?;
-			let mut protobuf = vec![];
-			ir.encode(&mut protobuf)?;
-			let protobuf = Box::into_raw(protobuf.into_boxed_slice());
-			let code = match target {
-				Target::Flutter => dropin_target_flutter::codegen(protobuf),
+			let mut registry = Registry::builtin();
+			for plugin in plugins {
+				let target = WasmTarget::load(&plugin).with_context(|| {
+					format!("failed to load the {} plugin", plugin.display())
+				})?;
+				registry.register(Box::new(target));
+			}
+			let Some(target) = registry.get(&target) else {
+				let names = registry.names().collect::<Vec<_>>().join(", ");
+				bail!("unknown target `{target}`, expected one of {names}");
 			};
-			let code = unsafe { Box::from_raw(code) };
+			let mut ir = parse_model(&path)?;
+			fold_model(&mut ir);
+			let mut options = Options::default();
+			for (name, is_set) in [("compact", compact), ("source-map", source_map)] {
+				if !is_set {
+					continue;
+				}
+				if !target.options().iter().any(|option| option.name == name) {
+					bail!("the {} target has no `{name}` option", target.name());
+				}
+				options.set(name);
+			}
+			let generated = match target.generate(&ir, &options) {
+				Ok(generated) => generated,
+				Err(diagnostics) => {
+					eprint!("{diagnostics}");
+					bail!("{} compilation failed", target.name());
+				}
+			};
+			eprint!("{}", generated.warnings);
+			let files = generated.files;
+			// checked before anything is written, a plugin may be faulty
+			if let Some(file) = files.iter().find(|file| !file.has_valid_path()) {
+				bail!(
+					"the {} target is faulty, it generated the invalid path `{}`",
+					target.name(),
+					file.path,
+				);
+			}
 			if let Some(output) = output {
 				if output.exists() {
 					remove_dir_all(&output)?;
 				}
-				create_dir(&output)?;
-				for (path, content) in *code {
+				create_dir_all(&output)?;
+				for OutputFile { path, content } in files {
 					let path = path
 						.split('/')
 						.fold(output.clone(), |path, key| path.join(key));
+					if let Some(parent) = path.parent() {
+						create_dir_all(parent)?;
+					}
 					let mut file = File::create(path)?;
-					file.write(content.as_bytes())?;
+					file.write_all(content.as_bytes())?;
 				}
 			} else {
-				println!("{}", serde_json::to_string(&code)?);
+				let files = files
+					.into_iter()
+					.map(|file| (file.path, file.content))
+					.collect::<BTreeMap<_, _>>();
+				println!("{}", serde_json::to_string(&files)?);
 			}
 		}
 	}
@@ -0,0 +1,59 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_target::Target;
+
+// the code generators known by name
+#[derive(Default)]
+pub struct Registry {
+	targets: BTreeMap<String, Box<dyn Target>>,
+}
+
+impl Registry {
+	pub fn builtin() -> Self {
+		let mut registry = Self::default();
+		registry.register(Box::new(dropin_target_compose::Compose));
+		registry.register(Box::new(dropin_target_flutter::Flutter));
+		registry.register(Box::new(dropin_target_html::Html));
+		registry.register(Box::new(dropin_target_leptos::Leptos));
+		registry.register(Box::new(dropin_target_react::React));
+		registry.register(Box::new(dropin_target_swiftui::SwiftUi));
+		registry.register(Box::new(dropin_target_typescript::Typescript));
+		registry.register(Box::new(dropin_target_vue::Vue));
+		registry.register(Box::new(dropin_target_webcomponents::WebComponents));
+		registry
+	}
+
+	// a later target replaces a former one of the same name
+	pub fn register(&mut self, target: Box<dyn Target>) {
+		self.targets.insert(target.name().into(), target);
+	}
+
+	pub fn get(&self, name: &str) -> Option<&dyn Target> {
+		self.targets.get(name).map(Box::as_ref)
+	}
+
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.targets.keys().map(String::as_str)
+	}
+}
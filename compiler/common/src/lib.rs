@@ -19,6 +19,8 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use std::fmt::{self, Display, Formatter};
+
 #[cfg(feature = "macros")]
 use proc_macro2::TokenStream;
 #[cfg(feature = "macros")]
@@ -55,6 +57,10 @@ pub enum TokenKind<'a> {
   In,
   Add,
   Sub,
+  Mul,
+  Div,
+  Mod,
+  Pow,
   Dot,
   Comma,
   And,
@@ -101,6 +107,10 @@ impl<'a> TokenKind<'a> {
       Self::In => "in",
       Self::Add => "+",
       Self::Sub => "-",
+      Self::Mul => "*",
+      Self::Div => "/",
+      Self::Mod => "%",
+      Self::Pow => "^",
       Self::Dot => ".",
       Self::Comma => ",",
       Self::And => "&",
@@ -177,6 +187,10 @@ impl<'a> ToTokens for TokenKind<'a> {
       Self::In => quote!(dropin_compiler_common::TokenKind::In),
       Self::Add => quote!(dropin_compiler_common::TokenKind::Add),
       Self::Sub => quote!(dropin_compiler_common::TokenKind::Sub),
+      Self::Mul => quote!(dropin_compiler_common::TokenKind::Mul),
+      Self::Div => quote!(dropin_compiler_common::TokenKind::Div),
+      Self::Mod => quote!(dropin_compiler_common::TokenKind::Mod),
+      Self::Pow => quote!(dropin_compiler_common::TokenKind::Pow),
       Self::Dot => quote!(dropin_compiler_common::TokenKind::Dot),
       Self::Comma => quote!(dropin_compiler_common::TokenKind::Comma),
       Self::And => quote!(dropin_compiler_common::TokenKind::And),
@@ -213,8 +227,73 @@ pub fn to_upper_camelcase(id: &str) -> String {
   result
 }
 
+pub fn to_kebab_case(id: &str) -> String {
+  let mut result = String::new();
+  for c in id.chars() {
+    if c == '_' || c == '/' {
+      result.push('-');
+      continue;
+    }
+    if c.is_ascii_uppercase() {
+      if !result.is_empty() && !result.ends_with('-') {
+        result.push('-');
+      }
+      result.push(c.to_ascii_lowercase());
+    } else {
+      result.push(c);
+    }
+  }
+  result
+}
+
 #[derive(Debug)]
 pub enum Key<'a> {
   Text(&'a str),
   Quantity(usize),
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  Warning,
+  Error,
+}
+
+impl Display for Severity {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Warning => f.write_str("warning"),
+      Self::Error => f.write_str("error"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  pub severity: Severity,
+  pub component: String,
+  pub message: String,
+}
+
+impl Diagnostic {
+  pub fn warning(component: &str, message: String) -> Self {
+    Self {
+      severity: Severity::Warning,
+      component: component.into(),
+      message,
+    }
+  }
+
+  pub fn error(component: &str, message: String) -> Self {
+    Self {
+      severity: Severity::Error,
+      component: component.into(),
+      message,
+    }
+  }
+}
+
+impl Display for Diagnostic {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}: {}: {}", self.severity, self.component, self.message)
+  }
+}
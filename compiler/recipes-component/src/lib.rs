@@ -0,0 +1,76 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Error;
+use dropin_compiler_recipes::parser::{
+  parse_component, parse_expression, parse_model_from_files,
+};
+use prost::Message;
+
+use self::exports::blueforest::dropin::parse::{Diagnostic, Guest};
+
+wit_bindgen::generate!({ path: "wit", world: "parser" });
+
+pub struct Parser;
+
+impl Guest for Parser {
+  fn parse(
+    input: String,
+    main_non_terminal: Option<String>,
+  ) -> Result<Vec<u8>, Vec<Diagnostic>> {
+    parse_expression(&input, main_non_terminal)
+      .map(|expression| expression.encode_to_vec())
+      .map_err(diagnostics)
+  }
+
+  fn parse_component(
+    id: String,
+    path: String,
+    recipe: String,
+  ) -> Result<Vec<u8>, Vec<Diagnostic>> {
+    parse_component(&id, &path, &recipe)
+      .map(|component| component.encode_to_vec())
+      .map_err(diagnostics)
+  }
+
+  fn parse_model(
+    files: Vec<(String, String)>,
+  ) -> Result<Vec<u8>, Vec<Diagnostic>> {
+    parse_model_from_files(&files.into_iter().collect())
+      .map(|model| model.encode_to_vec())
+      .map_err(diagnostics)
+  }
+}
+
+// exporting on other architectures would clash with the native symbols
+#[cfg(target_arch = "wasm32")]
+export!(Parser);
+
+fn diagnostics(err: Error) -> Vec<Diagnostic> {
+  let location = err
+    .downcast_ref::<serde_yaml::Error>()
+    .and_then(serde_yaml::Error::location);
+  vec![Diagnostic {
+    message: format!("{err:#}"),
+    line: location.as_ref().map(|location| location.line() as u32),
+    column: location.as_ref().map(|location| location.column() as u32),
+  }]
+}
@@ -0,0 +1,115 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_compiler_recipes::ir::{Component, Expression, Model};
+use dropin_compiler_recipes_component::{
+  exports::blueforest::dropin::parse::Guest, Parser,
+};
+use prost::Message;
+
+#[test]
+fn parse() {
+  let bytes = Parser::parse("a + 1".into(), None).unwrap();
+  let expression = Expression::decode(bytes.as_slice()).unwrap();
+  assert_eq!(
+    expression,
+    Expression::add(
+      Expression::getter("a".into(), vec![]),
+      Expression::quantity(1.),
+    ),
+  );
+}
+
+// syntax errors come back as diagnostics instead of panicking
+#[test]
+fn parse_errors() {
+  for input in ["a +", "a b", "(a", "a $ b"] {
+    let diagnostics = Parser::parse(input.into(), None).unwrap_err();
+    assert_eq!(diagnostics.len(), 1, "{input}");
+  }
+  let diagnostics = Parser::parse("a +".into(), None).unwrap_err();
+  assert_eq!(diagnostics[0].message, "unexpected end of `a +`");
+}
+
+const HOME: &str = r#"variables:
+  required:
+    count: '1'
+  keys:
+    count:
+      type: quantity
+blocks:
+  - type: text
+    content: "${count}"
+"#;
+
+#[test]
+fn parse_component() {
+  let bytes = Parser::parse_component(
+    "home".into(),
+    "components/home.dropin.yml".into(),
+    HOME.into(),
+  )
+  .unwrap();
+  let component = Component::decode(bytes.as_slice()).unwrap();
+  assert_eq!(component.id, "home");
+}
+
+// a syntax error in an expression is located in the recipe
+#[test]
+fn parse_component_errors() {
+  let recipe = HOME.replace("${count}", "${count +}");
+  let diagnostics = Parser::parse_component(
+    "home".into(),
+    "components/home.dropin.yml".into(),
+    recipe,
+  )
+  .unwrap_err();
+  assert_eq!(diagnostics.len(), 1);
+  assert!(diagnostics[0]
+    .message
+    .contains("unexpected end of `count +`"));
+  assert_eq!(diagnostics[0].line, Some(9));
+}
+
+#[test]
+fn parse_model() {
+  let model = r#"app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+  let files = vec![
+    ("model.dropin.yml".into(), model.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+  ];
+  let bytes = Parser::parse_model(files.clone()).unwrap();
+  let model = Model::decode(bytes.as_slice()).unwrap();
+  assert_eq!(model.name, "test");
+  assert_eq!(model.components.len(), 1);
+  assert_eq!(model.pages["home"].component, 0);
+
+  let diagnostics = Parser::parse_model(files[1..].to_vec()).unwrap_err();
+  assert_eq!(diagnostics[0].message, "missing model.dropin.yml");
+}
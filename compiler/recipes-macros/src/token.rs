@@ -31,6 +31,10 @@ pub fn node_to_token(node: &Node) -> TokenKind {
       "in" => TokenKind::In,
       "+" => TokenKind::Add,
       "-" => TokenKind::Sub,
+      "*" => TokenKind::Mul,
+      "/" => TokenKind::Div,
+      "%" => TokenKind::Mod,
+      "^" => TokenKind::Pow,
       "." => TokenKind::Dot,
       "," => TokenKind::Comma,
       "&" => TokenKind::And,
@@ -0,0 +1,348 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::mem::take;
+
+use crate::{
+  ir::{
+    AnonymousFunction, ArithmeticInner, Binary, ComparisonInner, ControlInner,
+    Expression, ExpressionInner, FunctionCall, LogicInner, Model,
+    NamedFunction, Operands, RichText, RichTextInner, RichTextPart, Value,
+    ValueInner,
+  },
+  visitor::{walk_expression_mut, walk_rich_text_mut, Visitor, VisitorMut},
+};
+
+// evaluates what is known before any target runs, so that `"a" + "b"`,
+// `!(!(a < b))` or `if true: a else: b` reach the targets already simplified
+pub fn fold_model(model: &mut Model) {
+  Folder.visit_model_mut(model);
+}
+
+pub fn fold_expression(expression: &mut Expression) {
+  Folder.visit_expression_mut(expression);
+}
+
+struct Folder;
+
+impl VisitorMut for Folder {
+  // operands are folded first, so each node only looks one level down
+  fn visit_expression_mut(&mut self, expression: &mut Expression) {
+    walk_expression_mut(self, expression);
+    if let Some(folded) = fold(expression) {
+      *expression = folded;
+    }
+  }
+
+  fn visit_rich_text_mut(&mut self, text: &mut RichText) {
+    walk_rich_text_mut(self, text);
+    let mut parts = Vec::with_capacity(text.parts.len());
+    for part in take(&mut text.parts) {
+      match part.rich_text_inner {
+        Some(RichTextInner::Dynamic(Expression {
+          expression_inner:
+            Some(ExpressionInner::Value(Value {
+              value_inner: Some(ValueInner::Text(nested)),
+            })),
+        })) => {
+          for part in nested.parts {
+            push_part(&mut parts, part);
+          }
+        }
+        _ => push_part(&mut parts, part),
+      }
+    }
+    text.parts = parts;
+  }
+}
+
+// replacement of an expression whose operands are already folded
+fn fold(expression: &mut Expression) -> Option<Expression> {
+  match expression.expression_inner.as_mut()? {
+    ExpressionInner::Value(_) => None,
+    ExpressionInner::Arithmetic(arithmetic) => {
+      match arithmetic.arithmetic_inner.as_mut()? {
+        ArithmeticInner::Opposite(operand) => {
+          Some(Expression::quantity(-quantity(operand)?))
+        }
+        ArithmeticInner::Add(binary) => {
+          let (left, right) = operands(binary);
+          if let (Some(left), Some(right)) = (quantity(left), quantity(right)) {
+            return Some(Expression::quantity(left + right));
+          }
+          text(left)?;
+          let right = take(text(right)?);
+          let left = text(left).unwrap();
+          for part in right.parts {
+            push_part(&mut left.parts, part);
+          }
+          Some(Expression::text(take(&mut left.parts)))
+        }
+        ArithmeticInner::Sub(binary) => calculate(binary, |l, r| l - r),
+        ArithmeticInner::Mul(binary) => calculate(binary, |l, r| l * r),
+        // a division by zero is left for the target to report
+        ArithmeticInner::Div(binary) | ArithmeticInner::Mod(binary)
+          if binary.right.as_deref().and_then(quantity) == Some(0.) =>
+        {
+          None
+        }
+        ArithmeticInner::Div(binary) => calculate(binary, |l, r| l / r),
+        // the remainder of `%` is euclidean, never negative
+        ArithmeticInner::Mod(binary) => calculate(binary, f64::rem_euclid),
+        ArithmeticInner::Pow(binary) => calculate(binary, f64::powf),
+      }
+    }
+    ExpressionInner::Logic(logic) => match logic.logic_inner.as_mut()? {
+      LogicInner::Not(operand) => {
+        if let Some(operand) = boolean(operand) {
+          return Some(Expression::boolean(!operand));
+        }
+        let ExpressionInner::Logic(logic) =
+          operand.expression_inner.as_mut()?
+        else {
+          return None;
+        };
+        let LogicInner::Not(operand) = logic.logic_inner.as_mut()? else {
+          return None;
+        };
+        // `!` turns anything else into a boolean
+        if !is_boolean(operand) {
+          return None;
+        }
+        Some(take(&mut **operand))
+      }
+      LogicInner::And(operands) => flatten(operands, true),
+      LogicInner::Or(operands) => flatten(operands, false),
+      LogicInner::Exists(_) => None,
+    },
+    ExpressionInner::Comparison(comparison) => {
+      match comparison.comparison_inner.as_mut()? {
+        ComparisonInner::EqualsTo(binary) => {
+          equals(binary).map(Expression::boolean)
+        }
+        ComparisonInner::DifferentFrom(binary) => {
+          equals(binary).map(|equals| Expression::boolean(!equals))
+        }
+        ComparisonInner::In(_) => None,
+        ComparisonInner::LessThan(binary) => compare(binary, |l, r| l < r),
+        ComparisonInner::MoreThan(binary) => compare(binary, |l, r| l > r),
+        ComparisonInner::AtLeast(binary) => compare(binary, |l, r| l >= r),
+        ComparisonInner::AtMost(binary) => compare(binary, |l, r| l <= r),
+      }
+    }
+    ExpressionInner::Control(control) => {
+      match control.control_inner.as_mut()? {
+        ControlInner::If(r#if) => {
+          let branch = if boolean(r#if.condition.as_ref()?)? {
+            r#if.then.as_mut()
+          } else {
+            r#if.r#else.as_mut()
+          };
+          Some(
+            branch
+              .map_or_else(Expression::undefined, |branch| take(&mut **branch)),
+          )
+        }
+        ControlInner::AnonymousFunction(_)
+        | ControlInner::NamedFunction(_)
+        | ControlInner::FunctionCall(_) => None,
+      }
+    }
+  }
+}
+
+// merges nested operands of the same operator and drops the neutral ones,
+// `neutral` being true for `&` and false for `|`
+fn flatten(operands: &mut Operands, neutral: bool) -> Option<Expression> {
+  let mut flattened = Vec::with_capacity(operands.operands.len());
+  for mut operand in take(&mut operands.operands) {
+    match boolean(&operand) {
+      Some(value) if value == neutral => continue,
+      // the operands after it are never evaluated, the ones before it are
+      // only dropped if they have no effect
+      Some(_) => {
+        if flattened.iter().all(is_pure) {
+          return Some(Expression::boolean(!neutral));
+        }
+        flattened.push(operand);
+        break;
+      }
+      None => {}
+    }
+    if let Some(ExpressionInner::Logic(logic)) =
+      operand.expression_inner.as_mut()
+    {
+      match logic.logic_inner.as_mut() {
+        Some(LogicInner::And(nested)) if neutral => {
+          flattened.append(&mut nested.operands);
+          continue;
+        }
+        Some(LogicInner::Or(nested)) if !neutral => {
+          flattened.append(&mut nested.operands);
+          continue;
+        }
+        _ => {}
+      }
+    }
+    flattened.push(operand);
+  }
+  match flattened.len() {
+    0 => Some(Expression::boolean(neutral)),
+    1 => flattened.pop(),
+    _ => {
+      operands.operands = flattened;
+      None
+    }
+  }
+}
+
+fn is_boolean(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner,
+    Some(ExpressionInner::Logic(_) | ExpressionInner::Comparison(_))
+  )
+}
+
+// whether evaluating the expression calls no function
+fn is_pure(expression: &Expression) -> bool {
+  let mut calls = Calls(false);
+  calls.visit_expression(expression);
+  !calls.0
+}
+
+struct Calls(bool);
+
+// the bodies of the functions defined along are not evaluated
+impl Visitor<'_> for Calls {
+  fn visit_function_call(&mut self, _call: &FunctionCall) {
+    self.0 = true;
+  }
+
+  fn visit_anonymous_function(&mut self, _function: &AnonymousFunction) {}
+
+  fn visit_named_function(&mut self, _function: &NamedFunction) {}
+}
+
+fn equals(binary: &mut Binary) -> Option<bool> {
+  let (left, right) = operands(binary);
+  if let (Some(left), Some(right)) = (quantity(left), quantity(right)) {
+    return Some(left == right);
+  }
+  if let (Some(left), Some(right)) = (boolean(left), boolean(right)) {
+    return Some(left == right);
+  }
+  Some(static_text(left)? == static_text(right)?)
+}
+
+fn calculate(
+  binary: &mut Binary,
+  operate: impl Fn(f64, f64) -> f64,
+) -> Option<Expression> {
+  let (left, right) = operands(binary);
+  Some(Expression::quantity(operate(
+    quantity(left)?,
+    quantity(right)?,
+  )))
+}
+
+fn compare(
+  binary: &mut Binary,
+  compare: impl Fn(f64, f64) -> bool,
+) -> Option<Expression> {
+  let (left, right) = operands(binary);
+  Some(Expression::boolean(compare(
+    quantity(left)?,
+    quantity(right)?,
+  )))
+}
+
+fn operands(binary: &mut Binary) -> (&mut Expression, &mut Expression) {
+  (
+    binary.left.as_mut().unwrap(),
+    binary.right.as_mut().unwrap(),
+  )
+}
+
+fn value(expression: &Expression) -> Option<&ValueInner> {
+  let ExpressionInner::Value(value) = expression.expression_inner.as_ref()?
+  else {
+    return None;
+  };
+  value.value_inner.as_ref()
+}
+
+fn quantity(expression: &Expression) -> Option<f64> {
+  match value(expression)? {
+    ValueInner::Quantity(quantity) => Some(*quantity),
+    _ => None,
+  }
+}
+
+fn boolean(expression: &Expression) -> Option<bool> {
+  match value(expression)? {
+    ValueInner::Boolean(boolean) => Some(*boolean),
+    _ => None,
+  }
+}
+
+fn text(expression: &mut Expression) -> Option<&mut RichText> {
+  let ExpressionInner::Value(value) = expression.expression_inner.as_mut()?
+  else {
+    return None;
+  };
+  match value.value_inner.as_mut()? {
+    ValueInner::Text(text) => Some(text),
+    _ => None,
+  }
+}
+
+// texts are merged once folded, so a static text has at most one part
+fn static_text(expression: &Expression) -> Option<&str> {
+  let ValueInner::Text(text) = value(expression)? else {
+    return None;
+  };
+  match text.parts.as_slice() {
+    [] => Some(""),
+    [RichTextPart {
+      rich_text_inner: Some(RichTextInner::Static(text)),
+    }] => Some(text),
+    _ => None,
+  }
+}
+
+// appends a part, joining consecutive static parts and dropping empty ones
+fn push_part(parts: &mut Vec<RichTextPart>, part: RichTextPart) {
+  let Some(RichTextInner::Static(text)) = &part.rich_text_inner else {
+    parts.push(part);
+    return;
+  };
+  if text.is_empty() {
+    return;
+  }
+  if let Some(RichTextPart {
+    rich_text_inner: Some(RichTextInner::Static(last)),
+  }) = parts.last_mut()
+  {
+    last.push_str(text);
+    return;
+  }
+  parts.push(part);
+}
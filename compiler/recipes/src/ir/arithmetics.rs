@@ -36,4 +36,56 @@ impl Expression {
       ))),
     }
   }
+
+  pub fn mul(left: Expression, right: Expression) -> Self {
+    Self {
+      expression_inner: Some(ExpressionInner::Arithmetic(Box::new(
+        Arithmetic {
+          arithmetic_inner: Some(ArithmeticInner::Mul(Box::new(Binary {
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
+          }))),
+        },
+      ))),
+    }
+  }
+
+  pub fn div(left: Expression, right: Expression) -> Self {
+    Self {
+      expression_inner: Some(ExpressionInner::Arithmetic(Box::new(
+        Arithmetic {
+          arithmetic_inner: Some(ArithmeticInner::Div(Box::new(Binary {
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
+          }))),
+        },
+      ))),
+    }
+  }
+
+  pub fn r#mod(left: Expression, right: Expression) -> Self {
+    Self {
+      expression_inner: Some(ExpressionInner::Arithmetic(Box::new(
+        Arithmetic {
+          arithmetic_inner: Some(ArithmeticInner::Mod(Box::new(Binary {
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
+          }))),
+        },
+      ))),
+    }
+  }
+
+  pub fn pow(left: Expression, right: Expression) -> Self {
+    Self {
+      expression_inner: Some(ExpressionInner::Arithmetic(Box::new(
+        Arithmetic {
+          arithmetic_inner: Some(ArithmeticInner::Pow(Box::new(Binary {
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
+          }))),
+        },
+      ))),
+    }
+  }
 }
@@ -20,6 +20,7 @@ impl Component {
         common: Some(ComponentCommon { classes }),
         blocks,
       }),
+      source: None,
     }
   }
 
@@ -26,6 +26,8 @@ macro_rules! debug {
   };
 }
 
+pub mod fold;
 pub mod ir;
 #[cfg(feature = "parser")]
 pub mod parser;
+pub mod visitor;
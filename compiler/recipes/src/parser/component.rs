@@ -1,11 +1,30 @@
 use std::fmt::{self, Formatter};
 
+use anyhow::Result;
 use serde::{
   de::{self, MapAccess, Visitor},
   Deserialize, Deserializer,
 };
 
-use crate::ir::Component;
+use crate::{
+  ir::{Component, Source},
+  parser::source::yaml_lines,
+};
+
+// `path` is the recipe path relative to the model root
+pub fn parse_component(
+  id: &str,
+  path: &str,
+  recipe: &str,
+) -> Result<Component> {
+  let mut component = serde_yaml::from_str::<Component>(recipe)?;
+  component.set_id(id.into());
+  component.source = Some(Source {
+    path: path.into(),
+    lines: yaml_lines(recipe),
+  });
+  Ok(component)
+}
 
 impl<'de> Deserialize<'de> for Component {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
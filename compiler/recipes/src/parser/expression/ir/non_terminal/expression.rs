@@ -62,9 +62,7 @@ pub(super) fn build(
       LessThan => less_than,
       MoreThan => more_than,
       AtLeast => at_least,
-      AtMost => at_most,
-      Add => add,
-      Sub => sub
+      AtMost => at_most
     )
   } else {
     left
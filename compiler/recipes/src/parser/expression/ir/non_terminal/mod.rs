@@ -25,6 +25,7 @@ use std::vec::Vec;
 use super::{BuildState, ExpressionBuilder};
 use crate::ir::Expression;
 
+mod conjunction;
 mod expression;
 mod function;
 mod function_call;
@@ -34,7 +35,10 @@ mod if_then;
 mod list_id;
 mod list_lit;
 mod object;
+mod power;
 mod predicate;
+mod product;
+mod sum;
 mod value;
 mod value_indent;
 mod value_lit;
@@ -80,7 +84,11 @@ impl<'a> ExpressionBuilder<'a> {
     }
     Ok(build!(
       "predicate" => predicate,
+      "conjunction" => conjunction,
       "expression" => expression,
+      "sum" => sum,
+      "product" => product,
+      "power" => power,
       "value" => value,
       "value-no-indent" => value_no_indent,
       "value-indent" => value_indent,
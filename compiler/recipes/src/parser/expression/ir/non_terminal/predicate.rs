@@ -19,12 +19,12 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use dropin_compiler_common::TokenKind;
 use std::vec::Vec;
 
 use crate::ir::Expression;
 use crate::parser::expression::ir::{BuildState, ExpressionBuilder};
 
+// `|` binds looser than `&`, so each operand is a conjunction
 pub(super) fn build(
   children: &[usize],
   nodes: &mut Vec<Option<ExpressionBuilder>>,
@@ -35,41 +35,25 @@ pub(super) fn build(
     return Expression::undefined();
   }
   state.in_keys = false;
-  let first_operand =
-    nodes[children[0]]
-      .take()
-      .unwrap()
-      .build_inner(nodes, input, state.clone());
-  if children.len() > 1 {
-    let mut operands = vec![first_operand];
-    let mut i = 2;
-    let mut logic_token = nodes[children[i - 1]].as_ref().unwrap().token;
-    while i < children.len() {
-      let new_logic_token = nodes[children[i - 1]].take().unwrap().token;
-      if new_logic_token != logic_token {
-        match logic_token {
-          TokenKind::And => {
-            let and = Expression::and(operands);
-            operands = vec![and];
-          }
-          TokenKind::Or => {
-            let or = Expression::or(operands);
-            operands = vec![or];
-          }
-          _ => unreachable!(),
-        }
-        logic_token = new_logic_token;
-      }
-      let node = nodes[children[i]].take().unwrap();
-      operands.push(node.build_inner(nodes, input, state.clone()));
-      i += 2;
-    }
-    match logic_token {
-      TokenKind::And => Expression::and(operands),
-      TokenKind::Or => Expression::or(operands),
-      _ => unreachable!(),
-    }
+  let mut operands = operands(children, nodes, input, state);
+  if operands.len() > 1 {
+    Expression::or(operands)
   } else {
-    first_operand
+    operands.pop().unwrap()
   }
 }
+
+// operands of a chain of the same operator, skipping the operator tokens
+pub(super) fn operands(
+  children: &[usize],
+  nodes: &mut Vec<Option<ExpressionBuilder>>,
+  input: &str,
+  state: BuildState,
+) -> Vec<Expression> {
+  let mut operands = Vec::with_capacity(children.len() / 2 + 1);
+  for child in children.iter().step_by(2) {
+    let node = nodes[*child].take().unwrap();
+    operands.push(node.build_inner(nodes, input, state.clone()));
+  }
+  operands
+}
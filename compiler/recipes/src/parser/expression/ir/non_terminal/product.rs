@@ -0,0 +1,55 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_compiler_common::TokenKind;
+use std::vec::Vec;
+
+use crate::ir::Expression;
+use crate::parser::expression::ir::{BuildState, ExpressionBuilder};
+
+// `*`, `/` and `%` are left associative
+pub(super) fn build(
+  children: &[usize],
+  nodes: &mut Vec<Option<ExpressionBuilder>>,
+  input: &str,
+  state: BuildState,
+) -> Expression {
+  let mut left =
+    nodes[children[0]]
+      .take()
+      .unwrap()
+      .build_inner(nodes, input, state.clone());
+  for pair in children[1..].chunks(2) {
+    let operator = nodes[pair[0]].take().unwrap().token;
+    let right =
+      nodes[pair[1]]
+        .take()
+        .unwrap()
+        .build_inner(nodes, input, state.clone());
+    left = match operator {
+      TokenKind::Mul => Expression::mul(left, right),
+      TokenKind::Div => Expression::div(left, right),
+      TokenKind::Mod => Expression::r#mod(left, right),
+      _ => panic!("unknown product operator: {operator:?}"),
+    };
+  }
+  left
+}
@@ -104,9 +104,12 @@ pub fn id(
   while i < siblings.len() {
     let sep = nodes[siblings[i - 1]].take().unwrap().token;
     let key = nodes[siblings[i]].take().unwrap();
-    indexes.push(key.build_inner(nodes, input, keys_state.clone()));
+    // `.key` is a static key, `[key]` the value of an expression
     if let TokenKind::BracGlued = sep {
+      indexes.push(key.build_inner(nodes, input, state.clone()));
       i += 1;
+    } else {
+      indexes.push(key.build_inner(nodes, input, keys_state.clone()));
     }
     i += 2;
   }
@@ -21,6 +21,7 @@
 
 use std::fmt::{self, Formatter};
 
+use anyhow::{bail, Result};
 use dropin_compiler_common::TokenKind;
 use serde::{
   de::{self, Visitor},
@@ -29,7 +30,7 @@ use serde::{
 
 use crate::{
   ir::Expression,
-  parser::{lexer, Table},
+  parser::{lexer, token::Token, Table},
 };
 
 use self::non_terminal::parse_non_terminal;
@@ -59,7 +60,7 @@ impl<'de> Deserialize<'de> for Expression {
       where
         E: de::Error,
       {
-        Ok(parse(v, None, &Table::default()))
+        parse(v, None, &Table::default()).map_err(E::custom)
       }
     }
 
@@ -71,10 +72,10 @@ pub fn parse(
   input: &str,
   main_non_terminal: Option<String>,
   table: &Table,
-) -> Expression {
+) -> Result<Expression> {
   debug!("{:?}", input);
 
-  let mut tokens = lexer(input);
+  let mut tokens = lexer(input)?;
   debug!("{:?}", tokens);
 
   let mut stack = Stack::new(
@@ -101,14 +102,14 @@ pub fn parse(
     let control = match token {
       TokenKind::NonTerminal(name) => {
         let (control, new_is_deindent) = parse_non_terminal(
-          &table,
+          table,
           input,
           &mut tokens,
           &mut current,
           stack_top,
           name,
           is_deindent,
-        );
+        )?;
         is_deindent = new_is_deindent;
         control
       }
@@ -117,12 +118,12 @@ pub fn parse(
       TokenKind::Deindent => {
         debug!("DEINDENT");
         is_deindent = true;
-        parse_terminal(&tokens, &mut current, stack_top)
+        parse_terminal(input, &tokens, &mut current, stack_top)?
       }
       _ => {
         debug!("PUSH {}", token.as_str());
         is_deindent = false;
-        parse_terminal(&tokens, &mut current, stack_top)
+        parse_terminal(input, &tokens, &mut current, stack_top)?
       }
     };
     if let LoopControl::Break = control {
@@ -136,10 +137,37 @@ pub fn parse(
     .unwrap()
     .debug(&stack.nodes, input, 0);
 
+  // what follows a whole expression can only be the end of its lines
+  if let Some(token) =
+    tokens[current.min(tokens.len())..].iter().find(|token| {
+      !matches!(
+        token.kind,
+        TokenKind::Newline | TokenKind::Indent | TokenKind::Deindent
+      )
+    })
+  {
+    bail!(unexpected(input, Some(token)));
+  }
+
   let root = stack.into_expression(input);
 
   debug!("{root:?}");
-  root
+  Ok(root)
+}
+
+// the token found where the grammar expected another one, `None` being the
+// end of the input
+fn unexpected(input: &str, token: Option<&Token>) -> String {
+  match token {
+    Some(token) if token.span.0 < token.span.1 => format!(
+      "unexpected `{}` in `{input}`",
+      &input[token.span.0..token.span.1],
+    ),
+    Some(token) => {
+      format!("unexpected {} in `{input}`", token.kind.as_str())
+    }
+    None => format!("unexpected end of `{input}`"),
+  }
 }
 
 pub enum LoopControl {
@@ -19,12 +19,13 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use anyhow::{bail, Result};
 use dropin_compiler_common::TokenKind;
 use std::vec::Vec;
 
 use crate::parser::{token::Token, Table};
 
-use super::{stack::StackNode, LoopControl};
+use super::{stack::StackNode, unexpected, LoopControl};
 
 pub(super) fn parse_non_terminal(
   table: &Table,
@@ -34,7 +35,7 @@ pub(super) fn parse_non_terminal(
   stack_top: StackNode,
   name: &str,
   is_deindent: bool,
-) -> (LoopControl, bool) {
+) -> Result<(LoopControl, bool)> {
   let token_type = if *current < tokens.len() {
     tokens[*current].kind
   } else {
@@ -59,8 +60,12 @@ pub(super) fn parse_non_terminal(
   let substitute = if let Some(substitute) = substitute {
     substitute
   } else {
+    // an empty input is an empty expression, any other one ended too early
     if token_type == TokenKind::Eof {
-      return (LoopControl::Break, false);
+      if tokens.is_empty() {
+        return Ok((LoopControl::Break, false));
+      }
+      bail!(unexpected(input, None));
     }
 
     if is_deindent {
@@ -69,17 +74,17 @@ pub(super) fn parse_non_terminal(
       if let Some(parent) = parent {
         stack_top.stack.pop_children(parent);
       }
-      return (LoopControl::Continue, false);
+      return Ok((LoopControl::Continue, false));
     }
 
     if let TokenKind::Newline | TokenKind::Indent | TokenKind::Deindent =
       tokens[*current].kind
     {
       *current += 1;
-      return (LoopControl::Continue, is_deindent);
+      return Ok((LoopControl::Continue, is_deindent));
     }
     debug!("{:?}", &tokens[*current..]);
-    panic!("{}\nunexpected {} {:?}", input, name, token_type);
+    bail!(unexpected(input, tokens.get(*current)));
   };
 
   debug!(
@@ -95,5 +100,5 @@ pub(super) fn parse_non_terminal(
 
   stack_top.stack.substitute(Some(stack_top.i), substitute);
 
-  (LoopControl::Continue, is_deindent)
+  Ok((LoopControl::Continue, is_deindent))
 }
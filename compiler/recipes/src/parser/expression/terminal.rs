@@ -19,17 +19,29 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use anyhow::{bail, Result};
+
 use crate::parser::token::Token;
 
-use super::{stack::StackNode, LoopControl};
+use super::{stack::StackNode, unexpected, LoopControl};
 
 pub(super) fn parse_terminal(
+  input: &str,
   tokens: &[Token],
   current: &mut usize,
   mut stack_top: StackNode,
-) -> LoopControl {
-  stack_top.builder().span = Some(tokens[*current].span);
+) -> Result<LoopControl> {
+  let expected = stack_top.builder().token;
+  let Some(token) = tokens.get(*current).filter(|token| token.kind == expected)
+  else {
+    bail!(
+      "{}, expected `{}`",
+      unexpected(input, tokens.get(*current)),
+      expected.as_str(),
+    );
+  };
+  stack_top.builder().span = Some(token.span);
   stack_top.stack.push_children(stack_top.i);
   *current += 1;
-  LoopControl::Continue
+  Ok(LoopControl::Continue)
 }
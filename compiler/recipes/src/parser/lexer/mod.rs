@@ -19,6 +19,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use anyhow::{bail, Result};
 use dropin_compiler_common::TokenKind;
 
 use crate::parser::token::{Token, TokenState};
@@ -52,7 +53,7 @@ impl<'a> Tokens<'a> {
   }
 }
 
-pub fn lexer(input: &str) -> Vec<Token> {
+pub fn lexer(input: &str) -> Result<Vec<Token>> {
   let mut tokens = Tokens(Vec::new());
   let mut current: Option<Token> = None;
   let mut indents = IndentLexer::default();
@@ -117,6 +118,14 @@ pub fn lexer(input: &str) -> Vec<Token> {
           tokens.push(Token::new(TokenKind::Add, (index, index + 1)));
         } else if c == b'-' {
           tokens.push(Token::new(TokenKind::Sub, (index, index + 1)));
+        } else if c == b'*' {
+          tokens.push(Token::new(TokenKind::Mul, (index, index + 1)));
+        } else if c == b'/' {
+          tokens.push(Token::new(TokenKind::Div, (index, index + 1)));
+        } else if c == b'%' {
+          tokens.push(Token::new(TokenKind::Mod, (index, index + 1)));
+        } else if c == b'^' {
+          tokens.push(Token::new(TokenKind::Pow, (index, index + 1)));
         } else if c == b':' {
           tokens.push(Token::new(TokenKind::Block, (index, index + 1)));
         } else if c == b'.' {
@@ -178,7 +187,7 @@ pub fn lexer(input: &str) -> Vec<Token> {
         } else if input[index..].starts_with('\\') {
           tokens.push(Token::new(TokenKind::Backslash, (index, index + 1)));
         } else {
-          panic!("unexpected token: {}", char::from(c));
+          bail!("unexpected `{}` in `{input}`", char::from(c));
         }
       }
       index += 1;
@@ -239,7 +248,7 @@ pub fn lexer(input: &str) -> Vec<Token> {
     ));
   }
 
-  tokens.into_inner()
+  Ok(tokens.into_inner())
 }
 
 fn is_whitespace(byte: u8) -> bool {
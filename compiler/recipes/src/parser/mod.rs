@@ -19,10 +19,15 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub use self::component::parse_component;
 pub use self::lexer::lexer;
-pub use self::model::parse_model;
+pub use self::model::{parse_model, parse_model_from_files};
 pub use self::token::Token;
 
+use anyhow::Result;
+
+use crate::ir::Expression;
+
 mod child;
 mod component;
 mod expression;
@@ -30,6 +35,7 @@ mod format;
 mod keys;
 mod lexer;
 mod model;
+mod source;
 mod text;
 mod token;
 
@@ -39,3 +45,12 @@ const EXTENSION: &str = ".dropin.yml";
   grammar = "compiler/recipes/src/parser/grammar.abnf"
 )]
 pub struct Table;
+
+// parses an expression from the `main_non_terminal` rule of the grammar,
+// `predicate` by default
+pub fn parse_expression(
+  input: &str,
+  main_non_terminal: Option<String>,
+) -> Result<Expression> {
+  expression::parse(input, main_non_terminal, &Table::default())
+}
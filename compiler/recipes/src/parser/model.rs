@@ -1,19 +1,62 @@
-use std::{collections::BTreeMap, fs::File, io::Read, path::Path};
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::Read,
+  path::{Component as PathComponent, Path},
+};
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use serde::Deserialize;
 use walkdir::WalkDir;
 
 use crate::{
-  ir::{Component, Model, Page},
-  parser::EXTENSION,
+  ir::{Model, Page},
+  parser::{component::parse_component, EXTENSION},
 };
 
+const MODEL: &str = "model.dropin.yml";
+const COMPONENTS: &str = "components";
+
 pub fn parse_model(root: &Path) -> Result<Model> {
-  let mut f = File::open(root.join("model.dropin.yml"))?;
-  let mut recipe = String::new();
-  f.read_to_string(&mut recipe)?;
-  let model = serde_yaml::from_str::<ModelRecipe>(&recipe)?;
+  let mut files = BTreeMap::new();
+  files.insert(MODEL.into(), read(&root.join(MODEL))?);
+  let components_path = root.join(COMPONENTS);
+  for entry in WalkDir::new(&components_path) {
+    let entry = entry?;
+    if entry.metadata()?.is_dir() {
+      continue;
+    }
+    let path = entry.path().strip_prefix(&components_path)?;
+    let path = path
+      .components()
+      .filter_map(|component| match component {
+        PathComponent::Normal(name) => name.to_str(),
+        _ => None,
+      })
+      .collect::<Vec<_>>()
+      .join("/");
+    if !path.ends_with(EXTENSION) {
+      continue;
+    }
+    files.insert(format!("{COMPONENTS}/{path}"), read(entry.path())?);
+  }
+
+  let model = parse_model_from_files(&files)?;
+
+  #[cfg(debug_assertions)]
+  println!("{model:#?}");
+
+  Ok(model)
+}
+
+// `files` maps the recipe paths, relative to the model root and separated
+// with `/`, to their content
+pub fn parse_model_from_files(
+  files: &BTreeMap<String, String>,
+) -> Result<Model> {
+  let recipe = files.get(MODEL).ok_or_else(|| anyhow!("missing {MODEL}"))?;
+  let model = serde_yaml::from_str::<ModelRecipe>(recipe)
+    .with_context(|| format!("invalid {MODEL}"))?;
   let mut pages = BTreeMap::new();
   let mut components = Vec::new();
   let mut component_to_page = model
@@ -23,31 +66,16 @@ pub fn parse_model(root: &Path) -> Result<Model> {
     .map(|(key, page)| (page.component.as_str(), key))
     .collect::<BTreeMap<_, _>>();
 
-  let components_path = root.join("components");
-  let components_path_len = components_path.to_str().unwrap().len();
-  for entry in WalkDir::new(&components_path) {
-    let entry = entry?;
-    if entry.metadata()?.is_dir() {
-      continue;
-    }
-    let path = entry.path();
-    if !path
-      .file_name()
-      .unwrap()
-      .to_str()
-      .unwrap()
-      .ends_with(EXTENSION)
-    {
+  for (path, recipe) in files {
+    let Some(id) = path
+      .strip_prefix(COMPONENTS)
+      .and_then(|id| id.strip_prefix('/'))
+      .and_then(|id| id.strip_suffix(EXTENSION))
+    else {
       continue;
-    }
-    let mut f = File::open(path)?;
-    let mut recipe = String::new();
-    f.read_to_string(&mut recipe)?;
-    let mut component = serde_yaml::from_str::<Component>(&recipe)?;
-    let path_str = path.to_str().unwrap();
-    let id =
-      &path_str[components_path_len + 1..path_str.len() - EXTENSION.len()];
-    component.set_id(id.into());
+    };
+    let component = parse_component(id, path, recipe)
+      .with_context(|| format!("invalid {path}"))?;
     if let Some(key) = component_to_page.remove(id) {
       let page = model.app.pages.get(key).unwrap();
       let index = components.len() as u64;
@@ -63,17 +91,19 @@ pub fn parse_model(root: &Path) -> Result<Model> {
     components.push(component);
   }
 
-  let model = Model {
+  Ok(Model {
     name: model.app.name,
     version: model.app.version,
     pages,
     components,
-  };
-
-  #[cfg(debug_assertions)]
-  println!("{model:#?}");
+  })
+}
 
-  Ok(model)
+fn read(path: &Path) -> Result<String> {
+  let mut f = File::open(path)?;
+  let mut recipe = String::new();
+  f.read_to_string(&mut recipe)?;
+  Ok(recipe)
 }
 
 #[derive(Deserialize)]
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+// line of each key and sequence item of a yaml recipe, by dotted path
+pub fn yaml_lines(recipe: &str) -> BTreeMap<String, u32> {
+  let mut lines = BTreeMap::new();
+  lines.insert(String::new(), 1);
+  // indentation, path and whether it is a sequence item
+  let mut parents: Vec<(usize, String, bool)> = Vec::new();
+  let mut next_indexes = BTreeMap::<String, usize>::new();
+  let mut block_scalar_indent = None;
+  for (number, line) in recipe.lines().enumerate() {
+    let number = number as u32 + 1;
+    let mut content = line.trim_start();
+    let mut indent = line.len() - content.len();
+    if content.is_empty() || content.starts_with('#') {
+      continue;
+    }
+    if let Some(block_indent) = block_scalar_indent {
+      if indent > block_indent {
+        continue;
+      }
+      block_scalar_indent = None;
+    }
+
+    let mut item = None;
+    while content == "-" || content.starts_with("- ") {
+      while let Some((parent_indent, _, is_item)) = parents.last() {
+        if *parent_indent > indent || (*parent_indent == indent && *is_item) {
+          parents.pop();
+        } else {
+          break;
+        }
+      }
+      let parent = parents.last().map(|(_, path, _)| path.as_str());
+      let parent = parent.unwrap_or("").to_string();
+      let index = next_indexes.entry(parent.clone()).or_default();
+      let path = join(&parent, &index.to_string());
+      *index += 1;
+      lines.insert(path.clone(), number);
+      parents.push((indent, path.clone(), true));
+      item = Some(path);
+      let rest = content[1..].trim_start();
+      indent += content.len() - rest.len();
+      content = rest;
+    }
+
+    let Some((key, value)) = split_key(content) else {
+      if let Some(item) = item {
+        for key in flow_keys(content) {
+          lines.insert(join(&item, key), number);
+        }
+      }
+      continue;
+    };
+    while let Some((parent_indent, _, _)) = parents.last() {
+      if *parent_indent >= indent {
+        parents.pop();
+      } else {
+        break;
+      }
+    }
+    let parent = parents.last().map(|(_, path, _)| path.as_str());
+    let path = join(parent.unwrap_or(""), key);
+    lines.insert(path.clone(), number);
+    for key in flow_keys(value) {
+      lines.insert(join(&path, key), number);
+    }
+    parents.push((indent, path, false));
+    if value.starts_with('|') || value.starts_with('>') {
+      block_scalar_indent = Some(indent);
+    }
+  }
+  lines
+}
+
+// `None` for the lines without a key, flow collections included
+fn split_key(content: &str) -> Option<(&str, &str)> {
+  let (key, rest) = match content.chars().next()? {
+    '{' | '[' => return None,
+    quote @ ('"' | '\'') => {
+      let end = content[1..].find(quote)? + 1;
+      (&content[1..end], content[end + 1..].trim_start())
+    }
+    _ => {
+      let end = content
+        .find(": ")
+        .or_else(|| content.ends_with(':').then(|| content.len() - 1))?;
+      (content[..end].trim_end(), &content[end..])
+    }
+  };
+  let value = rest.strip_prefix(':')?;
+  if !value.is_empty() && !value.starts_with(' ') {
+    return None;
+  }
+  Some((key, value.trim_start()))
+}
+
+// keys of a flow map written on a single line, without the nested ones
+fn flow_keys(flow: &str) -> Vec<&str> {
+  let mut keys = Vec::new();
+  let Some(inner) = flow.strip_prefix('{') else {
+    return keys;
+  };
+  let mut depth = 0;
+  let mut quote = None;
+  let mut start = 0;
+  for (i, c) in inner.char_indices() {
+    match (quote, c) {
+      (Some(open), c) if c == open => quote = None,
+      (Some(_), _) => {}
+      (None, '"' | '\'') => quote = Some(c),
+      (None, '{' | '[') => depth += 1,
+      (None, '}' | ']') if depth > 0 => depth -= 1,
+      (None, '}' | ',') if depth == 0 => {
+        if let Some((key, _)) = split_key(inner[start..i].trim()) {
+          keys.push(key);
+        }
+        if c == '}' {
+          break;
+        }
+        start = i + 1;
+      }
+      _ => {}
+    }
+  }
+  keys
+}
+
+fn join(parent: &str, key: &str) -> String {
+  if parent.is_empty() {
+    key.to_string()
+  } else {
+    format!("{parent}.{key}")
+  }
+}
@@ -38,7 +38,7 @@ impl<'de> Deserialize<'de> for RichText {
           let bytes = v.as_bytes();
           loop {
             if end >= bytes.len() {
-              panic!("didn't find closing brace");
+              return Err(E::custom(format!("unclosed `${{` in `{v}`")));
             }
             if bytes[end] == b'{' {
               depth += 1;
@@ -50,7 +50,8 @@ impl<'de> Deserialize<'de> for RichText {
             }
             end += 1;
           }
-          let expr = parse(&v[i + 2..end], None, &Table::default());
+          let expr = parse(&v[i + 2..end], None, &Table::default())
+            .map_err(E::custom)?;
           parts.push(RichTextPart::dynamic(expr));
           i = end + 1;
         }
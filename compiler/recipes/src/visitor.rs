@@ -0,0 +1,948 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::ir::{
+  AnonymousFunction, Arithmetic, ArithmeticInner, Binary, Comparison,
+  ComparisonInner, Component, ComponentChild, ComponentChildInner,
+  ComponentCommon, ComponentZone, Control, ControlInner, Expression,
+  ExpressionInner, Format, FormatCommon, FormatInner, FunctionCall, Getter, If,
+  KeyFormat, Keys, List, Logic, LogicInner, Model, NamedFunction, Object,
+  Operands, RichText, RichTextInner, Value, ValueInner,
+};
+
+// walks the whole IR, overriding a `visit_*` method stops the walk below its
+// node unless it calls the matching `walk_*` function
+pub trait Visitor<'a> {
+  fn visit_model(&mut self, model: &'a Model) {
+    walk_model(self, model)
+  }
+
+  fn visit_component(&mut self, component: &'a Component) {
+    walk_component(self, component)
+  }
+
+  fn visit_keys(&mut self, keys: &'a Keys) {
+    walk_keys(self, keys)
+  }
+
+  fn visit_key_format(&mut self, key_format: &'a KeyFormat) {
+    walk_key_format(self, key_format)
+  }
+
+  fn visit_format(&mut self, format: &'a Format) {
+    walk_format(self, format)
+  }
+
+  fn visit_format_common(&mut self, common: &'a FormatCommon) {
+    walk_format_common(self, common)
+  }
+
+  fn visit_zone(&mut self, zone: &'a ComponentZone) {
+    walk_zone(self, zone)
+  }
+
+  fn visit_component_common(&mut self, common: &'a ComponentCommon) {
+    walk_component_common(self, common)
+  }
+
+  fn visit_child(&mut self, child: &'a ComponentChild) {
+    walk_child(self, child)
+  }
+
+  fn visit_expression(&mut self, expression: &'a Expression) {
+    walk_expression(self, expression)
+  }
+
+  fn visit_value(&mut self, value: &'a Value) {
+    walk_value(self, value)
+  }
+
+  fn visit_rich_text(&mut self, text: &'a RichText) {
+    walk_rich_text(self, text)
+  }
+
+  fn visit_getter(&mut self, getter: &'a Getter) {
+    walk_getter(self, getter)
+  }
+
+  fn visit_list(&mut self, list: &'a List) {
+    walk_list(self, list)
+  }
+
+  fn visit_object(&mut self, object: &'a Object) {
+    walk_object(self, object)
+  }
+
+  fn visit_comparison(&mut self, comparison: &'a Comparison) {
+    walk_comparison(self, comparison)
+  }
+
+  fn visit_logic(&mut self, logic: &'a Logic) {
+    walk_logic(self, logic)
+  }
+
+  fn visit_control(&mut self, control: &'a Control) {
+    walk_control(self, control)
+  }
+
+  fn visit_if(&mut self, r#if: &'a If) {
+    walk_if(self, r#if)
+  }
+
+  fn visit_anonymous_function(&mut self, function: &'a AnonymousFunction) {
+    walk_anonymous_function(self, function)
+  }
+
+  fn visit_named_function(&mut self, function: &'a NamedFunction) {
+    walk_named_function(self, function)
+  }
+
+  fn visit_function_call(&mut self, call: &'a FunctionCall) {
+    walk_function_call(self, call)
+  }
+
+  fn visit_arithmetic(&mut self, arithmetic: &'a Arithmetic) {
+    walk_arithmetic(self, arithmetic)
+  }
+
+  fn visit_binary(&mut self, binary: &'a Binary) {
+    walk_binary(self, binary)
+  }
+
+  fn visit_operands(&mut self, operands: &'a Operands) {
+    walk_operands(self, operands)
+  }
+}
+
+pub fn walk_model<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  model: &'a Model,
+) {
+  for component in model.components.iter() {
+    visitor.visit_component(component);
+  }
+}
+
+pub fn walk_component<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  component: &'a Component,
+) {
+  if let Some(properties) = component.properties.as_ref() {
+    visitor.visit_keys(properties);
+  }
+  if let Some(variables) = component.variables.as_ref() {
+    visitor.visit_keys(variables);
+  }
+  if let Some(zone) = component.zone.as_ref() {
+    visitor.visit_zone(zone);
+  }
+}
+
+pub fn walk_keys<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, keys: &'a Keys) {
+  for key_format in keys.keys.iter() {
+    visitor.visit_key_format(key_format);
+  }
+  for default in keys.required.values() {
+    visitor.visit_expression(default);
+  }
+}
+
+pub fn walk_key_format<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  key_format: &'a KeyFormat,
+) {
+  if let Some(format) = key_format.format.as_ref() {
+    visitor.visit_format(format);
+  }
+}
+
+pub fn walk_format<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  format: &'a Format,
+) {
+  let Some(format_inner) = format.format_inner.as_ref() else {
+    return;
+  };
+  let common = match format_inner {
+    FormatInner::Any(common) => Some(common),
+    FormatInner::Boolean(format) => format.common.as_ref(),
+    FormatInner::Choices(format) => format.common.as_ref(),
+    FormatInner::Date(format) => format.common.as_ref(),
+    FormatInner::Index(format) => {
+      if let Some(format) = format.format.as_deref() {
+        visitor.visit_format(format);
+      }
+      format.common.as_ref()
+    }
+    FormatInner::List(format) => {
+      if let Some(format) = format.format.as_deref() {
+        visitor.visit_format(format);
+      }
+      format.common.as_ref()
+    }
+    FormatInner::Object(format) => {
+      for key_format in format.keys.iter() {
+        visitor.visit_key_format(key_format);
+      }
+      for default in format.required.values() {
+        visitor.visit_expression(default);
+      }
+      format.common.as_ref()
+    }
+    FormatInner::Quantity(format) => format.common.as_ref(),
+    FormatInner::Text(format) => format.common.as_ref(),
+  };
+  if let Some(common) = common {
+    visitor.visit_format_common(common);
+  }
+}
+
+pub fn walk_format_common<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  common: &'a FormatCommon,
+) {
+  for constraint in common.constraints.iter() {
+    if let Some(assert) = constraint.assert.as_ref() {
+      visitor.visit_expression(assert);
+    }
+  }
+}
+
+pub fn walk_zone<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  zone: &'a ComponentZone,
+) {
+  if let Some(common) = zone.common.as_ref() {
+    visitor.visit_component_common(common);
+  }
+  for child in zone.blocks.iter() {
+    visitor.visit_child(child);
+  }
+}
+
+pub fn walk_component_common<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  common: &'a ComponentCommon,
+) {
+  for class in common.classes.iter() {
+    visitor.visit_rich_text(class);
+  }
+}
+
+pub fn walk_child<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  child: &'a ComponentChild,
+) {
+  let Some(child_inner) = child.component_child_inner.as_ref() else {
+    return;
+  };
+  let common = match child_inner {
+    ComponentChildInner::Text(text) => {
+      if let Some(content) = text.content.as_ref() {
+        visitor.visit_rich_text(content);
+      }
+      text.common.as_ref()
+    }
+    ComponentChildInner::Input(input) => {
+      if let Some(on_change) = input.on_change.as_ref() {
+        visitor.visit_getter(on_change);
+      }
+      input.common.as_ref()
+    }
+    ComponentChildInner::Extern(r#extern) => {
+      if let Some(properties) = r#extern.properties.as_ref() {
+        visitor.visit_object(properties);
+      }
+      r#extern.common.as_ref()
+    }
+  };
+  if let Some(common) = common {
+    visitor.visit_component_common(common);
+  }
+}
+
+pub fn walk_expression<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  expression: &'a Expression,
+) {
+  match expression.expression_inner.as_ref() {
+    Some(ExpressionInner::Value(value)) => visitor.visit_value(value),
+    Some(ExpressionInner::Comparison(comparison)) => {
+      visitor.visit_comparison(comparison)
+    }
+    Some(ExpressionInner::Logic(logic)) => visitor.visit_logic(logic),
+    Some(ExpressionInner::Control(control)) => visitor.visit_control(control),
+    Some(ExpressionInner::Arithmetic(arithmetic)) => {
+      visitor.visit_arithmetic(arithmetic)
+    }
+    None => {}
+  }
+}
+
+pub fn walk_value<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  value: &'a Value,
+) {
+  match value.value_inner.as_ref() {
+    Some(ValueInner::Text(text)) => visitor.visit_rich_text(text),
+    Some(ValueInner::Getter(getter)) => visitor.visit_getter(getter),
+    Some(ValueInner::List(list)) => visitor.visit_list(list),
+    Some(ValueInner::Object(object)) => visitor.visit_object(object),
+    Some(
+      ValueInner::Quantity(_)
+      | ValueInner::Boolean(_)
+      | ValueInner::Undefined(_),
+    )
+    | None => {}
+  }
+}
+
+pub fn walk_rich_text<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  text: &'a RichText,
+) {
+  for part in text.parts.iter() {
+    if let Some(RichTextInner::Dynamic(expression)) =
+      part.rich_text_inner.as_ref()
+    {
+      visitor.visit_expression(expression);
+    }
+  }
+}
+
+pub fn walk_getter<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  getter: &'a Getter,
+) {
+  for index in getter.indexes.iter() {
+    visitor.visit_expression(index);
+  }
+}
+
+pub fn walk_list<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, list: &'a List) {
+  for value in list.values.iter() {
+    visitor.visit_expression(value);
+  }
+}
+
+pub fn walk_object<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  object: &'a Object,
+) {
+  for value in object.values.values() {
+    visitor.visit_expression(value);
+  }
+}
+
+pub fn walk_comparison<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  comparison: &'a Comparison,
+) {
+  match comparison.comparison_inner.as_ref() {
+    Some(
+      ComparisonInner::EqualsTo(binary)
+      | ComparisonInner::DifferentFrom(binary)
+      | ComparisonInner::In(binary)
+      | ComparisonInner::LessThan(binary)
+      | ComparisonInner::MoreThan(binary)
+      | ComparisonInner::AtLeast(binary)
+      | ComparisonInner::AtMost(binary),
+    ) => visitor.visit_binary(binary),
+    None => {}
+  }
+}
+
+pub fn walk_logic<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  logic: &'a Logic,
+) {
+  match logic.logic_inner.as_ref() {
+    Some(LogicInner::And(operands) | LogicInner::Or(operands)) => {
+      visitor.visit_operands(operands)
+    }
+    Some(LogicInner::Not(expression) | LogicInner::Exists(expression)) => {
+      visitor.visit_expression(expression)
+    }
+    None => {}
+  }
+}
+
+pub fn walk_control<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  control: &'a Control,
+) {
+  match control.control_inner.as_ref() {
+    Some(ControlInner::If(r#if)) => visitor.visit_if(r#if),
+    Some(ControlInner::AnonymousFunction(function)) => {
+      visitor.visit_anonymous_function(function)
+    }
+    Some(ControlInner::NamedFunction(function)) => {
+      visitor.visit_named_function(function)
+    }
+    Some(ControlInner::FunctionCall(call)) => visitor.visit_function_call(call),
+    None => {}
+  }
+}
+
+pub fn walk_if<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, r#if: &'a If) {
+  for expression in [
+    r#if.condition.as_deref(),
+    r#if.then.as_deref(),
+    r#if.r#else.as_deref(),
+  ]
+  .into_iter()
+  .flatten()
+  {
+    visitor.visit_expression(expression);
+  }
+}
+
+pub fn walk_anonymous_function<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  function: &'a AnonymousFunction,
+) {
+  if let Some(body) = function.body.as_deref() {
+    visitor.visit_expression(body);
+  }
+}
+
+pub fn walk_named_function<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  function: &'a NamedFunction,
+) {
+  if let Some(body) = function.body.as_deref() {
+    visitor.visit_expression(body);
+  }
+}
+
+pub fn walk_function_call<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  call: &'a FunctionCall,
+) {
+  if let Some(function) = call.function.as_deref() {
+    visitor.visit_expression(function);
+  }
+  for arg in call.args.iter() {
+    visitor.visit_expression(arg);
+  }
+}
+
+pub fn walk_arithmetic<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  arithmetic: &'a Arithmetic,
+) {
+  match arithmetic.arithmetic_inner.as_ref() {
+    Some(ArithmeticInner::Opposite(expression)) => {
+      visitor.visit_expression(expression)
+    }
+    Some(
+      ArithmeticInner::Add(binary)
+      | ArithmeticInner::Sub(binary)
+      | ArithmeticInner::Mul(binary)
+      | ArithmeticInner::Div(binary)
+      | ArithmeticInner::Mod(binary)
+      | ArithmeticInner::Pow(binary),
+    ) => visitor.visit_binary(binary),
+    None => {}
+  }
+}
+
+pub fn walk_binary<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  binary: &'a Binary,
+) {
+  if let Some(left) = binary.left.as_deref() {
+    visitor.visit_expression(left);
+  }
+  if let Some(right) = binary.right.as_deref() {
+    visitor.visit_expression(right);
+  }
+}
+
+pub fn walk_operands<'a, V: Visitor<'a> + ?Sized>(
+  visitor: &mut V,
+  operands: &'a Operands,
+) {
+  for operand in operands.operands.iter() {
+    visitor.visit_expression(operand);
+  }
+}
+
+// same walk as `Visitor`, for rewriting the IR in place
+pub trait VisitorMut {
+  fn visit_model_mut(&mut self, model: &mut Model) {
+    walk_model_mut(self, model)
+  }
+
+  fn visit_component_mut(&mut self, component: &mut Component) {
+    walk_component_mut(self, component)
+  }
+
+  fn visit_keys_mut(&mut self, keys: &mut Keys) {
+    walk_keys_mut(self, keys)
+  }
+
+  fn visit_key_format_mut(&mut self, key_format: &mut KeyFormat) {
+    walk_key_format_mut(self, key_format)
+  }
+
+  fn visit_format_mut(&mut self, format: &mut Format) {
+    walk_format_mut(self, format)
+  }
+
+  fn visit_format_common_mut(&mut self, common: &mut FormatCommon) {
+    walk_format_common_mut(self, common)
+  }
+
+  fn visit_zone_mut(&mut self, zone: &mut ComponentZone) {
+    walk_zone_mut(self, zone)
+  }
+
+  fn visit_component_common_mut(&mut self, common: &mut ComponentCommon) {
+    walk_component_common_mut(self, common)
+  }
+
+  fn visit_child_mut(&mut self, child: &mut ComponentChild) {
+    walk_child_mut(self, child)
+  }
+
+  fn visit_expression_mut(&mut self, expression: &mut Expression) {
+    walk_expression_mut(self, expression)
+  }
+
+  fn visit_value_mut(&mut self, value: &mut Value) {
+    walk_value_mut(self, value)
+  }
+
+  fn visit_rich_text_mut(&mut self, text: &mut RichText) {
+    walk_rich_text_mut(self, text)
+  }
+
+  fn visit_getter_mut(&mut self, getter: &mut Getter) {
+    walk_getter_mut(self, getter)
+  }
+
+  fn visit_list_mut(&mut self, list: &mut List) {
+    walk_list_mut(self, list)
+  }
+
+  fn visit_object_mut(&mut self, object: &mut Object) {
+    walk_object_mut(self, object)
+  }
+
+  fn visit_comparison_mut(&mut self, comparison: &mut Comparison) {
+    walk_comparison_mut(self, comparison)
+  }
+
+  fn visit_logic_mut(&mut self, logic: &mut Logic) {
+    walk_logic_mut(self, logic)
+  }
+
+  fn visit_control_mut(&mut self, control: &mut Control) {
+    walk_control_mut(self, control)
+  }
+
+  fn visit_if_mut(&mut self, r#if: &mut If) {
+    walk_if_mut(self, r#if)
+  }
+
+  fn visit_anonymous_function_mut(&mut self, function: &mut AnonymousFunction) {
+    walk_anonymous_function_mut(self, function)
+  }
+
+  fn visit_named_function_mut(&mut self, function: &mut NamedFunction) {
+    walk_named_function_mut(self, function)
+  }
+
+  fn visit_function_call_mut(&mut self, call: &mut FunctionCall) {
+    walk_function_call_mut(self, call)
+  }
+
+  fn visit_arithmetic_mut(&mut self, arithmetic: &mut Arithmetic) {
+    walk_arithmetic_mut(self, arithmetic)
+  }
+
+  fn visit_binary_mut(&mut self, binary: &mut Binary) {
+    walk_binary_mut(self, binary)
+  }
+
+  fn visit_operands_mut(&mut self, operands: &mut Operands) {
+    walk_operands_mut(self, operands)
+  }
+}
+
+pub fn walk_model_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  model: &mut Model,
+) {
+  for component in model.components.iter_mut() {
+    visitor.visit_component_mut(component);
+  }
+}
+
+pub fn walk_component_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  component: &mut Component,
+) {
+  if let Some(properties) = component.properties.as_mut() {
+    visitor.visit_keys_mut(properties);
+  }
+  if let Some(variables) = component.variables.as_mut() {
+    visitor.visit_keys_mut(variables);
+  }
+  if let Some(zone) = component.zone.as_mut() {
+    visitor.visit_zone_mut(zone);
+  }
+}
+
+pub fn walk_keys_mut<V: VisitorMut + ?Sized>(visitor: &mut V, keys: &mut Keys) {
+  for key_format in keys.keys.iter_mut() {
+    visitor.visit_key_format_mut(key_format);
+  }
+  for default in keys.required.values_mut() {
+    visitor.visit_expression_mut(default);
+  }
+}
+
+pub fn walk_key_format_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  key_format: &mut KeyFormat,
+) {
+  if let Some(format) = key_format.format.as_mut() {
+    visitor.visit_format_mut(format);
+  }
+}
+
+pub fn walk_format_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  format: &mut Format,
+) {
+  let Some(format_inner) = format.format_inner.as_mut() else {
+    return;
+  };
+  let common = match format_inner {
+    FormatInner::Any(common) => Some(common),
+    FormatInner::Boolean(format) => format.common.as_mut(),
+    FormatInner::Choices(format) => format.common.as_mut(),
+    FormatInner::Date(format) => format.common.as_mut(),
+    FormatInner::Index(format) => {
+      if let Some(format) = format.format.as_deref_mut() {
+        visitor.visit_format_mut(format);
+      }
+      format.common.as_mut()
+    }
+    FormatInner::List(format) => {
+      if let Some(format) = format.format.as_deref_mut() {
+        visitor.visit_format_mut(format);
+      }
+      format.common.as_mut()
+    }
+    FormatInner::Object(format) => {
+      for key_format in format.keys.iter_mut() {
+        visitor.visit_key_format_mut(key_format);
+      }
+      for default in format.required.values_mut() {
+        visitor.visit_expression_mut(default);
+      }
+      format.common.as_mut()
+    }
+    FormatInner::Quantity(format) => format.common.as_mut(),
+    FormatInner::Text(format) => format.common.as_mut(),
+  };
+  if let Some(common) = common {
+    visitor.visit_format_common_mut(common);
+  }
+}
+
+pub fn walk_format_common_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  common: &mut FormatCommon,
+) {
+  for constraint in common.constraints.iter_mut() {
+    if let Some(assert) = constraint.assert.as_mut() {
+      visitor.visit_expression_mut(assert);
+    }
+  }
+}
+
+pub fn walk_zone_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  zone: &mut ComponentZone,
+) {
+  if let Some(common) = zone.common.as_mut() {
+    visitor.visit_component_common_mut(common);
+  }
+  for child in zone.blocks.iter_mut() {
+    visitor.visit_child_mut(child);
+  }
+}
+
+pub fn walk_component_common_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  common: &mut ComponentCommon,
+) {
+  for class in common.classes.iter_mut() {
+    visitor.visit_rich_text_mut(class);
+  }
+}
+
+pub fn walk_child_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  child: &mut ComponentChild,
+) {
+  let Some(child_inner) = child.component_child_inner.as_mut() else {
+    return;
+  };
+  let common = match child_inner {
+    ComponentChildInner::Text(text) => {
+      if let Some(content) = text.content.as_mut() {
+        visitor.visit_rich_text_mut(content);
+      }
+      text.common.as_mut()
+    }
+    ComponentChildInner::Input(input) => {
+      if let Some(on_change) = input.on_change.as_mut() {
+        visitor.visit_getter_mut(on_change);
+      }
+      input.common.as_mut()
+    }
+    ComponentChildInner::Extern(r#extern) => {
+      if let Some(properties) = r#extern.properties.as_mut() {
+        visitor.visit_object_mut(properties);
+      }
+      r#extern.common.as_mut()
+    }
+  };
+  if let Some(common) = common {
+    visitor.visit_component_common_mut(common);
+  }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  expression: &mut Expression,
+) {
+  match expression.expression_inner.as_mut() {
+    Some(ExpressionInner::Value(value)) => visitor.visit_value_mut(value),
+    Some(ExpressionInner::Comparison(comparison)) => {
+      visitor.visit_comparison_mut(comparison)
+    }
+    Some(ExpressionInner::Logic(logic)) => visitor.visit_logic_mut(logic),
+    Some(ExpressionInner::Control(control)) => {
+      visitor.visit_control_mut(control)
+    }
+    Some(ExpressionInner::Arithmetic(arithmetic)) => {
+      visitor.visit_arithmetic_mut(arithmetic)
+    }
+    None => {}
+  }
+}
+
+pub fn walk_value_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  value: &mut Value,
+) {
+  match value.value_inner.as_mut() {
+    Some(ValueInner::Text(text)) => visitor.visit_rich_text_mut(text),
+    Some(ValueInner::Getter(getter)) => visitor.visit_getter_mut(getter),
+    Some(ValueInner::List(list)) => visitor.visit_list_mut(list),
+    Some(ValueInner::Object(object)) => visitor.visit_object_mut(object),
+    Some(
+      ValueInner::Quantity(_)
+      | ValueInner::Boolean(_)
+      | ValueInner::Undefined(_),
+    )
+    | None => {}
+  }
+}
+
+pub fn walk_rich_text_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  text: &mut RichText,
+) {
+  for part in text.parts.iter_mut() {
+    if let Some(RichTextInner::Dynamic(expression)) =
+      part.rich_text_inner.as_mut()
+    {
+      visitor.visit_expression_mut(expression);
+    }
+  }
+}
+
+pub fn walk_getter_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  getter: &mut Getter,
+) {
+  for index in getter.indexes.iter_mut() {
+    visitor.visit_expression_mut(index);
+  }
+}
+
+pub fn walk_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut List) {
+  for value in list.values.iter_mut() {
+    visitor.visit_expression_mut(value);
+  }
+}
+
+pub fn walk_object_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  object: &mut Object,
+) {
+  for value in object.values.values_mut() {
+    visitor.visit_expression_mut(value);
+  }
+}
+
+pub fn walk_comparison_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  comparison: &mut Comparison,
+) {
+  match comparison.comparison_inner.as_mut() {
+    Some(
+      ComparisonInner::EqualsTo(binary)
+      | ComparisonInner::DifferentFrom(binary)
+      | ComparisonInner::In(binary)
+      | ComparisonInner::LessThan(binary)
+      | ComparisonInner::MoreThan(binary)
+      | ComparisonInner::AtLeast(binary)
+      | ComparisonInner::AtMost(binary),
+    ) => visitor.visit_binary_mut(binary),
+    None => {}
+  }
+}
+
+pub fn walk_logic_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  logic: &mut Logic,
+) {
+  match logic.logic_inner.as_mut() {
+    Some(LogicInner::And(operands) | LogicInner::Or(operands)) => {
+      visitor.visit_operands_mut(operands)
+    }
+    Some(LogicInner::Not(expression) | LogicInner::Exists(expression)) => {
+      visitor.visit_expression_mut(expression)
+    }
+    None => {}
+  }
+}
+
+pub fn walk_control_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  control: &mut Control,
+) {
+  match control.control_inner.as_mut() {
+    Some(ControlInner::If(r#if)) => visitor.visit_if_mut(r#if),
+    Some(ControlInner::AnonymousFunction(function)) => {
+      visitor.visit_anonymous_function_mut(function)
+    }
+    Some(ControlInner::NamedFunction(function)) => {
+      visitor.visit_named_function_mut(function)
+    }
+    Some(ControlInner::FunctionCall(call)) => {
+      visitor.visit_function_call_mut(call)
+    }
+    None => {}
+  }
+}
+
+pub fn walk_if_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#if: &mut If) {
+  for expression in [
+    r#if.condition.as_deref_mut(),
+    r#if.then.as_deref_mut(),
+    r#if.r#else.as_deref_mut(),
+  ]
+  .into_iter()
+  .flatten()
+  {
+    visitor.visit_expression_mut(expression);
+  }
+}
+
+pub fn walk_anonymous_function_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  function: &mut AnonymousFunction,
+) {
+  if let Some(body) = function.body.as_deref_mut() {
+    visitor.visit_expression_mut(body);
+  }
+}
+
+pub fn walk_named_function_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  function: &mut NamedFunction,
+) {
+  if let Some(body) = function.body.as_deref_mut() {
+    visitor.visit_expression_mut(body);
+  }
+}
+
+pub fn walk_function_call_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  call: &mut FunctionCall,
+) {
+  if let Some(function) = call.function.as_deref_mut() {
+    visitor.visit_expression_mut(function);
+  }
+  for arg in call.args.iter_mut() {
+    visitor.visit_expression_mut(arg);
+  }
+}
+
+pub fn walk_arithmetic_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  arithmetic: &mut Arithmetic,
+) {
+  match arithmetic.arithmetic_inner.as_mut() {
+    Some(ArithmeticInner::Opposite(expression)) => {
+      visitor.visit_expression_mut(expression)
+    }
+    Some(
+      ArithmeticInner::Add(binary)
+      | ArithmeticInner::Sub(binary)
+      | ArithmeticInner::Mul(binary)
+      | ArithmeticInner::Div(binary)
+      | ArithmeticInner::Mod(binary)
+      | ArithmeticInner::Pow(binary),
+    ) => visitor.visit_binary_mut(binary),
+    None => {}
+  }
+}
+
+pub fn walk_binary_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  binary: &mut Binary,
+) {
+  if let Some(left) = binary.left.as_deref_mut() {
+    visitor.visit_expression_mut(left);
+  }
+  if let Some(right) = binary.right.as_deref_mut() {
+    visitor.visit_expression_mut(right);
+  }
+}
+
+pub fn walk_operands_mut<V: VisitorMut + ?Sized>(
+  visitor: &mut V,
+  operands: &mut Operands,
+) {
+  for operand in operands.operands.iter_mut() {
+    visitor.visit_expression_mut(operand);
+  }
+}
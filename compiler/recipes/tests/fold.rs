@@ -0,0 +1,93 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_compiler_recipes::{
+  fold::fold_expression,
+  ir::{Expression, RichTextPart},
+  parser::parse_expression,
+};
+
+fn fold(input: &str) -> Expression {
+  let mut expression = parse_expression(input, None).unwrap();
+  fold_expression(&mut expression);
+  expression
+}
+
+fn assert_folds(input: &str, expected: &str) {
+  assert_eq!(
+    fold(input),
+    parse_expression(expected, None).unwrap(),
+    "{input}"
+  );
+}
+
+#[test]
+fn constants() {
+  assert_folds("1 + 2", "3");
+  assert_eq!(fold("5 - 7"), Expression::quantity(-2.));
+  assert_folds(r#""a" + "b""#, r#""ab""#);
+  assert_folds("3 < 4", "true");
+  assert_folds(r#""a" == "a""#, "true");
+  assert_folds("!(!(a < b))", "a < b");
+  assert_folds("!(!x)", "!(!x)");
+}
+
+#[test]
+fn euclidean_modulo() {
+  assert_folds("7 % 3", "1");
+  assert_folds("(0 - 7) % 3", "2");
+  assert_folds("7 % (0 - 3)", "1");
+}
+
+#[test]
+fn logic() {
+  assert_folds("a & true & (b & c)", "a & b & c");
+  assert_folds("a | true", "true");
+  assert_folds("a & !true", "false");
+}
+
+#[test]
+fn function_calls() {
+  assert_folds("f() | true", "f() | true");
+  assert_folds("f() & false", "f() & false");
+  assert_folds("a & f(b) & false & g()", "a & f(b) & false");
+  assert_folds("true | f()", "true");
+  assert_folds("f() & true", "f()");
+}
+
+#[test]
+fn dead_branches() {
+  assert_folds("if true: a else: b", "a");
+  assert_folds("if 1 > 2: a else: b", "b");
+}
+
+#[test]
+fn rich_texts() {
+  assert_eq!(
+    fold(r#""a${true}b""#),
+    Expression::text(vec![
+      RichTextPart::r#static("a".into()),
+      RichTextPart::dynamic(Expression::boolean(true)),
+      RichTextPart::r#static("b".into()),
+    ]),
+  );
+  assert_folds(r#""a${1 + 1}""#, r#""a${2}""#);
+}
@@ -0,0 +1,322 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_compiler_recipes::{
+  ir::{
+    ArithmeticInner, Binary, ComparisonInner, Expression, ExpressionInner,
+    LogicInner, RichTextPart, ValueInner,
+  },
+  parser::parse_expression,
+};
+
+fn getter(ident: &str) -> Expression {
+  Expression::getter(ident.into(), vec![])
+}
+
+#[test]
+fn arithmetic() {
+  let [a, b, c] = ["a", "b", "c"].map(getter);
+  let cases = [
+    (
+      "a + b * c",
+      Expression::add(a.clone(), Expression::mul(b.clone(), c.clone())),
+    ),
+    (
+      "a - b - c",
+      Expression::sub(Expression::sub(a.clone(), b.clone()), c.clone()),
+    ),
+    (
+      "a ^ b ^ c",
+      Expression::pow(a.clone(), Expression::pow(b.clone(), c.clone())),
+    ),
+    (
+      "-a % b",
+      Expression::r#mod(Expression::opposite(a.clone()), b.clone()),
+    ),
+    (
+      "-a ^ b",
+      Expression::opposite(Expression::pow(a.clone(), b.clone())),
+    ),
+    (
+      "a * -b",
+      Expression::mul(a.clone(), Expression::opposite(b.clone())),
+    ),
+    (
+      "2 - -a",
+      Expression::sub(
+        Expression::quantity(2.),
+        Expression::opposite(a.clone()),
+      ),
+    ),
+    (
+      "a ^ -b",
+      Expression::pow(a.clone(), Expression::opposite(b.clone())),
+    ),
+    (
+      "(a + b) * c",
+      Expression::mul(Expression::add(a.clone(), b.clone()), c.clone()),
+    ),
+    (
+      "a / b * c",
+      Expression::mul(Expression::div(a.clone(), b.clone()), c.clone()),
+    ),
+    (
+      "a < b + 1",
+      Expression::less_than(
+        a.clone(),
+        Expression::add(b.clone(), Expression::quantity(1.)),
+      ),
+    ),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(parse_expression(input, None).unwrap(), expected, "{input}");
+  }
+}
+
+#[test]
+fn logic() {
+  let [a, b, c, d] = ["a", "b", "c", "d"].map(getter);
+  let cases = [
+    (
+      "a & b | c",
+      Expression::or(vec![
+        Expression::and(vec![a.clone(), b.clone()]),
+        c.clone(),
+      ]),
+    ),
+    (
+      "a | b & c",
+      Expression::or(vec![
+        a.clone(),
+        Expression::and(vec![b.clone(), c.clone()]),
+      ]),
+    ),
+    (
+      "a | b & c | d",
+      Expression::or(vec![
+        a.clone(),
+        Expression::and(vec![b.clone(), c.clone()]),
+        d.clone(),
+      ]),
+    ),
+    (
+      "a & b & c",
+      Expression::and(vec![a.clone(), b.clone(), c.clone()]),
+    ),
+    (
+      "(a | b) & c",
+      Expression::and(vec![
+        Expression::or(vec![a.clone(), b.clone()]),
+        c.clone(),
+      ]),
+    ),
+    (
+      "a & (b & c)",
+      Expression::and(vec![
+        a.clone(),
+        Expression::and(vec![b.clone(), c.clone()]),
+      ]),
+    ),
+    (
+      "!a | b",
+      Expression::or(vec![Expression::not(a.clone()), b.clone()]),
+    ),
+    (
+      "a == b | c < d",
+      Expression::or(vec![
+        Expression::equals_to(a.clone(), b.clone()),
+        Expression::less_than(c.clone(), d.clone()),
+      ]),
+    ),
+    (
+      "a + 1 == b & c",
+      Expression::and(vec![
+        Expression::equals_to(
+          Expression::add(a.clone(), Expression::quantity(1.)),
+          b.clone(),
+        ),
+        c.clone(),
+      ]),
+    ),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(parse_expression(input, None).unwrap(), expected, "{input}");
+  }
+}
+
+// `.key` is a static key, `[key]` the value of an expression
+#[test]
+fn indexes() {
+  let key =
+    |key: &str| Expression::text(vec![RichTextPart::r#static(key.into())]);
+  let cases = [
+    ("a.b", Expression::getter("a".into(), vec![key("b")])),
+    ("a[b]", Expression::getter("a".into(), vec![getter("b")])),
+    (
+      "a[b.c].d",
+      Expression::getter(
+        "a".into(),
+        vec![Expression::getter("b".into(), vec![key("c")]), key("d")],
+      ),
+    ),
+    (
+      "a[(b + 1)].c",
+      Expression::getter(
+        "a".into(),
+        vec![
+          Expression::add(getter("b"), Expression::quantity(1.)),
+          key("c"),
+        ],
+      ),
+    ),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(parse_expression(input, None).unwrap(), expected, "{input}");
+  }
+}
+
+// printing a tree with every operation parenthesized and parsing it back gives
+// the same tree, whatever the precedence of its operators
+#[test]
+fn round_trip() {
+  let inputs = [
+    "a & b | c",
+    "a | b & c | d",
+    "!a & (b | c)",
+    "a < b & c != d | e",
+    "a + b * c ^ d == e % f",
+    "-a - b / c",
+    "a * -b % -c",
+    "a & (b & c)",
+    "(a | b) | c",
+    "a in b | true & c >= 1",
+  ];
+  for input in inputs {
+    let expression = parse_expression(input, None).unwrap();
+    let mut printed = String::new();
+    print(&mut printed, &expression);
+    assert_eq!(
+      parse_expression(&printed, None).unwrap(),
+      expression,
+      "{printed}"
+    );
+  }
+}
+
+fn print(output: &mut String, expression: &Expression) {
+  match expression.expression_inner.as_ref().unwrap() {
+    ExpressionInner::Value(value) => {
+      match value.value_inner.as_ref().unwrap() {
+        ValueInner::Getter(getter) => output.push_str(&getter.ident),
+        ValueInner::Quantity(quantity) => {
+          output.push_str(&quantity.to_string())
+        }
+        ValueInner::Boolean(boolean) => output.push_str(&boolean.to_string()),
+        value => panic!("unexpected value {value:?}"),
+      }
+    }
+    ExpressionInner::Logic(logic) => {
+      match logic.logic_inner.as_ref().unwrap() {
+        LogicInner::And(operands) => {
+          print_operands(output, " & ", &operands.operands)
+        }
+        LogicInner::Or(operands) => {
+          print_operands(output, " | ", &operands.operands)
+        }
+        LogicInner::Not(operand) => {
+          output.push('!');
+          print_nested(output, operand);
+        }
+        LogicInner::Exists(operand) => {
+          output.push('?');
+          print_nested(output, operand);
+        }
+      }
+    }
+    ExpressionInner::Comparison(comparison) => {
+      match comparison.comparison_inner.as_ref().unwrap() {
+        ComparisonInner::EqualsTo(binary) => {
+          print_binary(output, " == ", binary)
+        }
+        ComparisonInner::DifferentFrom(binary) => {
+          print_binary(output, " != ", binary)
+        }
+        ComparisonInner::In(binary) => print_binary(output, " in ", binary),
+        ComparisonInner::LessThan(binary) => {
+          print_binary(output, " < ", binary)
+        }
+        ComparisonInner::MoreThan(binary) => {
+          print_binary(output, " > ", binary)
+        }
+        ComparisonInner::AtLeast(binary) => {
+          print_binary(output, " >= ", binary)
+        }
+        ComparisonInner::AtMost(binary) => print_binary(output, " <= ", binary),
+      }
+    }
+    ExpressionInner::Arithmetic(arithmetic) => {
+      match arithmetic.arithmetic_inner.as_ref().unwrap() {
+        ArithmeticInner::Opposite(operand) => {
+          output.push('-');
+          print_nested(output, operand);
+        }
+        ArithmeticInner::Add(binary) => print_binary(output, " + ", binary),
+        ArithmeticInner::Sub(binary) => print_binary(output, " - ", binary),
+        ArithmeticInner::Mul(binary) => print_binary(output, " * ", binary),
+        ArithmeticInner::Div(binary) => print_binary(output, " / ", binary),
+        ArithmeticInner::Mod(binary) => print_binary(output, " % ", binary),
+        ArithmeticInner::Pow(binary) => print_binary(output, " ^ ", binary),
+      }
+    }
+    ExpressionInner::Control(control) => {
+      panic!("unexpected control {control:?}")
+    }
+  }
+}
+
+fn print_binary(output: &mut String, operator: &str, binary: &Binary) {
+  print_nested(output, binary.left.as_ref().unwrap());
+  output.push_str(operator);
+  print_nested(output, binary.right.as_ref().unwrap());
+}
+
+fn print_operands(
+  output: &mut String,
+  operator: &str,
+  operands: &[Expression],
+) {
+  for (i, operand) in operands.iter().enumerate() {
+    if i > 0 {
+      output.push_str(operator);
+    }
+    print_nested(output, operand);
+  }
+}
+
+fn print_nested(output: &mut String, expression: &Expression) {
+  if matches!(expression.expression_inner, Some(ExpressionInner::Value(_))) {
+    print(output, expression);
+  } else {
+    output.push('(');
+    print(output, expression);
+    output.push(')');
+  }
+}
@@ -0,0 +1,137 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_component;
+
+fn lines(recipe: &str) -> BTreeMap<String, u32> {
+  let component = parse_component("home", "home.dropin.yml", recipe).unwrap();
+  component.source.unwrap().lines
+}
+
+fn expected(lines: &[(&str, u32)]) -> BTreeMap<String, u32> {
+  lines
+    .iter()
+    .map(|(path, line)| (path.to_string(), *line))
+    .collect()
+}
+
+// the lines of a block scalar are text, even when they look like yaml
+#[test]
+fn block_scalars() {
+  let recipe = r#"blocks:
+  - type: text
+    content: |
+      not: a key
+      - nor an item
+  - type: text
+    content: >
+      folded: text
+"#;
+  assert_eq!(
+    lines(recipe),
+    expected(&[
+      ("", 1),
+      ("blocks", 1),
+      ("blocks.0", 2),
+      ("blocks.0.type", 2),
+      ("blocks.0.content", 3),
+      ("blocks.1", 6),
+      ("blocks.1.type", 6),
+      ("blocks.1.content", 7),
+    ]),
+  );
+}
+
+// the keys of a flow map are on its line, its nested maps aren't scanned
+#[test]
+fn flow_maps() {
+  let recipe = r#"variables:
+  keys:
+    tags: {type: list, format: {type: text}}
+blocks:
+  - {type: text, content: "${tags}"}
+"#;
+  assert_eq!(
+    lines(recipe),
+    expected(&[
+      ("", 1),
+      ("variables", 1),
+      ("variables.keys", 2),
+      ("variables.keys.tags", 3),
+      ("variables.keys.tags.type", 3),
+      ("variables.keys.tags.format", 3),
+      ("blocks", 4),
+      ("blocks.0", 5),
+      ("blocks.0.type", 5),
+      ("blocks.0.content", 5),
+    ]),
+  );
+}
+
+#[test]
+fn comments() {
+  let recipe = r#"# blocks: not a key
+blocks:
+  # - not an item
+  - type: text # a trailing comment
+    content: hello
+"#;
+  assert_eq!(
+    lines(recipe),
+    expected(&[
+      ("", 1),
+      ("blocks", 2),
+      ("blocks.0", 4),
+      ("blocks.0.type", 4),
+      ("blocks.0.content", 5),
+    ]),
+  );
+}
+
+// a quoted key may contain `: `
+#[test]
+fn quoted_keys() {
+  let recipe = r#"variables:
+  required:
+    "a: b": '"x"'
+  keys:
+    "a: b":
+      type: text
+    'c':
+      type: quantity
+"#;
+  assert_eq!(
+    lines(recipe),
+    expected(&[
+      ("", 1),
+      ("variables", 1),
+      ("variables.required", 2),
+      ("variables.required.a: b", 3),
+      ("variables.keys", 4),
+      ("variables.keys.a: b", 5),
+      ("variables.keys.a: b.type", 6),
+      ("variables.keys.c", 7),
+      ("variables.keys.c.type", 8),
+    ]),
+  );
+}
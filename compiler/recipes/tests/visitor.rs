@@ -0,0 +1,288 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::{
+  ir::{
+    Component, ComponentChild, ComponentCommon, ComponentZone, Expression,
+    ExpressionInner, Format, Getter, KeyFormat, Keys, Model, Object, RichText,
+    Value, ValueInner,
+  },
+  parser::{parse_expression, parse_model_from_files},
+  visitor::{
+    walk_child, walk_component, walk_component_common, walk_format,
+    walk_getter, walk_getter_mut, walk_key_format, walk_key_format_mut,
+    walk_keys, walk_object, walk_rich_text, walk_value_mut, walk_zone, Visitor,
+    VisitorMut,
+  },
+};
+
+#[derive(Default)]
+struct Getters<'a>(Vec<&'a str>);
+
+impl<'a> Visitor<'a> for Getters<'a> {
+  fn visit_getter(&mut self, getter: &'a Getter) {
+    self.0.push(&getter.ident);
+    walk_getter(self, getter)
+  }
+}
+
+struct Rename;
+
+impl VisitorMut for Rename {
+  fn visit_value_mut(&mut self, value: &mut Value) {
+    if let Some(ValueInner::Getter(getter)) = value.value_inner.as_mut() {
+      getter.ident = getter.ident.to_uppercase();
+    }
+    walk_value_mut(self, value)
+  }
+}
+
+fn getters(expression: &Expression) -> Vec<&str> {
+  let mut getters = Getters::default();
+  getters.visit_expression(expression);
+  getters.0
+}
+
+#[test]
+fn walks_nested_getters() {
+  let expression = parse_expression("a == b & !c", None).unwrap();
+  assert!(matches!(
+    expression.expression_inner,
+    Some(ExpressionInner::Logic(_))
+  ));
+  assert_eq!(getters(&expression), ["a", "b", "c"]);
+}
+
+#[test]
+fn rewrites_in_place() {
+  let mut expression = parse_expression("a == b & !c", None).unwrap();
+  Rename.visit_expression_mut(&mut expression);
+  assert_eq!(getters(&expression), ["A", "B", "C"]);
+}
+
+const MODEL: &str = r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+
+const HOME: &str = r#"
+variables:
+  required:
+    user: ''
+  keys:
+    user:
+      type: object
+      required:
+        name: '"anon"'
+      keys:
+        name:
+          type: text
+        tags:
+          type: list
+          format:
+            type: text
+classes:
+  - "row ${user.name}"
+blocks:
+  - type: text
+    content: "Hello ${user.name}"
+  - type: input
+    on_change: user.tags[0]
+  - type: component
+    id: child
+    properties:
+      value: user.name
+"#;
+
+const CHILD: &str = r#"
+properties:
+  required:
+    value: '"x"'
+  keys:
+    value:
+      type: text
+blocks:
+  - type: text
+    classes:
+      - bold
+    content: "${value}"
+"#;
+
+fn model() -> Model {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+    ("components/child.dropin.yml".into(), CHILD.into()),
+  ]);
+  parse_model_from_files(&files).unwrap()
+}
+
+#[derive(Default)]
+struct Trace(Vec<String>);
+
+impl<'a> Visitor<'a> for Trace {
+  fn visit_component(&mut self, component: &'a Component) {
+    self.0.push(format!("component {}", component.id));
+    walk_component(self, component)
+  }
+
+  fn visit_keys(&mut self, keys: &'a Keys) {
+    self.0.push("keys".into());
+    walk_keys(self, keys)
+  }
+
+  fn visit_key_format(&mut self, key_format: &'a KeyFormat) {
+    self.0.push(format!("key {}", key_format.key));
+    walk_key_format(self, key_format)
+  }
+
+  fn visit_format(&mut self, format: &'a Format) {
+    self.0.push("format".into());
+    walk_format(self, format)
+  }
+
+  fn visit_zone(&mut self, zone: &'a ComponentZone) {
+    self.0.push("zone".into());
+    walk_zone(self, zone)
+  }
+
+  fn visit_component_common(&mut self, common: &'a ComponentCommon) {
+    self.0.push("classes".into());
+    walk_component_common(self, common)
+  }
+
+  fn visit_child(&mut self, child: &'a ComponentChild) {
+    self.0.push("child".into());
+    walk_child(self, child)
+  }
+
+  fn visit_rich_text(&mut self, text: &'a RichText) {
+    self.0.push("text".into());
+    walk_rich_text(self, text)
+  }
+
+  fn visit_getter(&mut self, getter: &'a Getter) {
+    self.0.push(format!("getter {}", getter.ident));
+    walk_getter(self, getter)
+  }
+
+  fn visit_object(&mut self, object: &'a Object) {
+    self.0.push("object".into());
+    walk_object(self, object)
+  }
+}
+
+struct RenameAll;
+
+impl VisitorMut for RenameAll {
+  fn visit_key_format_mut(&mut self, key_format: &mut KeyFormat) {
+    key_format.key = key_format.key.to_uppercase();
+    walk_key_format_mut(self, key_format)
+  }
+
+  fn visit_getter_mut(&mut self, getter: &mut Getter) {
+    getter.ident = getter.ident.to_uppercase();
+    walk_getter_mut(self, getter)
+  }
+}
+
+fn trace(model: &Model) -> Vec<String> {
+  let mut trace = Trace::default();
+  trace.visit_model(model);
+  trace.0
+}
+
+// static keys are text indexes, every block has its classes
+#[test]
+fn walks_a_model() {
+  let model = model();
+  assert_eq!(
+    trace(&model),
+    [
+      "component child",
+      "keys",
+      "key value",
+      "format",
+      "text",
+      "zone",
+      "classes",
+      "child",
+      "text",
+      "getter value",
+      "classes",
+      "text",
+      "component home",
+      "keys",
+      "key user",
+      "format",
+      "key name",
+      "format",
+      "key tags",
+      "format",
+      "format",
+      "text",
+      "zone",
+      "classes",
+      "text",
+      "getter user",
+      "text",
+      "child",
+      "text",
+      "getter user",
+      "text",
+      "classes",
+      "child",
+      "getter user",
+      "text",
+      "classes",
+      "child",
+      "object",
+      "getter user",
+      "text",
+      "classes",
+    ]
+  );
+}
+
+#[test]
+fn rewrites_a_model_in_place() {
+  let mut model = model();
+  RenameAll.visit_model_mut(&mut model);
+  let trace = trace(&model);
+  let keys = trace
+    .iter()
+    .filter_map(|event| event.strip_prefix("key "))
+    .collect::<Vec<_>>();
+  assert_eq!(keys, ["VALUE", "USER", "NAME", "TAGS"]);
+  let getters = trace
+    .iter()
+    .filter_map(|event| event.strip_prefix("getter "))
+    .collect::<Vec<_>>();
+  assert_eq!(getters, ["VALUE", "USER", "USER", "USER", "USER"]);
+}
@@ -1,17 +1,25 @@
 use core::ops::Deref;
 
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  format,
+  vec::Vec,
+};
+use dropin_compiler_common::Diagnostic;
 use dropin_compiler_recipes::ir::{Component, ComponentExtern};
 
 use crate::visit::{ComponentChildTrace, Visit};
 
 #[derive(Debug)]
-pub struct DependenciesState<'a>(BTreeMap<&'a str, BTreeSet<&'a str>>);
+pub struct DependenciesState<'a> {
+  deps: BTreeMap<&'a str, BTreeSet<&'a str>>,
+  pub diagnostics: Vec<Diagnostic>,
+}
 
 impl<'a> Deref for DependenciesState<'a> {
   type Target = BTreeMap<&'a str, BTreeSet<&'a str>>;
   fn deref(&self) -> &Self::Target {
-    &self.0
+    &self.deps
   }
 }
 
@@ -42,6 +50,29 @@ pub struct Dependencies<'a> {
 
 impl<'a> Visit<'a, DependenciesState<'a>> for Dependencies<'a> {
   fn build(mut self) -> DependenciesState<'a> {
+    let mut cycles = Vec::new();
+    let mut explored = BTreeSet::new();
+    for &component in self.deps.keys() {
+      find_cycles(
+        &self.deps,
+        component,
+        &mut Vec::new(),
+        &mut explored,
+        &mut cycles,
+      );
+    }
+    let diagnostics = cycles
+      .into_iter()
+      .map(|cycle| {
+        Diagnostic::error(
+          cycle[0],
+          format!(
+            "recursive dependency `{}`, its blocks would nest endlessly",
+            cycle.join(" -> "),
+          ),
+        )
+      })
+      .collect();
     let mut is_modified = true;
     while is_modified {
       is_modified = false;
@@ -59,7 +90,10 @@ impl<'a> Visit<'a, DependenciesState<'a>> for Dependencies<'a> {
         is_modified = is_modified || old_len != tos.len();
       }
     }
-    DependenciesState(self.deps)
+    DependenciesState {
+      deps: self.deps,
+      diagnostics,
+    }
   }
 
   fn visit_component(&mut self, component: &'a Component, _index: usize) {
@@ -78,3 +112,27 @@ impl<'a> Visit<'a, DependenciesState<'a>> for Dependencies<'a> {
       .insert(&r#extern.id);
   }
 }
+
+// depth first over the direct dependencies, each back edge closes a cycle
+fn find_cycles<'a>(
+  deps: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+  component: &'a str,
+  path: &mut Vec<&'a str>,
+  explored: &mut BTreeSet<&'a str>,
+  cycles: &mut Vec<Vec<&'a str>>,
+) {
+  if let Some(start) = path.iter().position(|&from| from == component) {
+    let mut cycle = path[start..].to_vec();
+    cycle.push(component);
+    cycles.push(cycle);
+    return;
+  }
+  if !explored.insert(component) {
+    return;
+  }
+  path.push(component);
+  for &dep in deps.get(component).into_iter().flatten() {
+    find_cycles(deps, dep, path, explored, cycles);
+  }
+  path.pop();
+}
@@ -0,0 +1,112 @@
+use alloc::{format, string::String, vec::Vec};
+use dropin_compiler_common::Diagnostic;
+use dropin_compiler_recipes::ir::{
+  Component, ComponentExtern, ExpressionInner, Value, ValueInner,
+};
+
+use crate::{
+  formats::FormatsState,
+  types::{type_of, Type},
+  visit::{ComponentChildTrace, Visit},
+};
+
+#[derive(Debug, Default)]
+pub struct ExternsState {
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+// checks the properties given to extern components against their keys
+pub struct Externs<'a, 'b> {
+  formats: &'b FormatsState<'a>,
+  component: Option<&'a Component>,
+  diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a, 'b> Externs<'a, 'b> {
+  pub fn new(formats: &'b FormatsState<'a>) -> Self {
+    Self {
+      formats,
+      component: None,
+      diagnostics: Vec::new(),
+    }
+  }
+
+  fn error(&mut self, message: String) {
+    let component = self.component.unwrap();
+    self
+      .diagnostics
+      .push(Diagnostic::error(&component.id, message));
+  }
+
+  // the yaml path of the child, with its line when the source is known
+  fn location(&self, trace: &ComponentChildTrace) -> String {
+    let component = self.component.unwrap();
+    let path = format!("blocks.{}", trace.indexes.last().unwrap());
+    let line = component
+      .source
+      .as_ref()
+      .and_then(|source| source.lines.get(&path));
+    match line {
+      Some(line) => format!("{path} (line {line})"),
+      None => path,
+    }
+  }
+}
+
+impl<'a, 'b> Visit<'a, ExternsState> for Externs<'a, 'b> {
+  fn build(self) -> ExternsState {
+    ExternsState {
+      diagnostics: self.diagnostics,
+    }
+  }
+
+  fn visit_component(&mut self, component: &'a Component, _index: usize) {
+    self.component = Some(component);
+  }
+
+  fn visit_child_extern(
+    &mut self,
+    r#extern: &'a ComponentExtern,
+    trace: &ComponentChildTrace,
+  ) {
+    let id = &r#extern.id;
+    let location = self.location(trace);
+    if !self.formats.has_component(id) {
+      self.error(format!("unknown component `{id}` at {location}"));
+      return;
+    }
+    let values = r#extern.properties.as_ref().map(|object| &object.values);
+    let keys = self.formats.properties_of(id);
+
+    for (key, default) in keys.iter().flat_map(|keys| &keys.required) {
+      let is_undefined = matches!(
+        default.expression_inner,
+        Some(ExpressionInner::Value(Value {
+          value_inner: Some(ValueInner::Undefined(_)),
+        }))
+      );
+      if is_undefined && !values.is_some_and(|values| values.contains_key(key))
+      {
+        self.error(format!(
+          "`{id}` at {location} is missing its required property `{key}`",
+        ));
+      }
+    }
+
+    let component = &self.component.unwrap().id;
+    for (key, value) in values.into_iter().flatten() {
+      let Some(format) = self.formats.property_of(id, key) else {
+        self.error(format!("`{id}` at {location} has no property `{key}`"));
+        continue;
+      };
+      let expected = Type::from(format);
+      let r#type = type_of(self.formats, component, value);
+      if !expected.accepts(&r#type) {
+        self.error(format!(
+          "property `{key}` of `{id}` at {location} expects {expected}, \
+          found {type}",
+        ));
+      }
+    }
+  }
+}
@@ -0,0 +1,151 @@
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Expression, Format, FormatCommon, FormatInner, Getter, KeyFormat, Keys,
+};
+
+use crate::{objects_getter::static_key, visit::Visit, Stated};
+
+static DYNAMIC: Format = Format {
+  format_inner: Some(FormatInner::Any(FormatCommon {
+    constraints: Vec::new(),
+  })),
+};
+
+#[derive(Debug, Default)]
+pub struct FormatsState<'a> {
+  components: BTreeSet<&'a str>,
+  properties: BTreeMap<&'a str, &'a Keys>,
+  variables: BTreeMap<&'a str, &'a Keys>,
+}
+
+impl<'a> FormatsState<'a> {
+  // `None` when the getter reads no declared key, or a key its format lacks
+  pub fn format_of(&self, component: &str, getter: &Getter) -> Option<&Format> {
+    self.resolve(component, getter)?.ok()
+  }
+
+  // the format a getter reads, or why its keys can't be followed; `None`
+  // when its ident is neither a property nor a variable
+  pub fn resolve<'c>(
+    &self,
+    component: &str,
+    getter: &'c Getter,
+  ) -> Option<Result<&Format, Unresolved<'c>>> {
+    let format = [&self.properties, &self.variables]
+      .into_iter()
+      .filter_map(|keys| keys.get(component))
+      .find_map(|keys| find_in_keys(&keys.keys, &getter.ident))?;
+    Some(get_in_format(format, &getter.indexes, 0))
+  }
+
+  pub fn has_component(&self, component: &str) -> bool {
+    self.components.contains(component)
+  }
+
+  pub fn properties_of(&self, component: &str) -> Option<&'a Keys> {
+    self.properties.get(component).copied()
+  }
+
+  // format of a property declared by `component`
+  pub fn property_of(&self, component: &str, key: &str) -> Option<&Format> {
+    find_in_keys(&self.properties.get(component)?.keys, key)
+  }
+
+  // default of a required property or variable, undefined when none is given
+  pub fn default_of(
+    &self,
+    component: &str,
+    ident: &str,
+  ) -> Option<&Expression> {
+    [&self.properties, &self.variables]
+      .into_iter()
+      .filter_map(|keys| keys.get(component))
+      .find_map(|keys| keys.required.get(ident))
+  }
+}
+
+impl<'a> Stated<FormatsState<'a>> for FormatsState<'a> {
+  fn state(&self) -> &FormatsState<'a> {
+    self
+  }
+}
+
+impl<'a> Visit<'a, FormatsState<'a>> for FormatsState<'a> {
+  fn build(self) -> FormatsState<'a> {
+    self
+  }
+
+  fn visit_component(
+    &mut self,
+    component: &'a dropin_compiler_recipes::ir::Component,
+    _index: usize,
+  ) {
+    self.components.insert(&component.id);
+    if let Some(properties) = component.properties.as_ref() {
+      self.properties.insert(&component.id, properties);
+    }
+    if let Some(variables) = component.variables.as_ref() {
+      self.variables.insert(&component.id, variables);
+    }
+  }
+}
+
+// why the keys of a getter lead nowhere, with how many of them resolved
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unresolved<'a> {
+  NoKey(usize, &'a str),
+  NotIndexable(usize),
+}
+
+fn get_in_format<'a, 'b>(
+  format: &'a Format,
+  keys: &'b [Expression],
+  depth: usize,
+) -> Result<&'a Format, Unresolved<'b>> {
+  let Some((first, rest)) = keys.split_first() else {
+    return Ok(format);
+  };
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Index(index) => {
+      get_in_format(index.format.as_ref().unwrap(), rest, depth + 1)
+    }
+    FormatInner::List(list) => {
+      get_in_format(list.format.as_ref().unwrap(), rest, depth + 1)
+    }
+    FormatInner::Object(object) => {
+      let format = match static_key(first) {
+        Some(key) => find_in_keys(&object.keys, key)
+          .ok_or(Unresolved::NoKey(depth, key))?,
+        None => keys_union(&object.keys),
+      };
+      get_in_format(format, rest, depth + 1)
+    }
+    FormatInner::Any(_) => Ok(format),
+    _ => Err(Unresolved::NotIndexable(depth)),
+  }
+}
+
+fn find_in_keys<'a>(keys: &'a [KeyFormat], key: &str) -> Option<&'a Format> {
+  for key_format in keys {
+    if key_format.key == key {
+      return key_format.format.as_ref();
+    }
+  }
+  None
+}
+
+// format shared by all keys of a dynamically indexed object, `any` otherwise
+fn keys_union(keys: &[KeyFormat]) -> &Format {
+  let mut formats = keys.iter().map(|key| key.format.as_ref().unwrap());
+  let Some(first) = formats.next() else {
+    return &DYNAMIC;
+  };
+  if formats.all(|format| format == first) {
+    first
+  } else {
+    &DYNAMIC
+  }
+}
@@ -0,0 +1,153 @@
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  format,
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_common::Diagnostic;
+use dropin_compiler_recipes::{
+  ir::{
+    AnonymousFunction, Component, Format, FormatInner, Getter, NamedFunction,
+  },
+  visitor::{
+    walk_anonymous_function, walk_getter, walk_named_function, Visitor,
+  },
+};
+
+use crate::{objects_getter::static_key, visit::Visit};
+
+#[derive(Debug, Default)]
+pub struct GettersState {
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+// resolves the getters of each component, and finds the keys never read
+#[derive(Default)]
+pub struct Getters {
+  diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visit<'a, GettersState> for Getters {
+  fn build(self) -> GettersState {
+    GettersState {
+      diagnostics: self.diagnostics,
+    }
+  }
+
+  fn visit_component(&mut self, component: &'a Component, _index: usize) {
+    let mut scope = Scope::default();
+    for (keys, is_property) in [
+      (component.properties.as_ref(), true),
+      (component.variables.as_ref(), false),
+    ] {
+      for key_format in keys.iter().flat_map(|keys| &keys.keys) {
+        scope.declared.insert(key_format.key.as_str(), is_property);
+        if let Some(format) = key_format.format.as_ref() {
+          scope.formats.insert(key_format.key.as_str(), format);
+        }
+      }
+    }
+    scope.visit_component(component);
+
+    for ident in scope.unknown {
+      self.diagnostics.push(Diagnostic::error(
+        &component.id,
+        format!(
+          "`{ident}` is neither a property, a variable nor a function \
+          argument",
+        ),
+      ));
+    }
+    for (path, key) in scope.missing {
+      self.diagnostics.push(Diagnostic::error(
+        &component.id,
+        format!("`{path}` has no key `{key}`"),
+      ));
+    }
+    for (ident, is_property) in scope.declared {
+      if scope.read.contains(ident) {
+        continue;
+      }
+      let kind = if is_property { "property" } else { "variable" };
+      self.diagnostics.push(Diagnostic::warning(
+        &component.id,
+        format!("{kind} `{ident}` is never read"),
+      ));
+    }
+  }
+}
+
+#[derive(Default)]
+struct Scope<'a> {
+  declared: BTreeMap<&'a str, bool>,
+  arguments: Vec<&'a str>,
+  formats: BTreeMap<&'a str, &'a Format>,
+  read: BTreeSet<&'a str>,
+  unknown: BTreeSet<&'a str>,
+  missing: BTreeSet<(String, &'a str)>,
+}
+
+impl<'a> Scope<'a> {
+  // follows the static keys of a getter through the objects it reads
+  fn resolve_keys(&mut self, getter: &'a Getter) {
+    let Some(mut format) = self.formats.get(getter.ident.as_str()).copied()
+    else {
+      return;
+    };
+    let mut path = String::from(&getter.ident);
+    for index in &getter.indexes {
+      let Some(FormatInner::Object(object)) = format.format_inner.as_ref()
+      else {
+        break;
+      };
+      let Some(key) = static_key(index) else {
+        break;
+      };
+      let Some(key_format) = object.keys.iter().find(|k| k.key == key) else {
+        self.missing.insert((path, key));
+        break;
+      };
+      let Some(next) = key_format.format.as_ref() else {
+        break;
+      };
+      format = next;
+      path.push('.');
+      path.push_str(key);
+    }
+  }
+}
+
+impl<'a> Visitor<'a> for Scope<'a> {
+  fn visit_getter(&mut self, getter: &'a Getter) {
+    let ident = getter.ident.as_str();
+    if !self.arguments.contains(&ident) {
+      if self.declared.contains_key(ident) {
+        self.read.insert(ident);
+        self.resolve_keys(getter);
+      } else {
+        self.unknown.insert(ident);
+      }
+    }
+    walk_getter(self, getter)
+  }
+
+  fn visit_anonymous_function(&mut self, function: &'a AnonymousFunction) {
+    let len = self.arguments.len();
+    self
+      .arguments
+      .extend(function.args.iter().map(|arg| arg.as_str()));
+    walk_anonymous_function(self, function);
+    self.arguments.truncate(len);
+  }
+
+  // a named function can call itself from its body
+  fn visit_named_function(&mut self, function: &'a NamedFunction) {
+    let len = self.arguments.len();
+    self.arguments.push(&function.name);
+    self
+      .arguments
+      .extend(function.args.iter().map(|arg| arg.as_str()));
+    walk_named_function(self, function);
+    self.arguments.truncate(len);
+  }
+}
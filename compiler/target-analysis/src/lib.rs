@@ -0,0 +1,46 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// passes over a model that targets query while generating code, like the
+// variables a block listens to or the format of a getter. `Stage0` and
+// `Stage1` run them all, `Stage` can run any other `Visit`.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod dependencies;
+pub mod externs;
+pub mod formats;
+pub mod getters;
+pub mod objects_getter;
+pub mod properties_resolver;
+pub mod stage;
+pub mod stage0;
+pub mod stage1;
+pub mod types;
+pub mod updated_listeners;
+pub mod visit;
+
+// a state holding the state `S` of a pass
+pub trait Stated<S> {
+  fn state(&self) -> &S;
+}
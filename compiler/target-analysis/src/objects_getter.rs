@@ -0,0 +1,166 @@
+use core::ops::{Bound, Deref};
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  format,
+  vec::Vec,
+};
+use dropin_compiler_common::Diagnostic;
+use dropin_compiler_recipes::ir::{
+  Component, Expression, ExpressionInner, FormatObject, Getter, RichText,
+  RichTextInner, RichTextPart, Value, ValueInner,
+};
+
+use crate::visit::{ExpressionTrace, FormatTrace, Visit};
+
+#[derive(Debug)]
+pub struct ObjectGetterState<'a> {
+  objects: BTreeMap<&'a str, BTreeMap<Vec<&'a str>, &'a FormatObject>>,
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ObjectGetterState<'a> {
+  pub fn contains_object(&self, component: &'a str, keys: &[&'a str]) -> bool {
+    let Some(component_objects) = self.get(component) else {
+      return false;
+    };
+    component_objects
+      .range((Bound::Included(keys.to_vec()), Bound::Unbounded))
+      .next()
+      .map(|(k, _)| {
+        if &k[..keys.len()] == keys {
+          true
+        } else {
+          false
+        }
+      })
+      .unwrap_or(false)
+  }
+}
+
+impl<'a> Deref for ObjectGetterState<'a> {
+  type Target = BTreeMap<&'a str, BTreeMap<Vec<&'a str>, &'a FormatObject>>;
+  fn deref(&self) -> &Self::Target {
+    &self.objects
+  }
+}
+
+#[derive(Default)]
+pub struct ObjectGetter<'a> {
+  component: Option<&'a str>,
+  objects: BTreeMap<&'a str, BTreeMap<Vec<&'a str>, &'a FormatObject>>,
+  dynamically_indexed: BTreeMap<&'a str, BTreeSet<Vec<&'a str>>>,
+}
+
+impl<'a, 'b> Visit<'a, ObjectGetterState<'a>> for ObjectGetter<'a> {
+  fn build(self) -> ObjectGetterState<'a> {
+    let mut diagnostics = Vec::new();
+    for (component, traces) in self.dynamically_indexed {
+      for trace in traces {
+        diagnostics.push(Diagnostic::warning(
+          component,
+          format!(
+            "object `{}` is indexed with a dynamic key, the value read has \
+            the format shared by its keys, `any` when they differ",
+            trace.join("."),
+          ),
+        ));
+      }
+    }
+    ObjectGetterState {
+      objects: self.objects,
+      diagnostics,
+    }
+  }
+
+  fn visit_component(&mut self, component: &'a Component, _index: usize) {
+    self.component = Some(&component.id);
+  }
+
+  fn visit_format_object(
+    &mut self,
+    format: &'a FormatObject,
+    trace: &FormatTrace<'a>,
+  ) {
+    self
+      .objects
+      .entry(trace.component)
+      .or_insert(BTreeMap::new())
+      .insert(trace.keys.clone(), format);
+  }
+
+  fn visit_getter(
+    &mut self,
+    getter: &'a Getter,
+    _trace: &ExpressionTrace<'a, '_>,
+  ) {
+    let component = self.component.unwrap();
+    let Some(objects) = self.objects.get(component) else {
+      return;
+    };
+    let mut trace_current = Vec::with_capacity(getter.indexes.len() + 1);
+    trace_current.push(getter.ident.as_str());
+    for key in &getter.indexes {
+      let key = static_key(key);
+      if key.is_none() && objects.contains_key(&trace_current) {
+        self
+          .dynamically_indexed
+          .entry(component)
+          .or_default()
+          .insert(trace_current.clone());
+      }
+      trace_current.push(key.unwrap_or("*"));
+    }
+  }
+}
+
+pub fn static_key(key: &Expression) -> Option<&str> {
+  let ExpressionInner::Value(Value {
+    value_inner: Some(ValueInner::Text(RichText { parts })),
+  }) = key.expression_inner.as_ref().unwrap()
+  else {
+    return None;
+  };
+  if parts.len() != 1 {
+    return None;
+  }
+  let RichTextPart {
+    rich_text_inner: Some(RichTextInner::Static(part)),
+  } = &parts[0]
+  else {
+    return None;
+  };
+  Some(part)
+}
+
+pub fn write_class_name(
+  output: &mut impl Write,
+  trace: &[&str],
+) -> fmt::Result {
+  for key in trace {
+    match *key {
+      "*" => {
+        write!(output, "_")?;
+      }
+      "_" => write!(output, "__")?,
+      _ => {
+        let mut is_capital = true;
+        for c in key.chars() {
+          if c == '_' {
+            is_capital = true;
+            continue;
+          }
+          if is_capital {
+            output.write_char(c.to_ascii_uppercase())?;
+          } else {
+            output.write_char(c)?;
+          }
+          is_capital = false;
+        }
+      }
+    }
+  }
+  write!(output, "Object")?;
+  Ok(())
+}
@@ -11,7 +11,10 @@ use dropin_compiler_recipes::ir::{
 };
 use itertools::iproduct;
 
-use crate::{visit::ExpressionTrace, Stated, Visit};
+use crate::{
+  visit::{ExpressionTrace, Visit},
+  Stated,
+};
 
 type PropertiesByComponent<'a> = BTreeMap<&'a str, PropertiesByProperty<'a>>;
 type PropertiesByProperty<'a> =
@@ -124,7 +124,18 @@ impl<'a, T, V: Visit<'a, T>> Stage<'a, T, V> {
           ValueInner::Boolean(boolean) => {
             self.visit.visit_boolean(*boolean, trace)
           }
-          ValueInner::Getter(getter) => self.visit.visit_getter(getter, trace),
+          ValueInner::Getter(getter) => {
+            self.visit.visit_getter(getter, trace);
+            let mut trace = ExpressionTrace::NestedQuantity {
+              parent: Some(expression),
+              index: 0,
+              trace,
+            };
+            for (index, key) in getter.indexes.iter().enumerate() {
+              trace.set_quantity_index(index);
+              self.expression(key, &mut trace);
+            }
+          }
           ValueInner::List(list) => {
             self.visit.visit_list(list, trace);
             let mut trace = ExpressionTrace::NestedQuantity {
@@ -357,6 +368,50 @@ impl<'a, T, V: Visit<'a, T>> Stage<'a, T, V> {
             trace.set_quantity_index(1);
             self.expression(sub.right.as_ref().unwrap(), &mut trace);
           }
+          ArithmeticInner::Mul(mul) => {
+            self.visit.visit_mul(mul, trace);
+            let mut trace = ExpressionTrace::NestedQuantity {
+              parent: Some(expression),
+              index: 0,
+              trace,
+            };
+            self.expression(mul.left.as_ref().unwrap(), &mut trace);
+            trace.set_quantity_index(1);
+            self.expression(mul.right.as_ref().unwrap(), &mut trace);
+          }
+          ArithmeticInner::Div(div) => {
+            self.visit.visit_div(div, trace);
+            let mut trace = ExpressionTrace::NestedQuantity {
+              parent: Some(expression),
+              index: 0,
+              trace,
+            };
+            self.expression(div.left.as_ref().unwrap(), &mut trace);
+            trace.set_quantity_index(1);
+            self.expression(div.right.as_ref().unwrap(), &mut trace);
+          }
+          ArithmeticInner::Mod(r#mod) => {
+            self.visit.visit_mod(r#mod, trace);
+            let mut trace = ExpressionTrace::NestedQuantity {
+              parent: Some(expression),
+              index: 0,
+              trace,
+            };
+            self.expression(r#mod.left.as_ref().unwrap(), &mut trace);
+            trace.set_quantity_index(1);
+            self.expression(r#mod.right.as_ref().unwrap(), &mut trace);
+          }
+          ArithmeticInner::Pow(pow) => {
+            self.visit.visit_pow(pow, trace);
+            let mut trace = ExpressionTrace::NestedQuantity {
+              parent: Some(expression),
+              index: 0,
+              trace,
+            };
+            self.expression(pow.left.as_ref().unwrap(), &mut trace);
+            trace.set_quantity_index(1);
+            self.expression(pow.right.as_ref().unwrap(), &mut trace);
+          }
         }
       }
     }
@@ -0,0 +1,46 @@
+use alloc::vec::Vec;
+use dropin_compiler_common::Diagnostic;
+use dropin_target_macros::Visit;
+
+use crate::{
+  dependencies::{Dependencies, DependenciesState},
+  formats::FormatsState,
+  getters::{Getters, GettersState},
+  objects_getter::{ObjectGetter, ObjectGetterState},
+  properties_resolver::{PropertiesResolver, PropertiesResolverState},
+  Stated,
+};
+
+#[derive(Debug)]
+pub struct Stage0State<'a> {
+  pub resolver: PropertiesResolverState<'a>,
+  pub object_getter: ObjectGetterState<'a>,
+  pub dependencies: DependenciesState<'a>,
+  pub formats: FormatsState<'a>,
+  pub getters: GettersState,
+}
+
+impl Stage0State<'_> {
+  // everything the passes diagnosed, for the target to report
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    let mut diagnostics = self.object_getter.diagnostics.clone();
+    diagnostics.extend(self.dependencies.diagnostics.iter().cloned());
+    diagnostics.extend(self.getters.diagnostics.iter().cloned());
+    diagnostics
+  }
+}
+
+#[derive(Default, Visit)]
+#[visit(path = "src/visit.rs", state = Stage0State<'a>)]
+pub struct Stage0<'a> {
+  #[state(PropertiesResolverState<'a>)]
+  resolver: PropertiesResolver<'a>,
+  #[state(ObjectGetterState<'a>)]
+  object_getter: ObjectGetter<'a>,
+  #[state(DependenciesState<'a>)]
+  dependencies: Dependencies<'a>,
+  #[state(FormatsState<'a>)]
+  formats: FormatsState<'a>,
+  #[state(GettersState)]
+  getters: Getters,
+}
@@ -0,0 +1,64 @@
+use alloc::vec::Vec;
+use dropin_compiler_common::Diagnostic;
+use dropin_target_macros::Visit;
+
+use crate::{
+  externs::{Externs, ExternsState},
+  stage0::Stage0State,
+  types::{Types, TypesState},
+  updated_listeners::{UpdatedAndListeners, UpdatedAndListenersState},
+  Stated,
+};
+
+#[derive(Debug)]
+pub struct Stage1State<'a, 'b> {
+  pub stage0: &'b Stage0State<'a>,
+  pub updated_listeners: UpdatedAndListenersState<'a>,
+  pub types: TypesState,
+  pub externs: ExternsState,
+}
+
+impl Stage1State<'_, '_> {
+  // everything the passes of both stages diagnosed
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    let mut diagnostics = self.stage0.diagnostics();
+    diagnostics.extend(self.types.diagnostics.iter().cloned());
+    diagnostics.extend(self.externs.diagnostics.iter().cloned());
+    diagnostics
+  }
+}
+
+impl<'a, 'b, T> Stated<T> for Stage1State<'a, 'b>
+where
+  Stage0State<'a>: Stated<T>,
+{
+  fn state(&self) -> &T {
+    self.stage0.state()
+  }
+}
+
+#[derive(Visit)]
+#[visit(path = "src/visit.rs", state = Stage1State<'a, 'b>)]
+pub struct Stage1<'a, 'b> {
+  stage0: &'b Stage0State<'a>,
+  #[state(UpdatedAndListenersState<'a>)]
+  updated_listeners: UpdatedAndListeners<'a, 'b>,
+  #[state(TypesState)]
+  types: Types<'a, 'b>,
+  #[state(ExternsState)]
+  externs: Externs<'a, 'b>,
+}
+
+impl<'a, 'b> Stage1<'a, 'b> {
+  pub fn new(stage0: &'b Stage0State<'a>) -> Self {
+    Self {
+      stage0,
+      updated_listeners: UpdatedAndListeners::new(
+        &stage0.resolver,
+        &stage0.dependencies,
+      ),
+      types: Types::new(&stage0.formats),
+      externs: Externs::new(&stage0.formats),
+    }
+  }
+}
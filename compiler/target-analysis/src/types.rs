@@ -0,0 +1,395 @@
+use core::fmt::{self, Display, Formatter, Write};
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use dropin_compiler_common::Diagnostic;
+use dropin_compiler_recipes::ir::{
+  ArithmeticInner, Binary, ComparisonInner, Component, ControlInner,
+  Expression, ExpressionInner, Format, FormatInner, LogicInner, Value,
+  ValueInner,
+};
+
+use crate::{
+  formats::{FormatsState, Unresolved},
+  objects_getter::static_key,
+  visit::{ExpressionTrace, Visit},
+};
+
+// what an expression evaluates to, `Any` when it can't be known statically
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+  Any,
+  Undefined,
+  Boolean,
+  Quantity,
+  Text,
+  Date,
+  List(Box<Type>),
+  Index(Box<Type>),
+  Object,
+  Function(usize),
+}
+
+impl Type {
+  // whether a value of one type can stand where the other is expected
+  pub fn accepts(&self, other: &Type) -> bool {
+    match (self, other) {
+      (Self::Any, _) | (_, Self::Any) => true,
+      (Self::Text | Self::Date, Self::Text | Self::Date) => true,
+      (Self::List(a), Self::List(b)) | (Self::Index(a), Self::Index(b)) => {
+        a.accepts(b)
+      }
+      (Self::Index(_), Self::Object) | (Self::Object, Self::Index(_)) => true,
+      (Self::Function(_), Self::Function(_)) => true,
+      _ => self == other,
+    }
+  }
+
+  // the type of a value that is either, `None` when they have nothing in
+  // common; a missing value keeps the type of the other one
+  pub fn unify(&self, other: &Type) -> Option<Type> {
+    Some(match (self, other) {
+      _ if self == other => self.clone(),
+      (Self::Any, _) | (_, Self::Any) => Self::Any,
+      (Self::Undefined, r#type) | (r#type, Self::Undefined) => r#type.clone(),
+      (Self::Text | Self::Date, Self::Text | Self::Date) => Self::Text,
+      (Self::List(a), Self::List(b)) => Self::List(Box::new(a.unify(b)?)),
+      (Self::Index(a), Self::Index(b)) => Self::Index(Box::new(a.unify(b)?)),
+      (Self::Index(_), Self::Object) | (Self::Object, Self::Index(_)) => {
+        Self::Object
+      }
+      (Self::Function(_), Self::Function(_)) => Self::Any,
+      _ => return None,
+    })
+  }
+
+  fn is(&self, expected: &Type) -> bool {
+    matches!(self, Self::Any) || self == expected
+  }
+
+  fn is_ordered(&self) -> bool {
+    matches!(self, Self::Any | Self::Quantity | Self::Date)
+  }
+}
+
+impl From<&Format> for Type {
+  fn from(format: &Format) -> Self {
+    match format.format_inner.as_ref().unwrap() {
+      FormatInner::Any(_) => Self::Any,
+      FormatInner::Boolean(_) => Self::Boolean,
+      FormatInner::Choices(_) | FormatInner::Text(_) => Self::Text,
+      FormatInner::Date(_) => Self::Date,
+      FormatInner::Index(index) => {
+        Self::Index(Box::new(index.format.as_deref().unwrap().into()))
+      }
+      FormatInner::List(list) => {
+        Self::List(Box::new(list.format.as_deref().unwrap().into()))
+      }
+      FormatInner::Object(_) => Self::Object,
+      FormatInner::Quantity(_) => Self::Quantity,
+    }
+  }
+}
+
+impl Display for Type {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Any => write!(f, "any"),
+      Self::Undefined => write!(f, "undefined"),
+      Self::Boolean => write!(f, "boolean"),
+      Self::Quantity => write!(f, "quantity"),
+      Self::Text => write!(f, "text"),
+      Self::Date => write!(f, "date"),
+      Self::List(element) => write!(f, "list of {element}"),
+      Self::Index(element) => write!(f, "index of {element}"),
+      Self::Object => write!(f, "object"),
+      Self::Function(arity) => write!(f, "function of {arity} arguments"),
+    }
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct TypesState {
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+pub struct Types<'a, 'b> {
+  formats: &'b FormatsState<'a>,
+  component: Option<&'a str>,
+  diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a, 'b> Types<'a, 'b> {
+  pub fn new(formats: &'b FormatsState<'a>) -> Self {
+    Self {
+      formats,
+      component: None,
+      diagnostics: Vec::new(),
+    }
+  }
+
+  fn error(&mut self, message: String) {
+    self
+      .diagnostics
+      .push(Diagnostic::error(self.component.unwrap(), message));
+  }
+
+  // getters unknown to the formats, like function arguments, are `any`
+  fn type_of(&self, expression: &Expression) -> Type {
+    type_of(self.formats, self.component.unwrap(), expression)
+  }
+
+  fn binary(&self, binary: &Binary) -> (Type, Type) {
+    (
+      self.type_of(binary.left.as_ref().unwrap()),
+      self.type_of(binary.right.as_ref().unwrap()),
+    )
+  }
+
+  fn check_equality(&mut self, operator: &str, binary: &Binary) {
+    let (left, right) = self.binary(binary);
+    if !left.accepts(&right) {
+      self.error(format!("`{operator}` compares {left} with {right}"));
+    }
+  }
+
+  fn check_order(&mut self, operator: &str, binary: &Binary) {
+    let (left, right) = self.binary(binary);
+    if !left.is_ordered() || !right.is_ordered() || !left.accepts(&right) {
+      self.error(format!(
+        "`{operator}` expects two quantities or two dates, found {left} and \
+        {right}",
+      ));
+    }
+  }
+
+  fn check_in(&mut self, binary: &Binary) {
+    let (left, right) = self.binary(binary);
+    let is_valid = match &right {
+      Type::Any => true,
+      Type::List(element) | Type::Index(element) => element.accepts(&left),
+      Type::Text | Type::Object => left.accepts(&Type::Text),
+      _ => false,
+    };
+    if !is_valid {
+      self.error(format!("`in` looks for {left} in {right}"));
+    }
+  }
+
+  fn check_boolean(&mut self, operator: &str, operand: &Expression) {
+    let r#type = self.type_of(operand);
+    if !r#type.is(&Type::Boolean) {
+      self.error(format!("`{operator}` expects a boolean, found {type}"));
+    }
+  }
+
+  fn check_quantities(&mut self, operator: &str, binary: &Binary) {
+    self.check_quantity(operator, binary.left.as_ref().unwrap());
+    self.check_quantity(operator, binary.right.as_ref().unwrap());
+  }
+
+  fn check_quantity(&mut self, operator: &str, operand: &Expression) {
+    let r#type = self.type_of(operand);
+    if !r#type.is(&Type::Quantity) {
+      self.error(format!("`{operator}` expects a quantity, found {type}"));
+    }
+  }
+}
+
+impl<'a, 'b> Visit<'a, TypesState> for Types<'a, 'b> {
+  fn build(self) -> TypesState {
+    TypesState {
+      diagnostics: self.diagnostics,
+    }
+  }
+
+  fn visit_component(&mut self, component: &'a Component, _index: usize) {
+    self.component = Some(&component.id);
+  }
+
+  fn visit_expression(
+    &mut self,
+    expression: &'a Expression,
+    _trace: &ExpressionTrace<'a, '_>,
+  ) {
+    match expression.expression_inner.as_ref().unwrap() {
+      ExpressionInner::Value(Value {
+        value_inner: Some(ValueInner::Getter(getter)),
+      }) => {
+        let component = self.component.unwrap();
+        // unknown keys are reported by the getters pass
+        if let Some(Err(Unresolved::NotIndexable(depth))) =
+          self.formats.resolve(component, getter)
+        {
+          let mut path = getter.ident.clone();
+          for key in &getter.indexes[..depth] {
+            match static_key(key) {
+              Some(key) => write!(path, ".{key}").unwrap(),
+              None => path.push_str("[]"),
+            }
+          }
+          self.error(format!("`{path}` cannot be indexed"));
+        }
+      }
+      ExpressionInner::Value(_) => {}
+      ExpressionInner::Comparison(comparison) => {
+        match comparison.comparison_inner.as_ref().unwrap() {
+          ComparisonInner::EqualsTo(binary) => {
+            self.check_equality("==", binary)
+          }
+          ComparisonInner::DifferentFrom(binary) => {
+            self.check_equality("!=", binary)
+          }
+          ComparisonInner::In(binary) => self.check_in(binary),
+          ComparisonInner::LessThan(binary) => self.check_order("<", binary),
+          ComparisonInner::MoreThan(binary) => self.check_order(">", binary),
+          ComparisonInner::AtLeast(binary) => self.check_order(">=", binary),
+          ComparisonInner::AtMost(binary) => self.check_order("<=", binary),
+        }
+      }
+      ExpressionInner::Logic(logic) => {
+        match logic.logic_inner.as_ref().unwrap() {
+          LogicInner::And(operands) => {
+            for operand in &operands.operands {
+              self.check_boolean("&", operand);
+            }
+          }
+          LogicInner::Or(operands) => {
+            for operand in &operands.operands {
+              self.check_boolean("|", operand);
+            }
+          }
+          LogicInner::Not(operand) => self.check_boolean("!", operand),
+          LogicInner::Exists(_) => {}
+        }
+      }
+      ExpressionInner::Control(control) => {
+        match control.control_inner.as_ref().unwrap() {
+          ControlInner::If(r#if) => {
+            self.check_boolean("if", r#if.condition.as_ref().unwrap());
+            if let Some(r#else) = r#if.r#else.as_ref() {
+              let then = self.type_of(r#if.then.as_ref().unwrap());
+              let r#else = self.type_of(r#else);
+              if then.unify(&r#else).is_none() {
+                self.error(format!(
+                  "`if` branches evaluate to {then} and {else}",
+                ));
+              }
+            }
+          }
+          ControlInner::FunctionCall(call) => {
+            match self.type_of(call.function.as_ref().unwrap()) {
+              Type::Any => {}
+              Type::Function(arity) => {
+                if arity != call.args.len() {
+                  self.error(format!(
+                    "function expects {arity} arguments, called with {}",
+                    call.args.len(),
+                  ));
+                }
+              }
+              r#type => self.error(format!("calls {type}, not a function")),
+            }
+          }
+          ControlInner::AnonymousFunction(_)
+          | ControlInner::NamedFunction(_) => {}
+        }
+      }
+      ExpressionInner::Arithmetic(arithmetic) => {
+        match arithmetic.arithmetic_inner.as_ref().unwrap() {
+          ArithmeticInner::Opposite(operand) => {
+            self.check_quantity("-", operand)
+          }
+          ArithmeticInner::Add(binary) => {
+            let (left, right) = self.binary(binary);
+            let is_valid = match (&left, &right) {
+              (Type::Any, _) | (_, Type::Any) => true,
+              (Type::Quantity, Type::Quantity) | (Type::Text, Type::Text) => {
+                true
+              }
+              (Type::List(_), Type::List(_)) => left.accepts(&right),
+              _ => false,
+            };
+            if !is_valid {
+              self.error(format!("`+` adds {left} and {right}"));
+            }
+          }
+          ArithmeticInner::Sub(binary) => self.check_quantities("-", binary),
+          ArithmeticInner::Mul(binary) => self.check_quantities("*", binary),
+          ArithmeticInner::Div(binary) => self.check_quantities("/", binary),
+          ArithmeticInner::Mod(binary) => self.check_quantities("%", binary),
+          ArithmeticInner::Pow(binary) => self.check_quantities("^", binary),
+        }
+      }
+    }
+  }
+}
+
+pub fn type_of(
+  formats: &FormatsState,
+  component: &str,
+  expression: &Expression,
+) -> Type {
+  match expression.expression_inner.as_ref().unwrap() {
+    ExpressionInner::Value(value) => {
+      match value.value_inner.as_ref().unwrap() {
+        ValueInner::Text(_) => Type::Text,
+        ValueInner::Quantity(_) => Type::Quantity,
+        ValueInner::Boolean(_) => Type::Boolean,
+        ValueInner::Getter(getter) => formats
+          .format_of(component, getter)
+          .map(Type::from)
+          .unwrap_or(Type::Any),
+        ValueInner::List(list) => {
+          let mut elements = list
+            .values
+            .iter()
+            .map(|element| type_of(formats, component, element));
+          let first = elements.next().unwrap_or(Type::Any);
+          let element = elements
+            .try_fold(first, |element, next| element.unify(&next))
+            .unwrap_or(Type::Any);
+          Type::List(Box::new(element))
+        }
+        ValueInner::Object(_) => Type::Object,
+        ValueInner::Undefined(_) => Type::Undefined,
+      }
+    }
+    ExpressionInner::Comparison(_) | ExpressionInner::Logic(_) => Type::Boolean,
+    ExpressionInner::Control(control) => {
+      match control.control_inner.as_ref().unwrap() {
+        ControlInner::If(r#if) => {
+          let then = type_of(formats, component, r#if.then.as_ref().unwrap());
+          match r#if.r#else.as_ref() {
+            Some(r#else) => then
+              .unify(&type_of(formats, component, r#else))
+              .unwrap_or(Type::Any),
+            None => then,
+          }
+        }
+        ControlInner::AnonymousFunction(function) => {
+          Type::Function(function.args.len())
+        }
+        ControlInner::NamedFunction(function) => {
+          Type::Function(function.args.len())
+        }
+        ControlInner::FunctionCall(_) => Type::Any,
+      }
+    }
+    ExpressionInner::Arithmetic(arithmetic) => {
+      match arithmetic.arithmetic_inner.as_ref().unwrap() {
+        ArithmeticInner::Add(binary) => {
+          let left = type_of(formats, component, binary.left.as_ref().unwrap());
+          let right =
+            type_of(formats, component, binary.right.as_ref().unwrap());
+          left.unify(&right).unwrap_or(Type::Any)
+        }
+        ArithmeticInner::Opposite(_)
+        | ArithmeticInner::Sub(_)
+        | ArithmeticInner::Mul(_)
+        | ArithmeticInner::Div(_)
+        | ArithmeticInner::Mod(_)
+        | ArithmeticInner::Pow(_) => Type::Quantity,
+      }
+    }
+  }
+}
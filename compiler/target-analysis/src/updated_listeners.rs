@@ -6,7 +6,6 @@ use crate::visit::{ComponentChildTrace, ExpressionTrace, Visit};
 use alloc::borrow::Cow;
 use alloc::collections::BTreeMap;
 use alloc::fmt::{self, Write};
-use alloc::string::String;
 use alloc::vec::Vec;
 use dropin_compiler_common::to_upper_camelcase;
 use dropin_compiler_recipes::ir::{
@@ -271,19 +270,27 @@ where
 }
 
 pub fn write_notifier_name(
-  output: &mut String,
+  output: &mut impl Write,
   getter: &Getter,
 ) -> fmt::Result {
   write!(output, "notifier")?;
-  write_getter_name(output, getter)
+  write_getter_name(output, getter)?;
+  write!(output, "_")
 }
 
-pub fn write_updater_name(output: &mut String, getter: &Getter) -> fmt::Result {
+pub fn write_updater_name(
+  output: &mut impl Write,
+  getter: &Getter,
+) -> fmt::Result {
   write!(output, "updater")?;
-  write_getter_name(output, getter)
+  write_getter_name(output, getter)?;
+  write!(output, "_")
 }
 
-fn write_getter_name(output: &mut String, getter: &Getter) -> fmt::Result {
+pub fn write_getter_name(
+  output: &mut impl Write,
+  getter: &Getter,
+) -> fmt::Result {
   write!(output, "{}", to_upper_camelcase(&getter.ident))?;
   for key in &getter.indexes {
     let ExpressionInner::Value(Value {
@@ -309,7 +316,6 @@ fn write_getter_name(output: &mut String, getter: &Getter) -> fmt::Result {
       _ => break,
     }
   }
-  write!(output, "_")?;
   Ok(())
 }
 
@@ -238,6 +238,14 @@ pub trait Visit<'a, T> {
 
   fn visit_sub(&mut self, _sub: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {}
 
+  fn visit_mul(&mut self, _mul: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {}
+
+  fn visit_div(&mut self, _div: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {}
+
+  fn visit_mod(&mut self, _mod: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {}
+
+  fn visit_pow(&mut self, _pow: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {}
+
   fn visit_zone(
     &mut self,
     _zone: &'a ComponentZone,
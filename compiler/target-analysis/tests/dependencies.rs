@@ -0,0 +1,74 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target_analysis::{stage::Stage, stage0::Stage0};
+
+const MODEL: &str = "
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: a
+      title: Home
+      path: /
+";
+
+fn component(externs: &[&str]) -> String {
+  let mut recipe = String::from("blocks:\n");
+  for id in externs {
+    recipe.push_str(&format!("  - type: component\n    id: {id}\n"));
+  }
+  recipe
+}
+
+#[test]
+fn recursive_dependencies() {
+  let mut files = BTreeMap::from([("model.dropin.yml".into(), MODEL.into())]);
+  for (id, externs) in [
+    ("a", &["b"][..]),
+    ("b", &["a"]),
+    ("c", &["c"]),
+    ("d", &["a"]),
+  ] {
+    files.insert(format!("components/{id}.dropin.yml"), component(externs));
+  }
+  let model = parse_model_from_files(&files).unwrap();
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let cycles = stage0
+    .dependencies
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    cycles,
+    [
+      "error: a: recursive dependency `a -> b -> a`, its blocks would nest \
+      endlessly",
+      "error: c: recursive dependency `c -> c`, its blocks would nest \
+      endlessly",
+    ]
+  );
+}
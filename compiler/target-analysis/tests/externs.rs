@@ -0,0 +1,147 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target_analysis::{stage::Stage, stage0::Stage0, stage1::Stage1};
+
+const MODEL: &str = "
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+";
+
+const HOME: &str = r#"
+blocks:
+  - type: component
+    id: card
+    properties:
+      title: '"Hello"'
+      count: 1
+  - type: component
+    id: card
+    properties:
+      title: 2
+      color: '"red"'
+"#;
+
+const CARD: &str = r#"
+properties:
+  required:
+    title: ''
+    count: '0'
+  keys:
+    title:
+      type: text
+    count:
+      type: quantity
+blocks:
+  - type: text
+    content: "${title} ${count}"
+"#;
+
+#[test]
+fn extern_properties() {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+    ("components/card.dropin.yml".into(), CARD.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let stage1 = Stage::new(Stage1::new(&stage0)).build(&model);
+  let errors = stage1
+    .externs
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    errors,
+    [
+      "error: home: `card` at blocks.1 (line 8) has no property `color`",
+      "error: home: property `title` of `card` at blocks.1 (line 8) expects \
+      text, found quantity",
+    ]
+  );
+}
+
+#[test]
+fn missing_required_property() {
+  let home = "
+blocks:
+  - type: component
+    id: card
+";
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), home.into()),
+    ("components/card.dropin.yml".into(), CARD.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let stage1 = Stage::new(Stage1::new(&stage0)).build(&model);
+  assert_eq!(
+    stage1.externs.diagnostics[0].message,
+    "`card` at blocks.0 (line 3) is missing its required property `title`",
+  );
+  assert_eq!(stage1.externs.diagnostics.len(), 1);
+}
+
+#[test]
+fn unknown_component() {
+  let home = r#"
+blocks:
+  - type: component
+    id: nope
+  - type: component
+    id: nope
+    properties:
+      value: '"x"'
+"#;
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), home.into()),
+    ("components/card.dropin.yml".into(), CARD.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let stage1 = Stage::new(Stage1::new(&stage0)).build(&model);
+  let errors = stage1
+    .externs
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.message.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    errors,
+    [
+      "unknown component `nope` at blocks.0 (line 3)",
+      "unknown component `nope` at blocks.1 (line 5)",
+    ]
+  );
+}
@@ -0,0 +1,92 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target_analysis::{stage::Stage, stage0::Stage0};
+
+const MODEL: &str = "
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+";
+
+const HOME: &str = r#"
+properties:
+  keys:
+    title:
+      type: text
+variables:
+  keys:
+    count:
+      type: quantity
+    unused:
+      type: text
+    user:
+      type: object
+      keys:
+        name:
+          type: text
+        profile:
+          type: object
+          keys:
+            age:
+              type: quantity
+blocks:
+  - type: text
+    content: "${title} ${cuont}"
+  - type: text
+    content: "${user.nmae} ${user.profile.aeg} ${user.profile.age}"
+  - type: input
+    on_change: count
+"#;
+
+#[test]
+fn getters() {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let diagnostics = stage0
+    .getters
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    diagnostics,
+    [
+      "error: home: `cuont` is neither a property, a variable nor a function \
+      argument",
+      "error: home: `user` has no key `nmae`",
+      "error: home: `user.profile` has no key `aeg`",
+      "warning: home: variable `unused` is never read",
+    ]
+  );
+}
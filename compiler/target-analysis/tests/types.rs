@@ -0,0 +1,269 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_common::Severity;
+use dropin_compiler_recipes::{
+  ir::{ComponentChildInner, Expression, Model, RichTextInner},
+  parser::parse_model_from_files,
+};
+use dropin_target_analysis::{
+  stage::Stage, stage0::Stage0, stage1::Stage1, types::Type,
+};
+
+const MODEL: &str = "
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+";
+
+const HOME: &str = r#"
+variables:
+  keys:
+    count:
+      type: quantity
+    name:
+      type: text
+    tags:
+      type: list
+      format:
+        type: text
+blocks:
+  - type: text
+    content: "${count + 1} ${name + \"!\"} ${name in tags} ${count < 3}"
+  - type: text
+    content: "${count + \"x\"} ${name < 3} ${count in tags}"
+  - type: text
+    content: "${if count: 1 else: \"a\"} ${!name}"
+"#;
+
+fn model(home: &str) -> Model {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), home.into()),
+  ]);
+  parse_model_from_files(&files).unwrap()
+}
+
+fn errors(home: &str) -> Vec<String> {
+  errors_of(&model(home))
+}
+
+fn errors_of(model: &Model) -> Vec<String> {
+  let stage0 = Stage::new(Stage0::default()).build(model);
+  let stage1 = Stage::new(Stage1::new(&stage0)).build(model);
+  stage1
+    .types
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.message.clone())
+    .collect()
+}
+
+#[test]
+fn type_errors() {
+  assert_eq!(
+    errors(HOME),
+    [
+      "`+` adds quantity and text",
+      "`<` expects two quantities or two dates, found text and quantity",
+      "`in` looks for quantity in list of text",
+      "`if` expects a boolean, found quantity",
+      "`if` branches evaluate to quantity and text",
+      "`!` expects a boolean, found text",
+    ]
+  );
+}
+
+const KEYS: &str = r#"
+variables:
+  keys:
+    count:
+      type: quantity
+    name:
+      type: text
+    day:
+      type: date
+    tags:
+      type: list
+      format:
+        type: text
+    scores:
+      type: index
+      format:
+        type: quantity
+    user:
+      type: object
+      keys:
+        id:
+          type: quantity
+"#;
+
+// branches of different types give their common type
+#[test]
+fn if_unification() {
+  let home = format!(
+    "{KEYS}{}",
+    r#"
+blocks:
+  - type: text
+    content: "${(if count < 1: name else: day) + \"!\"}"
+  - type: text
+    content: "${(if count < 1: name else: day) + 1}"
+  - type: text
+    content: "${(if count < 1: tags else: tags) + 1}"
+  - type: text
+    content: "${(if count < 1: user else: scores) + 1}"
+"#
+  );
+  assert_eq!(
+    errors(&home),
+    [
+      "`+` adds text and quantity",
+      "`+` adds list of text and quantity",
+      "`+` adds object and quantity",
+    ]
+  );
+}
+
+#[test]
+fn in_collections() {
+  let home = format!(
+    "{KEYS}{}",
+    r#"
+blocks:
+  - type: text
+    content: "${name in tags} ${count in scores} ${name in user}"
+  - type: text
+    content: "${count in tags} ${name in scores} ${count in user}"
+  - type: text
+    content: "${name in count}"
+"#
+  );
+  assert_eq!(
+    errors(&home),
+    [
+      "`in` looks for quantity in list of text",
+      "`in` looks for text in index of quantity",
+      "`in` looks for quantity in object",
+      "`in` looks for text in quantity",
+    ]
+  );
+}
+
+// getters whose keys lead nowhere are reported instead of typed
+#[test]
+fn unresolved_keys() {
+  let model = model(&format!(
+    "{KEYS}{}",
+    r#"
+blocks:
+  - type: text
+    content: "${user.nmae == 1} ${count.x + 1} ${name[count] == 1}"
+"#
+  ));
+  let stage0 = Stage::new(Stage0::default()).build(&model);
+  let stage1 = Stage::new(Stage1::new(&stage0)).build(&model);
+  let errors = stage1
+    .diagnostics()
+    .into_iter()
+    .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    .map(|diagnostic| diagnostic.message)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    errors,
+    [
+      "`user` has no key `nmae`",
+      "`count` cannot be indexed",
+      "`name` cannot be indexed",
+    ]
+  );
+}
+
+// the recipes can't write functions yet, the calls are built in the ir
+#[test]
+fn function_arity() {
+  let mut model = model(&format!(
+    "{KEYS}{}",
+    r#"
+blocks:
+  - type: text
+    content: "${count} ${count} ${count}"
+"#
+  ));
+  let add = || {
+    Expression::anonymous_function(
+      vec!["a".into(), "b".into()],
+      Expression::add(
+        Expression::getter("a".into(), vec![]),
+        Expression::getter("b".into(), vec![]),
+      ),
+    )
+  };
+  let mut calls = vec![
+    Expression::function_call(
+      add(),
+      vec![Expression::quantity(1.), Expression::quantity(2.)],
+    ),
+    Expression::function_call(add(), vec![Expression::quantity(1.)]),
+    Expression::function_call(
+      Expression::getter("count".into(), vec![]),
+      vec![],
+    ),
+  ]
+  .into_iter();
+  let zone = model.components[0].zone.as_mut().unwrap();
+  let Some(ComponentChildInner::Text(text)) =
+    zone.blocks[0].component_child_inner.as_mut()
+  else {
+    unreachable!();
+  };
+  for part in &mut text.content.as_mut().unwrap().parts {
+    if let Some(RichTextInner::Dynamic(expression)) =
+      part.rich_text_inner.as_mut()
+    {
+      *expression = calls.next().unwrap();
+    }
+  }
+  assert_eq!(
+    errors_of(&model),
+    [
+      "function expects 2 arguments, called with 1",
+      "calls quantity, not a function",
+    ]
+  );
+}
+
+// only `any` stands for a missing value
+#[test]
+fn undefined() {
+  assert!(!Type::Text.accepts(&Type::Undefined));
+  assert!(!Type::Undefined.accepts(&Type::Quantity));
+  assert!(Type::Undefined.accepts(&Type::Undefined));
+  assert!(Type::Any.accepts(&Type::Undefined));
+  assert_eq!(Type::Undefined.unify(&Type::Text), Some(Type::Text));
+}
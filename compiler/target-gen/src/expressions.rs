@@ -0,0 +1,288 @@
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{
+  Arithmetic, ArithmeticInner, Binary, Comparison, ComparisonInner, Control,
+  ControlInner, Expression, ExpressionInner, Logic, LogicInner, Value,
+  ValueInner,
+};
+
+use crate::CodeWriter;
+
+// writes expressions with the operators most languages share, each target
+// giving its own tokens, values and controls
+pub trait ExpressionGen {
+  const EQUALS_TO: &'static str = " == ";
+  const DIFFERENT_FROM: &'static str = " != ";
+  const AND: &'static str = " && ";
+  const OR: &'static str = " || ";
+  const NULL: &'static str = "null";
+  const COALESCE: &'static str = " ?? ";
+  // the euclidean remainder of its two operands
+  const MOD: &'static str = "((a, b) => ((a % b) + Math.abs(b)) % Math.abs(b))";
+
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result;
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result;
+
+  // whether a control is wrapped in parentheses among other operators
+  fn is_control_parenthesized(&self, control: &Control) -> bool {
+    matches!(control.control_inner, Some(ControlInner::If(_)))
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result;
+
+  // `%` is euclidean: `((left % right) + right) % right` with the truncated
+  // remainder of most languages when `right` is a positive quantity, a call
+  // to `MOD` evaluating each operand once otherwise
+  fn gen_mod(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    binary: &Binary,
+  ) -> fmt::Result {
+    let left = binary.left.as_ref().unwrap();
+    let right = binary.right.as_ref().unwrap();
+    if is_positive_quantity(right) {
+      write!(output, "((")?;
+      self.gen_expression(output, trace, true, left)?;
+      write!(output, " % ")?;
+      self.gen_expression(output, trace, true, right)?;
+      write!(output, ") + ")?;
+      self.gen_expression(output, trace, true, right)?;
+      write!(output, ") % ")?;
+      return self.gen_expression(output, trace, true, right);
+    }
+    write!(output, "{}(", Self::MOD)?;
+    self.gen_expression(output, trace, false, left)?;
+    write!(output, ", ")?;
+    self.gen_expression(output, trace, false, right)?;
+    write!(output, ")")
+  }
+
+  // whether an operand may be missing, `in` and the order comparisons then
+  // read the empty value of its format instead
+  fn is_optional(&self, _operand: &Expression) -> bool {
+    false
+  }
+
+  fn gen_empty(
+    &self,
+    _output: &mut CodeWriter,
+    _operand: &Expression,
+  ) -> fmt::Result {
+    unreachable!("only the optional operands have an empty value")
+  }
+
+  // `(operand ?? empty)`, or the operand alone when it is never missing
+  fn gen_or_empty(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    if !self.is_optional(operand) {
+      return self.gen_expression(output, &[], true, operand);
+    }
+    write!(output, "(")?;
+    self.gen_expression(output, &[], true, operand)?;
+    write!(output, "{}", Self::COALESCE)?;
+    self.gen_empty(output, operand)?;
+    write!(output, ")")
+  }
+
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_expression(output, &[], true, binary.left.as_ref().unwrap())?;
+    write!(output, " in ")?;
+    self.gen_or_empty(output, binary.right.as_ref().unwrap())
+  }
+
+  fn gen_exists(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    self.gen_expression(output, &[], true, operand)?;
+    write!(output, " != {}", Self::NULL)
+  }
+
+  fn gen_expression(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    is_nested: bool,
+    expression: &Expression,
+  ) -> fmt::Result {
+    let start = output.len();
+    let is_parenthesized = match expression.expression_inner.as_ref().unwrap() {
+      ExpressionInner::Value(value) => {
+        self.gen_value(output, trace, value)?;
+        false
+      }
+      ExpressionInner::Comparison(comparison) => {
+        self.gen_comparison(output, comparison)?;
+        true
+      }
+      ExpressionInner::Logic(logic) => {
+        self.gen_logic(output, logic)?;
+        true
+      }
+      ExpressionInner::Control(control) => {
+        self.gen_control(output, control)?;
+        self.is_control_parenthesized(control)
+      }
+      ExpressionInner::Arithmetic(arithmetic) => {
+        self.gen_arithmetic(output, trace, arithmetic)?;
+        true
+      }
+    };
+    if is_nested && is_parenthesized {
+      output.insert(start, '(');
+      write!(output, ")")?;
+    }
+    Ok(())
+  }
+
+  fn gen_comparison(
+    &self,
+    output: &mut CodeWriter,
+    comparison: &Comparison,
+  ) -> fmt::Result {
+    match comparison.comparison_inner.as_ref().unwrap() {
+      ComparisonInner::EqualsTo(binary) => {
+        self.gen_binary(output, binary, Self::EQUALS_TO)
+      }
+      ComparisonInner::DifferentFrom(binary) => {
+        self.gen_binary(output, binary, Self::DIFFERENT_FROM)
+      }
+      ComparisonInner::LessThan(binary) => {
+        self.gen_order(output, binary, " < ")
+      }
+      ComparisonInner::MoreThan(binary) => {
+        self.gen_order(output, binary, " > ")
+      }
+      ComparisonInner::AtLeast(binary) => {
+        self.gen_order(output, binary, " >= ")
+      }
+      ComparisonInner::AtMost(binary) => self.gen_order(output, binary, " <= "),
+      ComparisonInner::In(binary) => self.gen_in(output, binary),
+    }
+  }
+
+  // missing operands are ordered as their empty value
+  fn gen_order(
+    &self,
+    output: &mut CodeWriter,
+    binary: &Binary,
+    operator: &str,
+  ) -> fmt::Result {
+    self.gen_or_empty(output, binary.left.as_ref().unwrap())?;
+    write!(output, "{operator}")?;
+    self.gen_or_empty(output, binary.right.as_ref().unwrap())
+  }
+
+  fn gen_logic(&self, output: &mut CodeWriter, logic: &Logic) -> fmt::Result {
+    match logic.logic_inner.as_ref().unwrap() {
+      LogicInner::And(operands) => {
+        self.gen_operands(output, &operands.operands, Self::AND)
+      }
+      LogicInner::Or(operands) => {
+        self.gen_operands(output, &operands.operands, Self::OR)
+      }
+      LogicInner::Not(operand) => {
+        write!(output, "!")?;
+        self.gen_expression(output, &[], true, operand)
+      }
+      LogicInner::Exists(operand) => self.gen_exists(output, operand),
+    }
+  }
+
+  fn gen_arithmetic(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    arithmetic: &Arithmetic,
+  ) -> fmt::Result {
+    match arithmetic.arithmetic_inner.as_ref().unwrap() {
+      ArithmeticInner::Opposite(operand) => {
+        write!(output, "-")?;
+        self.gen_expression(output, &[], true, operand)
+      }
+      ArithmeticInner::Add(binary) => self.gen_binary(output, binary, " + "),
+      ArithmeticInner::Sub(binary) => self.gen_binary(output, binary, " - "),
+      ArithmeticInner::Mul(binary) => self.gen_binary(output, binary, " * "),
+      ArithmeticInner::Div(binary) => self.gen_binary(output, binary, " / "),
+      ArithmeticInner::Mod(binary) => self.gen_mod(output, trace, binary),
+      ArithmeticInner::Pow(binary) => self.gen_pow(output, binary),
+    }
+  }
+
+  // the operands with `operator` in between, each one parenthesized if needed
+  fn gen_binary(
+    &self,
+    output: &mut CodeWriter,
+    binary: &Binary,
+    operator: &str,
+  ) -> fmt::Result {
+    self.gen_expression(output, &[], true, binary.left.as_ref().unwrap())?;
+    write!(output, "{operator}")?;
+    self.gen_expression(output, &[], true, binary.right.as_ref().unwrap())
+  }
+
+  fn gen_operands(
+    &self,
+    output: &mut CodeWriter,
+    operands: &[Expression],
+    operator: &str,
+  ) -> fmt::Result {
+    for (i, operand) in operands.iter().enumerate() {
+      if i > 0 {
+        write!(output, "{operator}")?;
+      }
+      self.gen_expression(output, &[], true, operand)?;
+    }
+    Ok(())
+  }
+
+  // `right<method>left)`, for `in` as a method of the collection
+  fn gen_contains(
+    &self,
+    output: &mut CodeWriter,
+    binary: &Binary,
+    method: &str,
+  ) -> fmt::Result {
+    self.gen_or_empty(output, binary.right.as_ref().unwrap())?;
+    write!(output, "{method}")?;
+    self.gen_expression(output, &[], false, binary.left.as_ref().unwrap())?;
+    write!(output, ")")
+  }
+
+  // `function(left, right)`, for the operators that are library functions
+  fn gen_call(
+    &self,
+    output: &mut CodeWriter,
+    function: &str,
+    binary: &Binary,
+  ) -> fmt::Result {
+    write!(output, "{function}(")?;
+    self.gen_expression(output, &[], false, binary.left.as_ref().unwrap())?;
+    write!(output, ", ")?;
+    self.gen_expression(output, &[], false, binary.right.as_ref().unwrap())?;
+    write!(output, ")")
+  }
+}
+
+pub fn is_positive_quantity(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner,
+    Some(ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Quantity(quantity)),
+    })) if quantity >= 0.
+  )
+}
@@ -0,0 +1,324 @@
+use alloc::{
+  fmt::{self, Write},
+  format,
+  string::String,
+  vec::Vec,
+};
+use core::mem::take;
+use dropin_compiler_recipes::ir::Source;
+
+const INDENT: &str = "  ";
+
+// columns after which a line is broken at its soft breaks
+const WIDTH: usize = 80;
+
+// code writer, line breaks and indentation are dropped when compact
+#[derive(Debug, Default)]
+pub struct CodeWriter<'a> {
+  code: String,
+  compact: bool,
+  depth: usize,
+  is_line_start: bool,
+  source: Option<&'a Source>,
+  // where the current line starts, and its indentation
+  line_start: usize,
+  line_depth: usize,
+  // soft breaks of the current line, and the soft groups currently open
+  breaks: Vec<SoftBreak>,
+  level: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Break {
+  // after the opening of a group, its items going one level deeper
+  Open,
+  // in place of the space following a separator between items
+  Item,
+  // before the closing of a group, with a trailing separator
+  Close,
+  // in place of a space, the rest going two levels deeper
+  Space,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SoftBreak {
+  index: usize,
+  level: usize,
+  r#break: Break,
+}
+
+impl<'a> CodeWriter<'a> {
+  // recipe locations are written as comments when a source is given
+  pub fn new(compact: bool, source: Option<&'a Source>) -> Self {
+    Self {
+      compact,
+      source,
+      ..Default::default()
+    }
+  }
+
+  pub fn has_source(&self, path: &str) -> bool {
+    self
+      .source
+      .is_some_and(|source| source.lines.contains_key(path))
+  }
+
+  // points to the recipe line of the yaml `path`, before the code it produced
+  pub fn source(&mut self, path: &str) -> fmt::Result {
+    let Some(source) = self.source else {
+      return Ok(());
+    };
+    let Some(line) = source.lines.get(path) else {
+      return Ok(());
+    };
+    let comment = format!("from {}:{line}", source.path);
+    if self.compact {
+      write!(self, "/* {comment} */")
+    } else {
+      write!(self, "// {comment}")?;
+      self.line()
+    }
+  }
+
+  pub fn line(&mut self) -> fmt::Result {
+    if !self.compact {
+      self.wrap();
+      self.code.push('\n');
+      self.is_line_start = true;
+      self.line_start = self.code.len();
+    }
+    Ok(())
+  }
+
+  // for tokens that must stay apart, like an annotation and its member
+  pub fn line_or_space(&mut self) -> fmt::Result {
+    if self.compact {
+      self.code.push(' ');
+      Ok(())
+    } else {
+      self.line()
+    }
+  }
+
+  pub fn indent(&mut self) {
+    self.depth += 1;
+  }
+
+  pub fn dedent(&mut self) {
+    self.depth -= 1;
+  }
+
+  // writes `open`, then the following lines one level deeper
+  pub fn open(&mut self, open: &str) -> fmt::Result {
+    self.write_str(open)?;
+    self.indent();
+    self.line()
+  }
+
+  // writes `close` one level shallower, on the line just started
+  pub fn close(&mut self, close: &str) -> fmt::Result {
+    self.dedent();
+    self.write_str(close)
+  }
+
+  // separates items of a list opened with `open`, with a trailing comma
+  pub fn item(&mut self) -> fmt::Result {
+    self.write_str(",")?;
+    self.line()
+  }
+
+  // opens a group of items that go on their own lines if the line is too long
+  pub fn soft_open(&mut self, open: &str) -> fmt::Result {
+    self.write_str(open)?;
+    self.soft_break(Break::Open, self.level);
+    self.level += 1;
+    Ok(())
+  }
+
+  // separates the items of a group opened with `soft_open`
+  pub fn soft_item(&mut self) -> fmt::Result {
+    self.write_str(",")?;
+    self.soft_break(Break::Item, self.level - 1);
+    self.write_str(" ")
+  }
+
+  pub fn soft_close(&mut self, close: &str) -> fmt::Result {
+    self.level -= 1;
+    let is_empty = self.breaks.last().is_some_and(|soft_break| {
+      soft_break.r#break == Break::Open && soft_break.index == self.code.len()
+    });
+    if is_empty {
+      self.breaks.pop();
+    } else {
+      self.soft_break(Break::Close, self.level);
+    }
+    self.write_str(close)
+  }
+
+  // a space where the line continues below if it is too long
+  pub fn soft_space(&mut self) -> fmt::Result {
+    self.soft_break(Break::Space, self.level);
+    self.write_str(" ")
+  }
+
+  fn soft_break(&mut self, r#break: Break, level: usize) {
+    if !self.compact {
+      self.breaks.push(SoftBreak {
+        index: self.code.len(),
+        level,
+        r#break,
+      });
+    }
+  }
+
+  // breaks the current line if it is too long
+  fn wrap(&mut self) {
+    let breaks = take(&mut self.breaks);
+    let start = self.line_start + self.line_depth * INDENT.len();
+    if breaks.is_empty() || self.code.len() <= start {
+      return;
+    }
+    let text = self.code.split_off(start);
+    self.code.truncate(self.line_start);
+    let breaks = breaks
+      .into_iter()
+      .map(|soft_break| SoftBreak {
+        index: soft_break.index - start,
+        ..soft_break
+      })
+      .collect::<Vec<_>>();
+    wrap_line(&mut self.code, self.line_depth, &text, &breaks);
+  }
+
+  pub fn len(&self) -> usize {
+    self.code.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.code.is_empty()
+  }
+
+  // a character inserted where a line starts goes after its indentation
+  pub fn insert(&mut self, mut index: usize, c: char) {
+    if index == self.line_start && !self.is_line_start {
+      index += self.line_depth * INDENT.len();
+    }
+    self.code.insert(index, c);
+    if index < self.line_start {
+      self.line_start += c.len_utf8();
+    }
+    for soft_break in &mut self.breaks {
+      if soft_break.index > index {
+        soft_break.index += c.len_utf8();
+      }
+    }
+  }
+
+  pub fn into_code(mut self) -> String {
+    if !self.compact {
+      self.wrap();
+      if !self.code.ends_with('\n') {
+        self.code.push('\n');
+      }
+    }
+    self.code
+  }
+}
+
+// writes `text` indented by `depth`, breaking it while it is too long at its
+// outermost soft breaks, spaces before groups
+fn wrap_line(
+  code: &mut String,
+  depth: usize,
+  text: &str,
+  breaks: &[SoftBreak],
+) {
+  let width = depth * INDENT.len() + text.chars().count();
+  let level = breaks.iter().map(|soft_break| soft_break.level).min();
+  let Some(level) = level.filter(|_| width > WIDTH) else {
+    for _ in 0..depth {
+      code.push_str(INDENT);
+    }
+    code.push_str(text);
+    return;
+  };
+  let has_spaces = breaks.iter().any(|soft_break| {
+    soft_break.level == level && soft_break.r#break == Break::Space
+  });
+  let is_taken = |soft_break: &SoftBreak| {
+    soft_break.level == level
+      && (soft_break.r#break == Break::Space) == has_spaces
+  };
+
+  // pieces of the line, with their depth and trailing separator
+  let mut pieces = Vec::new();
+  let mut start = 0;
+  let mut piece_depth = depth;
+  for soft_break in breaks.iter().filter(|soft_break| is_taken(soft_break)) {
+    let index = soft_break.index;
+    let (next, next_depth, separator) = match soft_break.r#break {
+      Break::Open => (index, depth + 1, ""),
+      Break::Item => (index + 1, depth + 1, ""),
+      Break::Close => (index, depth, ","),
+      Break::Space => (index + 1, depth + 2, ""),
+    };
+    pieces.push((piece_depth, start, index, separator));
+    start = next;
+    piece_depth = next_depth;
+  }
+  pieces.push((piece_depth, start, text.len(), ""));
+
+  for (i, (piece_depth, start, end, separator)) in
+    pieces.into_iter().enumerate()
+  {
+    if i > 0 {
+      code.push('\n');
+    }
+    let mut piece = String::from(&text[start..end]);
+    piece.push_str(separator);
+    let breaks = breaks
+      .iter()
+      .filter(|soft_break| {
+        !is_taken(soft_break)
+          && soft_break.index >= start
+          && soft_break.index <= end
+      })
+      .map(|soft_break| SoftBreak {
+        index: soft_break.index - start,
+        ..*soft_break
+      })
+      .collect::<Vec<_>>();
+    wrap_line(code, piece_depth, &piece, &breaks);
+  }
+}
+
+impl Write for CodeWriter<'_> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    if s.is_empty() {
+      return Ok(());
+    }
+    if self.is_line_start {
+      self.is_line_start = false;
+      self.line_depth = self.depth;
+      for _ in 0..self.depth {
+        self.code.push_str(INDENT);
+      }
+    }
+    self.code.push_str(s);
+    Ok(())
+  }
+}
+
+// yaml path of the format reached by `trace` from the `root` keys
+pub fn keys_source(root: &str, trace: &[&str]) -> String {
+  let mut path = String::from(root);
+  for key in trace {
+    if *key == "*" {
+      path.push_str(".format");
+    } else {
+      path.push_str(".keys.");
+      path.push_str(key);
+    }
+  }
+  path
+}
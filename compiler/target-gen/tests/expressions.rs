@@ -0,0 +1,141 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use core::fmt::{self, Write};
+
+use dropin_compiler_recipes::{
+  ir::{Binary, Control, Expression, ExpressionInner, Value, ValueInner},
+  parser::parse_expression,
+};
+use dropin_target_gen::{CodeWriter, ExpressionGen};
+
+// a language with the usual operators, `nil` and a method for `in`, where
+// the getters named `maybe…` may be missing
+struct Language;
+
+impl ExpressionGen for Language {
+  const EQUALS_TO: &'static str = " === ";
+  const NULL: &'static str = "nil";
+
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    _trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    match value.value_inner.as_ref().unwrap() {
+      ValueInner::Getter(getter) => write!(output, "{}", getter.ident),
+      ValueInner::Quantity(quantity) => write!(output, "{quantity}"),
+      ValueInner::Boolean(boolean) => write!(output, "{boolean}"),
+      value => panic!("unexpected value {value:?}"),
+    }
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    _control: &Control,
+  ) -> fmt::Result {
+    write!(output, "control")
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_call(output, "pow", binary)
+  }
+
+  fn is_optional(&self, operand: &Expression) -> bool {
+    matches!(
+      operand.expression_inner.as_ref().unwrap(),
+      ExpressionInner::Value(Value {
+        value_inner: Some(ValueInner::Getter(getter)),
+      }) if getter.ident.starts_with("maybe")
+    )
+  }
+
+  fn gen_empty(
+    &self,
+    output: &mut CodeWriter,
+    _operand: &Expression,
+  ) -> fmt::Result {
+    write!(output, "empty")
+  }
+
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_contains(output, binary, ".contains(")
+  }
+}
+
+fn gen(input: &str) -> String {
+  let mut output = CodeWriter::new(true, None);
+  let expression = parse_expression(input, None).unwrap();
+  Language
+    .gen_expression(&mut output, &[], false, &expression)
+    .unwrap();
+  output.into_code()
+}
+
+#[test]
+fn operators() {
+  let cases = [
+    ("a + b * c", "a + (b * c)"),
+    ("a - b - c", "(a - b) - c"),
+    (
+      "a % (b + 1)",
+      "((a, b) => ((a % b) + Math.abs(b)) % Math.abs(b))(a, b + 1)",
+    ),
+    ("a % 3", "((a % 3) + 3) % 3"),
+    ("a == b", "a === b"),
+    ("a != b", "a != b"),
+    ("a >= 1 & b <= 2", "(a >= 1) && (b <= 2)"),
+    ("a | b | !c", "a || b || (!c)"),
+    ("?a", "a != nil"),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(gen(input), expected, "{input}");
+  }
+}
+
+#[test]
+fn overridden_operators() {
+  let cases = [
+    ("a ^ (b + 1)", "pow(a, b + 1)"),
+    ("a + 1 in b", "b.contains(a + 1)"),
+    ("a in b | c", "(b.contains(a)) || c"),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(gen(input), expected, "{input}");
+  }
+}
+
+// missing collections and ordered operands stand for their empty value
+#[test]
+fn optional_operands() {
+  let cases = [
+    ("a in maybeB", "(maybeB ?? empty).contains(a)"),
+    ("maybeA in b", "b.contains(maybeA)"),
+    ("maybeA > 1", "(maybeA ?? empty) > 1"),
+    ("1 <= maybeA", "1 <= (maybeA ?? empty)"),
+    ("maybeA == 1", "maybeA === 1"),
+  ];
+  for (input, expected) in cases {
+    assert_eq!(gen(input), expected, "{input}");
+  }
+}
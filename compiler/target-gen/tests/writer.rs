@@ -0,0 +1,170 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use core::fmt::Write;
+
+use dropin_target_gen::CodeWriter;
+
+fn call(output: &mut CodeWriter, name: &str, args: &[&str]) {
+  write!(output, "{name}").unwrap();
+  output.soft_open("(").unwrap();
+  for (i, arg) in args.iter().enumerate() {
+    if i > 0 {
+      output.soft_item().unwrap();
+    }
+    write!(output, "{arg}").unwrap();
+  }
+  output.soft_close(")").unwrap();
+}
+
+#[test]
+fn indentation() {
+  let mut output = CodeWriter::new(false, None);
+  output.open("class A {").unwrap();
+  output.open("f(").unwrap();
+  write!(output, "1").unwrap();
+  output.item().unwrap();
+  output.close(");").unwrap();
+  output.line().unwrap();
+  output.close("}").unwrap();
+  assert_eq!(output.into_code(), "class A {\n  f(\n    1,\n  );\n}\n");
+}
+
+#[test]
+fn compact() {
+  let mut output = CodeWriter::new(true, None);
+  assert!(output.is_empty());
+  output.open("class A {").unwrap();
+  output.line_or_space().unwrap();
+  call(
+    &mut output,
+    "f",
+    &["a".repeat(50).as_str(), &"b".repeat(50)],
+  );
+  output.line().unwrap();
+  output.close("}").unwrap();
+  let expected =
+    format!("class A {{ f({}, {})}}", "a".repeat(50), "b".repeat(50));
+  assert_eq!(output.into_code(), expected);
+}
+
+#[test]
+fn short_lines_are_kept() {
+  let mut output = CodeWriter::new(false, None);
+  write!(output, "x =").unwrap();
+  output.soft_space().unwrap();
+  call(&mut output, "f", &["a", "b"]);
+  write!(output, ";").unwrap();
+  assert_eq!(output.into_code(), "x = f(a, b);\n");
+}
+
+#[test]
+fn groups_are_wrapped() {
+  let [a, b, c] = ["a", "b", "c"].map(|arg| arg.repeat(30));
+  let mut output = CodeWriter::new(false, None);
+  output.open("{").unwrap();
+  call(&mut output, "f", &[&a, &b, &c]);
+  write!(output, ";").unwrap();
+  output.line().unwrap();
+  output.close("}").unwrap();
+  assert_eq!(
+    output.into_code(),
+    format!("{{\n  f(\n    {a},\n    {b},\n    {c},\n  );\n}}\n"),
+  );
+}
+
+#[test]
+fn empty_groups_are_not_wrapped() {
+  let long = "a".repeat(90);
+  let mut output = CodeWriter::new(false, None);
+  call(&mut output, &long, &[]);
+  assert_eq!(output.into_code(), format!("{long}()\n"));
+}
+
+// the spaces are broken first, then the groups still too long
+#[test]
+fn spaces_are_wrapped_before_groups() {
+  let [a, b] = ["a", "b"].map(|arg| arg.repeat(40));
+  let mut output = CodeWriter::new(false, None);
+  write!(output, "int get x =>").unwrap();
+  output.soft_space().unwrap();
+  call(&mut output, "f", &[&a, &b]);
+  write!(output, ";").unwrap();
+  output.line().unwrap();
+  write!(output, "int get y =>").unwrap();
+  output.soft_space().unwrap();
+  call(&mut output, "g", &[&a[..30]]);
+  write!(output, ";").unwrap();
+  assert_eq!(
+    output.into_code(),
+    format!(
+      "int get x =>\n    f(\n      {a},\n      {b},\n    );\n\
+      int get y => g({});\n",
+      &a[..30],
+    ),
+  );
+}
+
+#[test]
+fn nested_groups_are_wrapped_when_still_too_long() {
+  let [a, b] = ["a", "b"].map(|arg| arg.repeat(40));
+  let mut output = CodeWriter::new(false, None);
+  write!(output, "f").unwrap();
+  output.soft_open("(").unwrap();
+  write!(output, "x").unwrap();
+  output.soft_item().unwrap();
+  call(&mut output, "g", &[&a, &b]);
+  output.soft_close(")").unwrap();
+  assert_eq!(
+    output.into_code(),
+    format!("f(\n  x,\n  g(\n    {a},\n    {b},\n  ),\n)\n"),
+  );
+}
+
+// parentheses added around an expression keep the breaks in place
+#[test]
+fn insert_moves_the_breaks() {
+  let [a, b] = ["a", "b"].map(|arg| arg.repeat(40));
+  let mut output = CodeWriter::new(false, None);
+  write!(output, "x =").unwrap();
+  output.soft_space().unwrap();
+  let start = output.len();
+  call(&mut output, "f", &[&a, &b]);
+  output.insert(start, '(');
+  write!(output, ")").unwrap();
+  assert_eq!(
+    output.into_code(),
+    format!("x =\n    (f(\n      {a},\n      {b},\n    ))\n"),
+  );
+}
+
+#[test]
+fn insert_keeps_the_indentation() {
+  let mut output = CodeWriter::new(false, None);
+  output.open("{").unwrap();
+  let start = output.len();
+  write!(output, "a ??= b").unwrap();
+  output.insert(start, '(');
+  write!(output, ").c = d;").unwrap();
+  output.line().unwrap();
+  output.close("}").unwrap();
+  assert_eq!(output.into_code(), "{\n  (a ??= b).c = d;\n}\n");
+}
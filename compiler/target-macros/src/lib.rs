@@ -1,83 +1,208 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+extern crate proc_macro;
+
+use std::{env, fs::read_to_string, path::PathBuf};
+
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::{
+  parse_macro_input, parse_str, Data, DeriveInput, Fields, FnArg, GenericParam,
+  Item, ItemUse, LitStr, Pat, PatIdent, TraitItem, Type,
+};
 
-#[proc_macro_derive(Stage)]
-pub fn stage(_item: TokenStream) -> TokenStream {
-  quote!().into()
-  /*
-  let mut r#struct: ItemStruct = syn::parse(item).unwrap();
-  let generics = &mut r#struct.generics;
-  let fields = &mut r#struct.fields;
-  let Fields::Unnamed(fields) = fields else {
-    panic!("combine takes a tuple-like struct");
-  };
-  let mut states = Vec::<Type>::with_capacity(fields.unnamed.len());
-  for (field_i, field) in fields.unnamed.iter_mut().enumerate() {
-    let mut attr_i = 0;
-    while attr_i < field.attrs.len() {
-      let meta = &field.attrs[attr_i].meta;
-      attr_i += 1;
-      let Meta::List(list) = meta else {
-        continue;
-      };
-      if list.path.is_ident("state") {
-        if states.len() > field_i {
-          panic!("multiple states provided");
-        }
-        attr_i -= 1;
-        let attr = field.attrs.remove(attr_i);
-        let Meta::List(list) = attr.meta else {
-          unreachable!();
-        };
-        states.push(syn::parse(list.tokens.into()).unwrap());
-      }
+// implements `Visit` for a struct of visitors, declared by the trait at the
+// `path` relative to the crate root. Each method is forwarded to the fields
+// with a `#[state(..)]` and `build` collects their states into the `state`
+// struct. Fields without a `#[state(..)]` are moved to the state as is, and
+// the state is `Stated` for each of the field states.
+#[proc_macro_derive(Visit, attributes(visit, state))]
+pub fn visit(item: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(item as DeriveInput);
+
+  let mut path: Option<LitStr> = None;
+  let mut state: Option<Type> = None;
+  for attr in &input.attrs {
+    if !attr.path().is_ident("visit") {
+      continue;
     }
-    if states.len() != field_i + 1 {
-      panic!("no state provided");
+    attr
+      .parse_nested_meta(|meta| {
+        if meta.path.is_ident("path") {
+          path = Some(meta.value()?.parse()?);
+          Ok(())
+        } else if meta.path.is_ident("state") {
+          state = Some(meta.value()?.parse()?);
+          Ok(())
+        } else {
+          Err(meta.error("expected `path` or `state`"))
+        }
+      })
+      .unwrap();
+  }
+  let path = path.expect("missing #[visit(path = ..)]").value();
+  let state = state.expect("missing #[visit(state = ..)]");
+  let Type::Path(state_path) = &state else {
+    panic!("the state must be a struct");
+  };
+  let mut state_name = state_path.path.clone();
+  state_name.segments.last_mut().unwrap().arguments = Default::default();
+
+  let Data::Struct(data) = &input.data else {
+    panic!("Visit can only be derived for structs");
+  };
+  let Fields::Named(fields) = &data.fields else {
+    panic!("Visit needs named fields");
+  };
+  let mut visitors = Vec::new();
+  let mut moved = Vec::new();
+  for field in &fields.named {
+    let ident = field.ident.clone().unwrap();
+    match field
+      .attrs
+      .iter()
+      .find(|attr| attr.path().is_ident("state"))
+    {
+      Some(attr) => visitors.push((ident, attr.parse_args::<Type>().unwrap())),
+      None => moved.push(ident),
     }
-    let generic = Ident::new(&format!("S{field_i}"), Span::call_site());
-    generics.params.push(parse_quote!(#generic: Stage));
-    let Type::Path(ty) = &mut field.ty else {
-      panic!("unexpected type");
-    };
-    let PathArguments::AngleBracketed(args) =
-      &mut ty.path.segments.last_mut().unwrap().arguments
-    else {
-      panic!("unexpected args");
-    };
-    args.args.push(parse_quote!(#generic));
   }
 
-  let name = r#struct.ident.clone();
-  let generics_declaration = r#struct.generics.clone();
-  let generics = r#struct
-    .generics
-    .params
+  let file = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join(&path);
+  let source = read_to_string(&file).unwrap();
+  let syntax = syn::parse_file(&source).unwrap();
+  let uses = syntax
+    .items
+    .iter()
+    .filter_map(|item| match item {
+      Item::Use(r#use) => Some(r#use),
+      _ => None,
+    })
+    .collect::<Vec<&ItemUse>>();
+  let r#trait = syntax
+    .items
     .iter()
-    .map(|generic| match generic {
-      GenericParam::Lifetime(generic) => generic.lifetime.to_token_stream(),
-      GenericParam::Type(generic) => generic.ident.to_token_stream(),
-      GenericParam::Const(_) => todo!("const generics"),
+    .find_map(|item| match item {
+      Item::Trait(r#trait) if r#trait.ident == "Visit" => Some(r#trait),
+      _ => None,
     })
-    .collect::<Vec<_>>();
-  let mut stream = r#struct.to_token_stream();
-  for (i, state) in states.into_iter().enumerate() {
-    let i = Index::from(i);
-    stream.extend(quote!(
-      impl #generics_declaration Stated<#state> for #name <#(#generics),*> {
-        fn state(&self) -> &#state {
-          &self.#i.state()
+    .unwrap_or_else(|| panic!("no Visit trait in {path}"));
+  let Some(GenericParam::Lifetime(lifetime)) = r#trait.generics.params.first()
+  else {
+    panic!("Visit must start with a lifetime");
+  };
+  let lifetime = &lifetime.lifetime;
+  let module = module_of(&path);
+
+  let methods = r#trait.items.iter().filter_map(|item| {
+    let TraitItem::Fn(method) = item else {
+      return None;
+    };
+    method.default.as_ref()?;
+    let mut sig = method.sig.clone();
+    let mut args = Vec::new();
+    for input in sig.inputs.iter_mut() {
+      let FnArg::Typed(input) = input else {
+        continue;
+      };
+      let arg = format_ident!("arg{}", args.len());
+      *input.pat = Pat::Ident(PatIdent {
+        attrs: Vec::new(),
+        by_ref: None,
+        mutability: None,
+        ident: arg.clone(),
+        subpat: None,
+      });
+      args.push(arg);
+    }
+    let name = &sig.ident;
+    let calls = visitors
+      .iter()
+      .map(|(field, _)| quote!(self.#field.#name(#(#args),*);));
+    Some(quote!(#sig { #(#calls)* }))
+  });
+
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) =
+    input.generics.split_for_impl();
+  let built = visitors.iter().map(|(field, _)| field);
+  let stated = visitors.iter().map(|(field, field_state)| {
+    quote!(
+      impl #impl_generics Stated<#field_state> for #state #where_clause {
+        fn state(&self) -> &#field_state {
+          &self.#field
         }
       }
-    ));
-  }
-  stream.extend(quote!(
-    impl #generics_declaration Stage for #name <#(#generics),*> {
-      fn ir(&self) -> &Model {
-        self.0.ir()
+    )
+  });
+  let file = file.to_str().unwrap();
+
+  quote!(
+    // rebuilds when the trait changes
+    const _: &str = include_str!(#file);
+
+    const _: () = {
+      #(
+        #[allow(unused_imports)]
+        #uses
+      )*
+      #[allow(unused_imports)]
+      use #module::*;
+
+      impl #impl_generics #module::Visit<#lifetime, #state>
+        for #name #ty_generics #where_clause
+      {
+        fn build(self) -> #state {
+          #state_name {
+            #(#built: self.#built.build(),)*
+            #(#moved: self.#moved,)*
+          }
+        }
+
+        #(#methods)*
       }
+
+      #(#stated)*
+    };
+  )
+  .into()
+}
+
+// `src/a/b.rs` and `src/a/b/mod.rs` are `crate::a::b`, as `tests/a/b.rs` is
+// in an integration test
+fn module_of(path: &str) -> syn::Path {
+  let path = path
+    .strip_prefix("src/")
+    .or_else(|| path.strip_prefix("tests/"))
+    .expect("the path must be in src/ or tests/");
+  let path = path
+    .strip_suffix(".rs")
+    .expect("the path must be a .rs file");
+  let mut module = String::from("crate");
+  for segment in path.split('/') {
+    if segment != "lib" && segment != "mod" {
+      module.push_str("::");
+      module.push_str(segment);
     }
-  ));
-  stream.into()
-  */
+  }
+  parse_str(&module).unwrap()
 }
@@ -0,0 +1,36 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub trait Stated<S> {
+  fn state(&self) -> &S;
+}
+
+pub trait Visit<'a, S> {
+  fn build(self) -> S;
+
+  fn visit_format(&mut self, _format: &'a str) {}
+
+  fn visit_different_from(&mut self, _left: &'a str, _right: &'a str) {}
+
+  fn visit_anonymous_function(&mut self, _arguments: &'a [String]) {}
+
+  fn visit_named_function(&mut self, _name: &'a str) {}
+}
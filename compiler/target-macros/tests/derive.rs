@@ -0,0 +1,99 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::visit::{Stated, Visit};
+use dropin_target_macros::Visit;
+
+// records the hooks it receives
+macro_rules! probe {
+  ($probe:ident, $state:ident) => {
+    #[derive(Debug, Default, PartialEq)]
+    struct $state(Vec<String>);
+
+    #[derive(Default)]
+    struct $probe($state);
+
+    impl<'a> Visit<'a, $state> for $probe {
+      fn build(self) -> $state {
+        self.0
+      }
+
+      fn visit_format(&mut self, format: &'a str) {
+        self.0 .0.push(format!("format {format}"));
+      }
+
+      fn visit_different_from(&mut self, left: &'a str, right: &'a str) {
+        self.0 .0.push(format!("{left} != {right}"));
+      }
+
+      fn visit_anonymous_function(&mut self, arguments: &'a [String]) {
+        self.0 .0.push(format!("|{}|", arguments.join(", ")));
+      }
+
+      fn visit_named_function(&mut self, name: &'a str) {
+        self.0 .0.push(format!("fn {name}"));
+      }
+    }
+  };
+}
+
+probe!(Formats, FormatsState);
+probe!(Types, TypesState);
+
+struct StageState<'a> {
+  name: &'a str,
+  formats: FormatsState,
+  types: TypesState,
+}
+
+#[derive(Visit)]
+#[visit(path = "tests/common/visit.rs", state = StageState<'a>)]
+struct Stage<'a> {
+  name: &'a str,
+  #[state(FormatsState)]
+  formats: Formats,
+  #[state(TypesState)]
+  types: Types,
+}
+
+// the hand-written stages forgot `visit_different_from` and the functions
+#[test]
+fn forwards_every_hook() {
+  let arguments = vec![String::from("a"), String::from("b")];
+  let mut stage = Stage {
+    name: "stage",
+    formats: Formats::default(),
+    types: Types::default(),
+  };
+  stage.visit_format("text");
+  stage.visit_different_from("a", "b");
+  stage.visit_anonymous_function(&arguments);
+  stage.visit_named_function("f");
+  let state = stage.build();
+  let hooks = ["format text", "a != b", "|a, b|", "fn f"];
+  assert_eq!(state.name, "stage");
+  assert_eq!(state.formats.0, hooks);
+  assert_eq!(state.types.0, hooks);
+  assert_eq!(Stated::<FormatsState>::state(&state).0, hooks);
+  assert_eq!(Stated::<TypesState>::state(&state).0, hooks);
+}
@@ -0,0 +1,141 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// fixtures and helpers shared by the tests of the targets, each test only
+// holding the code its target is expected to write
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::{ir::Model, parser::parse_model_from_files};
+use dropin_target::{Options, Target};
+
+pub const MODEL: &str = r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+
+// `%` is euclidean, whatever the sign of its operands
+pub const HOME: &str = r#"
+variables:
+  required:
+    count: '7'
+    step: '3'
+  keys:
+    count:
+      type: quantity
+    step:
+      type: quantity
+blocks:
+  - type: text
+    content: "${count % 3} ${count % step}"
+"#;
+
+// variables start from their defaults, missing containers are created on write
+pub const USER: &str = r#"
+variables:
+  required:
+    user: ''
+  keys:
+    user:
+      type: object
+      required:
+        first: '"anon"'
+        id: ''
+      keys:
+        id:
+          type: quantity
+        first:
+          type: text
+        friends:
+          type: list
+          format:
+            type: object
+            keys:
+              name:
+                type: text
+blocks:
+  - type: text
+    content: "${user.first} ${user.friends[0].name}"
+  - type: input
+    on_change: user.friends[0].name
+"#;
+
+// collections and ordered operands that may be missing read as empty
+pub const OPTIONAL: &str = r#"
+variables:
+  required:
+    name: '"a"'
+  keys:
+    name:
+      type: text
+    user:
+      type: object
+      keys:
+        tags:
+          type: list
+          format:
+            type: text
+        scores:
+          type: index
+          format:
+            type: quantity
+        age:
+          type: quantity
+blocks:
+  - type: text
+    content: "${name in user.tags} ${user.scores[name] > 1} ${user.age <= 3}"
+"#;
+
+pub fn model(home: &str) -> Model {
+  model_of(MODEL, &[("home", home)])
+}
+
+pub fn model_of(model: &str, components: &[(&str, &str)]) -> Model {
+  let mut files = BTreeMap::from([("model.dropin.yml".into(), model.into())]);
+  for (id, recipe) in components {
+    files.insert(format!("components/{id}.dropin.yml"), (*recipe).into());
+  }
+  parse_model_from_files(&files).unwrap()
+}
+
+// the files written for `model`, by path
+pub fn generate_model(
+  target: &impl Target,
+  model: &Model,
+) -> BTreeMap<String, String> {
+  let generated = target.generate(model, &Options::default()).unwrap();
+  generated
+    .files
+    .into_iter()
+    .map(|file| (file.path, file.content))
+    .collect()
+}
+
+// all the code written for a home component
+pub fn generate(target: &impl Target, home: &str) -> String {
+  generate_model(target, &model(home)).into_values().collect()
+}
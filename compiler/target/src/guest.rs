@@ -0,0 +1,124 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{format, string::String, vec::Vec};
+use dropin_compiler_common::{Diagnostic, Severity};
+use dropin_compiler_recipes::ir::Model;
+use prost::Message;
+
+use crate::{Options, Target};
+
+pub mod bindings {
+  wit_bindgen::generate!({
+    path: "wit",
+    world: "plugin",
+    pub_export_macro: true,
+    default_bindings_module: "dropin_target::guest::bindings",
+  });
+}
+
+pub use bindings::exports::blueforest::dropin::target as wit;
+
+// implements the `plugin` world exports with a `Target` expression, for
+// crates built as a WebAssembly component
+#[macro_export]
+macro_rules! export_target {
+  ($target:expr) => {
+    struct DropinTargetGuest;
+
+    impl $crate::guest::wit::Guest for DropinTargetGuest {
+      fn name() -> String {
+        $crate::guest::name(&$target)
+      }
+
+      fn options() -> Vec<$crate::guest::wit::OptionSchema> {
+        $crate::guest::options(&$target)
+      }
+
+      fn generate(
+        model: Vec<u8>,
+        options: Vec<String>,
+      ) -> Result<
+        $crate::guest::wit::Generated,
+        Vec<$crate::guest::wit::Diagnostic>,
+      > {
+        $crate::guest::generate(&$target, model, options)
+      }
+    }
+
+    $crate::guest::bindings::export!(DropinTargetGuest);
+  };
+}
+
+pub fn name(target: &dyn Target) -> String {
+  target.name().into()
+}
+
+pub fn options(target: &dyn Target) -> Vec<wit::OptionSchema> {
+  target
+    .options()
+    .iter()
+    .map(|option| wit::OptionSchema {
+      name: option.name.clone().into_owned(),
+      description: option.description.clone().into_owned(),
+    })
+    .collect()
+}
+
+pub fn generate(
+  target: &dyn Target,
+  model: Vec<u8>,
+  flags: Vec<String>,
+) -> Result<wit::Generated, Vec<wit::Diagnostic>> {
+  let model = Model::decode(model.as_slice()).map_err(|err| {
+    let message = format!("invalid model: {err}");
+    vec![to_wit(Diagnostic::error(target.name(), message))]
+  })?;
+  let mut options = Options::default();
+  for flag in &flags {
+    options.set(flag);
+  }
+  match target.generate(&model, &options) {
+    Ok(generated) => Ok(wit::Generated {
+      files: generated
+        .files
+        .into_iter()
+        .map(|file| wit::OutputFile {
+          path: file.path,
+          content: file.content,
+        })
+        .collect(),
+      warnings: generated.warnings.0.into_iter().map(to_wit).collect(),
+    }),
+    Err(diagnostics) => Err(diagnostics.0.into_iter().map(to_wit).collect()),
+  }
+}
+
+fn to_wit(diagnostic: Diagnostic) -> wit::Diagnostic {
+  wit::Diagnostic {
+    severity: match diagnostic.severity {
+      Severity::Warning => wit::Severity::Warning,
+      Severity::Error => wit::Severity::Error,
+    },
+    component: diagnostic.component,
+    message: diagnostic.message,
+  }
+}
@@ -0,0 +1,163 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{cell::RefCell, path::Path};
+
+use anyhow::Result;
+use dropin_compiler_common::{Diagnostic, Severity};
+use dropin_compiler_recipes::ir::Model;
+use prost::Message;
+use wasmtime::{
+  component::{Component, Linker, ResourceTable},
+  Engine, Store,
+};
+use wasmtime_wasi::{IoView, WasiCtx, WasiCtxBuilder, WasiView};
+
+use crate::{
+  Diagnostics, Generated, OptionSchema, Options, OutputFile, Target,
+};
+
+mod bindings {
+  wasmtime::component::bindgen!({ path: "wit", world: "plugin" });
+}
+
+use bindings::exports::blueforest::dropin::target as wit;
+
+// a target compiled to a WebAssembly component of the `plugin` world
+pub struct WasmTarget {
+  name: String,
+  options: Vec<OptionSchema>,
+  plugin: bindings::Plugin,
+  store: RefCell<Store<State>>,
+}
+
+impl WasmTarget {
+  pub fn load(path: &Path) -> Result<Self> {
+    let engine = Engine::default();
+    let component = Component::from_file(&engine, path)?;
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+    let mut store = Store::new(
+      &engine,
+      State {
+        wasi: WasiCtxBuilder::new().inherit_stderr().build(),
+        table: ResourceTable::new(),
+      },
+    );
+    let plugin =
+      bindings::Plugin::instantiate(&mut store, &component, &linker)?;
+    let target = plugin.blueforest_dropin_target();
+    let name = target.call_name(&mut store)?;
+    let options = target
+      .call_options(&mut store)?
+      .into_iter()
+      .map(|option| OptionSchema {
+        name: option.name.into(),
+        description: option.description.into(),
+      })
+      .collect();
+    Ok(Self {
+      name,
+      options,
+      plugin,
+      store: RefCell::new(store),
+    })
+  }
+}
+
+impl Target for WasmTarget {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn options(&self) -> &[OptionSchema] {
+    &self.options
+  }
+
+  fn generate(
+    &self,
+    model: &Model,
+    options: &Options,
+  ) -> Result<Generated, Diagnostics> {
+    let flags = options.iter().map(String::from).collect::<Vec<_>>();
+    let result = self.plugin.blueforest_dropin_target().call_generate(
+      &mut *self.store.borrow_mut(),
+      &model.encode_to_vec(),
+      &flags,
+    );
+    match result {
+      Ok(Ok(generated)) => Ok(Generated {
+        files: generated
+          .files
+          .into_iter()
+          .map(|file| OutputFile {
+            path: file.path,
+            content: file.content,
+          })
+          .collect(),
+        warnings: generated
+          .warnings
+          .into_iter()
+          .map(from_wit)
+          .collect::<Vec<_>>()
+          .into(),
+      }),
+      Ok(Err(diagnostics)) => Err(
+        diagnostics
+          .into_iter()
+          .map(from_wit)
+          .collect::<Vec<_>>()
+          .into(),
+      ),
+      Err(err) => {
+        Err(vec![Diagnostic::error(&self.name, format!("{err:#}"))].into())
+      }
+    }
+  }
+}
+
+fn from_wit(diagnostic: wit::Diagnostic) -> Diagnostic {
+  Diagnostic {
+    severity: match diagnostic.severity {
+      wit::Severity::Warning => Severity::Warning,
+      wit::Severity::Error => Severity::Error,
+    },
+    component: diagnostic.component,
+    message: diagnostic.message,
+  }
+}
+
+struct State {
+  wasi: WasiCtx,
+  table: ResourceTable,
+}
+
+impl IoView for State {
+  fn table(&mut self) -> &mut ResourceTable {
+    &mut self.table
+  }
+}
+
+impl WasiView for State {
+  fn ctx(&mut self) -> &mut WasiCtx {
+    &mut self.wasi
+  }
+}
@@ -0,0 +1,183 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg_attr(not(any(feature = "host", feature = "guest")), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "guest")]
+pub mod guest;
+#[cfg(feature = "host")]
+pub mod host;
+
+use alloc::{
+  borrow::Cow,
+  collections::{BTreeMap, BTreeSet},
+  string::String,
+  vec,
+  vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+use dropin_compiler_common::{Diagnostic, Severity};
+use dropin_compiler_recipes::ir::Model;
+
+// a code generator, selected by its name
+pub trait Target {
+  fn name(&self) -> &str;
+
+  // flags accepted by `generate`
+  fn options(&self) -> &[OptionSchema] {
+    &[]
+  }
+
+  // warnings don't fail the generation, they come back with its files
+  fn generate(
+    &self,
+    model: &Model,
+    options: &Options,
+  ) -> Result<Generated, Diagnostics>;
+}
+
+// borrowed by the built-in targets, owned by the ones loaded at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionSchema {
+  pub name: Cow<'static, str>,
+  pub description: Cow<'static, str>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+  flags: BTreeSet<String>,
+}
+
+impl Options {
+  pub fn set(&mut self, name: &str) {
+    self.flags.insert(name.into());
+  }
+
+  pub fn is_set(&self, name: &str) -> bool {
+    self.flags.contains(name)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &str> {
+    self.flags.iter().map(String::as_str)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputFile {
+  // relative to the output directory, with `/` separators
+  pub path: String,
+  pub content: String,
+}
+
+impl OutputFile {
+  // whether `path` stays in the output directory, all its segments being names
+  pub fn has_valid_path(&self) -> bool {
+    self.path.split('/').all(|segment| {
+      !matches!(segment, "" | "." | "..") && !segment.contains(['\\', ':'])
+    })
+  }
+}
+
+impl From<(String, String)> for OutputFile {
+  fn from((path, content): (String, String)) -> Self {
+    Self { path, content }
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct Generated {
+  pub files: Vec<OutputFile>,
+  pub warnings: Diagnostics,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl Diagnostics {
+  pub fn push(&mut self, diagnostic: Diagnostic) {
+    self.0.push(diagnostic);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn has_errors(&self) -> bool {
+    self
+      .0
+      .iter()
+      .any(|diagnostic| diagnostic.severity == Severity::Error)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+    self.0.iter()
+  }
+
+  // the generated files, unless an error was diagnosed
+  pub fn into_result(
+    self,
+    files: Vec<OutputFile>,
+  ) -> Result<Generated, Diagnostics> {
+    if self.has_errors() {
+      return Err(self);
+    }
+    Ok(Generated {
+      files,
+      warnings: self,
+    })
+  }
+
+  // the files of `gen`, which only runs when no error was diagnosed
+  pub fn generate<F>(
+    self,
+    target: &str,
+    gen: F,
+  ) -> Result<Generated, Diagnostics>
+  where
+    F: FnOnce() -> Result<BTreeMap<String, String>, fmt::Error>,
+  {
+    if self.has_errors() {
+      return Err(self);
+    }
+    let Ok(files) = gen() else {
+      let message = "could not write the generated code".into();
+      return Err(vec![Diagnostic::error(target, message)].into());
+    };
+    self.into_result(files.into_iter().map(OutputFile::from).collect())
+  }
+}
+
+impl From<Vec<Diagnostic>> for Diagnostics {
+  fn from(diagnostics: Vec<Diagnostic>) -> Self {
+    Self(diagnostics)
+  }
+}
+
+impl Display for Diagnostics {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for diagnostic in &self.0 {
+      writeln!(f, "{diagnostic}")?;
+    }
+    Ok(())
+  }
+}
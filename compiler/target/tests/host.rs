@@ -0,0 +1,130 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{fs::write, path::PathBuf};
+
+use dropin_compiler_common::Severity;
+use dropin_compiler_recipes::ir::Model;
+use dropin_target::{host::WasmTarget, Options, Target};
+use wit_component::{
+  embed_component_metadata, ComponentEncoder, StringEncoding,
+};
+use wit_parser::Resolve;
+
+// a plugin named "echo", writing its first option to `options.txt`, or
+// failing when none is set
+const ECHO: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+  (data (i32.const 0) "echo")
+  (data (i32.const 8) "\00\00\00\00\04\00\00\00")
+  (data (i32.const 16) "verbose")
+  (data (i32.const 24) "prints more")
+  (data (i32.const 40) "\10\00\00\00\07\00\00\00\18\00\00\00\0b\00\00\00")
+  (data (i32.const 56) "\28\00\00\00\01\00\00\00")
+  (data (i32.const 64) "options.txt")
+  (data (i32.const 80) "no options")
+  (data (i32.const 96) "\01\00\00\00\00\00\00\00\04\00\00\00\50\00\00\00\0a\00\00\00")
+  (data (i32.const 120) "\01\00\00\00\60\00\00\00\01\00\00\00")
+  (data (i32.const 136) "\40\00\00\00\0b\00\00\00")
+  (data (i32.const 152) "\00\00\00\00\88\00\00\00\01\00\00\00\00\00\00\00\00\00\00\00")
+  (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+    (local $ptr i32)
+    global.get $heap
+    local.get 2
+    i32.add
+    i32.const 1
+    i32.sub
+    i32.const 0
+    local.get 2
+    i32.sub
+    i32.and
+    local.tee $ptr
+    local.get 3
+    i32.add
+    global.set $heap
+    local.get $ptr)
+  (func (export "blueforest:dropin/target#name") (result i32)
+    i32.const 8)
+  (func (export "blueforest:dropin/target#options") (result i32)
+    i32.const 56)
+  (func (export "blueforest:dropin/target#generate")
+    (param i32 i32 i32 i32) (result i32)
+    local.get 3
+    i32.eqz
+    if (result i32)
+      i32.const 120
+    else
+      i32.const 144
+      local.get 2
+      i32.load
+      i32.store
+      i32.const 148
+      local.get 2
+      i32.load offset=4
+      i32.store
+      i32.const 152
+    end))
+"#;
+
+fn echo() -> WasmTarget {
+  let mut resolve = Resolve::default();
+  let (package, _) = resolve.push_dir("wit").unwrap();
+  let world = resolve.select_world(package, Some("plugin")).unwrap();
+  let mut module = wat::parse_str(ECHO).unwrap();
+  embed_component_metadata(&mut module, &resolve, world, StringEncoding::UTF8)
+    .unwrap();
+  let component = ComponentEncoder::default()
+    .module(&module)
+    .unwrap()
+    .validate(true)
+    .encode()
+    .unwrap();
+  let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("echo.wasm");
+  write(&path, component).unwrap();
+  WasmTarget::load(&path).unwrap()
+}
+
+#[test]
+fn plugin() {
+  let target = echo();
+  assert_eq!(target.name(), "echo");
+  assert_eq!(target.options().len(), 1);
+  assert_eq!(target.options()[0].name, "verbose");
+  assert_eq!(target.options()[0].description, "prints more");
+
+  let model = Model::default();
+  let diagnostics = target.generate(&model, &Options::default()).unwrap_err();
+  assert!(diagnostics.has_errors());
+  let diagnostic = diagnostics.iter().next().unwrap();
+  assert_eq!(diagnostic.severity, Severity::Error);
+  assert_eq!(diagnostic.component, "echo");
+  assert_eq!(diagnostic.message, "no options");
+
+  let mut options = Options::default();
+  options.set("verbose");
+  let generated = target.generate(&model, &options).unwrap();
+  assert!(generated.warnings.is_empty());
+  assert_eq!(generated.files.len(), 1);
+  assert_eq!(generated.files[0].path, "options.txt");
+  assert_eq!(generated.files[0].content, "verbose");
+}
@@ -0,0 +1,57 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target::OutputFile;
+
+fn is_valid(path: &str) -> bool {
+  OutputFile {
+    path: path.into(),
+    content: String::new(),
+  }
+  .has_valid_path()
+}
+
+#[test]
+fn valid_paths() {
+  for path in ["main.dart", "components/home.ts", "a/b/c.d.rs", ".env"] {
+    assert!(is_valid(path), "{path}");
+  }
+}
+
+// a path leaving the output directory is refused
+#[test]
+fn invalid_paths() {
+  let paths = [
+    "",
+    "/etc/passwd",
+    "../../.bashrc",
+    "a/../../b",
+    "./a",
+    "a//b",
+    "a/",
+    "C:/Windows",
+    "C:a",
+    "..\\a",
+  ];
+  for path in paths {
+    assert!(!is_valid(path), "{path}");
+  }
+}
@@ -0,0 +1,87 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::BTreeMap,
+  fmt::{self, Write},
+};
+use dropin_target_analysis::{
+  objects_getter::{write_class_name, ObjectGetterState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{gen_keys, Sub};
+
+pub fn gen_classes<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for (trace, format) in <S as Stated<ObjectGetterState>>::state(state)
+    .get(component)
+    .unwrap_or(&BTreeMap::new())
+  {
+    output.line()?;
+    // data classes need at least one property
+    if format.keys.is_empty() {
+      write!(output, "class ")?;
+      write_class_name(output, trace)?;
+      output.open(" {")?;
+      write!(output, "fun toMap(): Map<String, Any?> = emptyMap()")?;
+    } else {
+      write!(output, "data class ")?;
+      write_class_name(output, trace)?;
+      output.open("(")?;
+      gen_keys(
+        output,
+        component,
+        state,
+        trace,
+        &format.required,
+        &format.keys,
+        true,
+      )?;
+      output.close(")")?;
+      output.open(" {")?;
+      write!(output, "fun toMap(): Map<String, Any?> = mapOf(")?;
+      let mut is_first = true;
+      for key_format in &format.keys {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        write!(output, "\"{0}\" to {0}", key_format.key)?;
+      }
+      write!(output, ")")?;
+    }
+    output.line()?;
+    output.line()?;
+    write!(output, "operator fun get(key: String): Any? = toMap()[key]")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+  }
+  Ok(())
+}
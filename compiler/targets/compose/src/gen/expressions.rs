@@ -0,0 +1,163 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Binary, Control, Expression, ExpressionInner, Format, FormatInner, Getter,
+  Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key, Stated,
+};
+use dropin_target_gen::{
+  expressions::is_positive_quantity, CodeWriter, ExpressionGen,
+};
+
+pub use self::{
+  control::gen_control,
+  value::{gen_getter, gen_rich_text, gen_setter, gen_value},
+};
+
+use super::{formats::gen_empty, Sub};
+
+mod control;
+mod value;
+
+pub fn gen_expressions<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  is_nested: bool,
+  expression: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  Expressions { component, state }
+    .gen_expression(output, trace, is_nested, expression)
+}
+
+struct Expressions<'s, S> {
+  component: &'s str,
+  state: &'s S,
+}
+
+impl<'a, 's, S> Expressions<'s, S>
+where
+  'a: 's,
+  S: Sub<'a>,
+{
+  // a nullable getter and its format, when it has an empty value
+  fn nullable<'e>(
+    &self,
+    operand: &'e Expression,
+  ) -> Option<(&'e Getter, &'s Format)> {
+    let ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Getter(getter)),
+    }) = operand.expression_inner.as_ref().unwrap()
+    else {
+      return None;
+    };
+    let mut read = CodeWriter::new(true, None);
+    if !gen_getter(&mut read, self.component, self.state, getter).ok()? {
+      return None;
+    }
+    let format = <S as Stated<FormatsState>>::state(self.state)
+      .format_of(self.component, getter)?;
+    if let Some(FormatInner::Any(_)) = format.format_inner {
+      return None;
+    }
+    Some((getter, format))
+  }
+}
+
+impl<'a, S> ExpressionGen for Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  const COALESCE: &'static str = " ?: ";
+
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    gen_value(output, self.component, self.state, trace, value)
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result {
+    gen_control(output, self.component, self.state, control)
+  }
+
+  // `mod` takes the sign of its divisor, positive here
+  fn gen_mod(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    binary: &Binary,
+  ) -> fmt::Result {
+    let right = binary.right.as_ref().unwrap();
+    self.gen_expression(output, trace, true, binary.left.as_ref().unwrap())?;
+    if is_positive_quantity(right) {
+      write!(output, ".mod(")?;
+      self.gen_expression(output, trace, false, right)?;
+      return write!(output, ")");
+    }
+    write!(output, ".mod(Math.abs(")?;
+    self.gen_expression(output, trace, false, right)?;
+    write!(output, "))")
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_call(output, "Math.pow", binary)
+  }
+
+  fn is_optional(&self, operand: &Expression) -> bool {
+    self.nullable(operand).is_some()
+  }
+
+  fn gen_empty(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    let (getter, format) = self.nullable(operand).unwrap();
+    let trace = [getter.ident.as_str()]
+      .into_iter()
+      .chain(
+        getter
+          .indexes
+          .iter()
+          .map(|key| static_key(key).unwrap_or("*")),
+      )
+      .collect::<Vec<_>>();
+    gen_empty(output, &trace, format)
+  }
+}
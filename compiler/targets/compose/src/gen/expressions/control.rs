@@ -0,0 +1,142 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_recipes::ir::{Control, ControlInner};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::gen_expressions;
+
+pub fn gen_control<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  control: &Control,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match control.control_inner.as_ref().unwrap() {
+    ControlInner::If(control) => {
+      write!(output, "if (")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        &[],
+        false,
+        control.condition.as_ref().unwrap(),
+      )?;
+      write!(output, ") ")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        &[],
+        true,
+        control.then.as_ref().unwrap(),
+      )?;
+      write!(output, " else ")?;
+      if let Some(r#else) = &control.r#else {
+        gen_expressions(output, component, state, &[], true, r#else)?;
+      } else {
+        write!(output, "null")?;
+      }
+    }
+    ControlInner::AnonymousFunction(control) => {
+      write!(output, "{{ ")?;
+      let mut is_first = true;
+      for arg in &control.args {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        write!(output, "{arg}")?;
+      }
+      if !control.args.is_empty() {
+        write!(output, " -> ")?;
+      }
+      gen_expressions(
+        output,
+        component,
+        state,
+        &[],
+        false,
+        control.body.as_ref().unwrap(),
+      )?;
+      write!(output, " }}")?;
+    }
+    ControlInner::NamedFunction(control) => {
+      // local functions are declarations, their reference is the expression
+      write!(output, "run {{ fun {}", control.name)?;
+      gen_params(output, &control.args)?;
+      write!(output, ": Any? = ")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        &[],
+        false,
+        control.body.as_ref().unwrap(),
+      )?;
+      write!(output, "; ::{} }}", control.name)?;
+    }
+    ControlInner::FunctionCall(control) => {
+      gen_expressions(
+        output,
+        component,
+        state,
+        &[],
+        true,
+        control.function.as_ref().unwrap(),
+      )?;
+      write!(output, "(")?;
+      let mut is_first = true;
+      for arg in &control.args {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        gen_expressions(output, component, state, &[], false, arg)?;
+      }
+      write!(output, ")")?;
+    }
+  }
+  Ok(())
+}
+
+fn gen_params(output: &mut CodeWriter, args: &[String]) -> fmt::Result {
+  write!(output, "(")?;
+  let mut is_first = true;
+  for arg in args {
+    if !is_first {
+      write!(output, ", ")?;
+    }
+    is_first = false;
+    write!(output, "{arg}: Any?")?;
+  }
+  write!(output, ")")
+}
@@ -0,0 +1,264 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  format,
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Expression, ExpressionInner, FormatInner, Getter, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::{expressions::gen_expressions, Sub};
+
+// returns whether the value read is nullable, safe calls make the rest of the
+// chain nullable too
+pub fn gen_getter<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  value: &Getter,
+) -> Result<bool, fmt::Error>
+where
+  S: Sub<'a>,
+{
+  let start = output.len();
+  write!(output, "{}", value.ident)?;
+  let mut is_nullable = is_root_nullable(component, state, &value.ident);
+  let mut is_chained = false;
+  let mut trace_current = Vec::with_capacity(value.indexes.len() + 1);
+  trace_current.push(value.ident.as_str());
+  for (i, key) in value.indexes.iter().enumerate() {
+    let trace_key = static_key(key).unwrap_or("*");
+    is_chained |= is_nullable;
+    let operator = if is_nullable { "?." } else { "." };
+    match receiver_of(component, state, value, i) {
+      Receiver::Object(required) if trace_key != "*" => {
+        write!(output, "{operator}{trace_key}")?;
+        is_nullable = !required.contains(&trace_key);
+      }
+      Receiver::Object(_) | Receiver::Index => {
+        write!(output, "{operator}get(")?;
+        gen_expressions(output, component, state, &trace_current, false, key)?;
+        write!(output, ")")?;
+        is_nullable = true;
+      }
+      Receiver::List => {
+        write!(output, "{operator}getOrNull(")?;
+        gen_list_index(output, component, state, &trace_current, key)?;
+        write!(output, ")")?;
+        is_nullable = true;
+      }
+      Receiver::Any => {
+        output.insert(start, '(');
+        write!(output, " as? Map<*, *>)?.get(")?;
+        gen_expressions(output, component, state, &trace_current, false, key)?;
+        write!(output, ")")?;
+        is_nullable = true;
+      }
+    }
+    trace_current.push(trace_key);
+  }
+  Ok(is_chained || is_nullable)
+}
+
+// data classes are immutable, nested keys are set on copies
+pub fn gen_setter<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  write!(output, "{} = ", getter.ident)?;
+  gen_copy(
+    output,
+    component,
+    state,
+    getter,
+    0,
+    getter.ident.clone(),
+    is_root_nullable(component, state, &getter.ident),
+    value,
+  )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gen_copy<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  depth: usize,
+  receiver: String,
+  is_nullable: bool,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let Some(key) = getter.indexes.get(depth) else {
+    return write!(output, "{value}");
+  };
+  let receiver = if is_nullable {
+    write!(output, "{receiver}?.let {{ r{depth} -> ")?;
+    format!("r{depth}")
+  } else {
+    receiver
+  };
+  let trace_key = static_key(key);
+  let trace = [getter.ident.as_str()];
+  match receiver_of(component, state, getter, depth) {
+    Receiver::Object(required) if trace_key.is_some() => {
+      let trace_key = trace_key.unwrap();
+      write!(output, "{receiver}.copy({trace_key} = ")?;
+      gen_copy(
+        output,
+        component,
+        state,
+        getter,
+        depth + 1,
+        format!("{receiver}.{trace_key}"),
+        !required.contains(&trace_key),
+        value,
+      )?;
+      write!(output, ")")?;
+    }
+    Receiver::List => {
+      let mut index = CodeWriter::new(true, None);
+      gen_list_index(&mut index, component, state, &trace, key)?;
+      let index = index.into_code();
+      write!(
+        output,
+        "{receiver}.toMutableList().apply {{ if ({index} in indices) set({index}, "
+      )?;
+      gen_copy(
+        output,
+        component,
+        state,
+        getter,
+        depth + 1,
+        format!("{receiver}[{index}]"),
+        false,
+        value,
+      )?;
+      write!(output, ") }}")?;
+    }
+    Receiver::Index => {
+      let mut index = CodeWriter::new(true, None);
+      gen_expressions(&mut index, component, state, &trace, false, key)?;
+      let index = index.into_code();
+      write!(output, "{receiver}.toMutableMap().apply {{ put({index}, ")?;
+      gen_copy(
+        output,
+        component,
+        state,
+        getter,
+        depth + 1,
+        format!("{receiver}[{index}]"),
+        true,
+        value,
+      )?;
+      write!(output, ") }}")?;
+    }
+    // dynamic keys of objects and untyped values can't be set
+    Receiver::Object(_) | Receiver::Any => write!(output, "{receiver}")?,
+  }
+  if is_nullable {
+    write!(output, " }}")?;
+  }
+  Ok(())
+}
+
+enum Receiver<'a> {
+  Object(Vec<&'a str>),
+  List,
+  Index,
+  Any,
+}
+
+// format of the getter value indexed by its `depth`th key
+fn receiver_of<'a, 'b, S>(
+  component: &str,
+  state: &'b S,
+  getter: &Getter,
+  depth: usize,
+) -> Receiver<'b>
+where
+  'a: 'b,
+  S: Sub<'a>,
+{
+  let receiver = Getter {
+    ident: getter.ident.clone(),
+    indexes: getter.indexes[..depth].to_vec(),
+  };
+  let format = <S as Stated<FormatsState>>::state(state)
+    .format_of(component, &receiver)
+    .and_then(|format| format.format_inner.as_ref());
+  match format {
+    Some(FormatInner::Object(object)) => {
+      Receiver::Object(object.required.keys().map(String::as_str).collect())
+    }
+    Some(FormatInner::List(_)) => Receiver::List,
+    Some(FormatInner::Index(_)) => Receiver::Index,
+    _ => Receiver::Any,
+  }
+}
+
+// quantities are doubles, list indexes are ints
+fn gen_list_index<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  key: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if let ExpressionInner::Value(Value {
+    value_inner: Some(ValueInner::Quantity(index)),
+  }) = key.expression_inner.as_ref().unwrap()
+  {
+    return write!(output, "{}", *index as i64);
+  }
+  gen_expressions(output, component, state, trace, true, key)?;
+  write!(output, ".toInt()")
+}
+
+// only the keys missing from `required` hold null
+fn is_root_nullable<'a, S>(component: &str, state: &S, ident: &str) -> bool
+where
+  S: Sub<'a>,
+{
+  <S as Stated<FormatsState>>::state(state)
+    .default_of(component, ident)
+    .is_none()
+}
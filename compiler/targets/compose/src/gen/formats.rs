@@ -0,0 +1,111 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{Format, FormatInner};
+use dropin_target_analysis::objects_getter::write_class_name;
+
+use super::is_undefined;
+
+pub fn gen_format(
+  output: &mut impl Write,
+  trace: &[&str],
+  format: &Format,
+) -> fmt::Result {
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Any(_) => write!(output, "Any?")?,
+    FormatInner::Boolean(_) => write!(output, "Boolean")?,
+    FormatInner::Choices(_) => write!(output, "String")?,
+    FormatInner::Date(_) => write!(output, "java.time.LocalDateTime")?,
+    FormatInner::Index(sub) => {
+      write!(output, "Map<String, ")?;
+      gen_format(
+        output,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+      )?;
+      write!(output, ">")?;
+    }
+    FormatInner::List(sub) => {
+      write!(output, "List<")?;
+      gen_format(
+        output,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+      )?;
+      write!(output, ">")?;
+    }
+    FormatInner::Object(_) => write_class_name(output, trace)?,
+    FormatInner::Quantity(_) => write!(output, "Double")?,
+    FormatInner::Text(_) => write!(output, "String")?,
+  }
+  Ok(())
+}
+
+// `Any?` is already nullable
+pub fn gen_nullable_format(
+  output: &mut impl Write,
+  trace: &[&str],
+  format: &Format,
+) -> fmt::Result {
+  gen_format(output, trace, format)?;
+  if !matches!(format.format_inner, Some(FormatInner::Any(_))) {
+    write!(output, "?")?;
+  }
+  Ok(())
+}
+
+// data classes default their other keys
+pub fn gen_empty(
+  output: &mut impl Write,
+  trace: &[&str],
+  format: &Format,
+) -> fmt::Result {
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Any(_) => write!(output, "null")?,
+    FormatInner::Boolean(_) => write!(output, "false")?,
+    FormatInner::Choices(_) | FormatInner::Text(_) => write!(output, "\"\"")?,
+    FormatInner::Date(_) => write!(output, "java.time.LocalDateTime.MIN")?,
+    FormatInner::Index(_) => write!(output, "mapOf()")?,
+    FormatInner::List(_) => write!(output, "listOf()")?,
+    FormatInner::Object(object) => {
+      write_class_name(output, trace)?;
+      write!(output, "(")?;
+      let mut is_first = true;
+      for key_format in &object.keys {
+        let key = key_format.key.as_str();
+        if !object.required.get(key).is_some_and(is_undefined) {
+          continue;
+        }
+        write!(output, "{}{key} = ", if is_first { "" } else { ", " })?;
+        is_first = false;
+        gen_empty(
+          output,
+          &[trace, &[key]].concat(),
+          key_format.format.as_ref().unwrap(),
+        )?;
+      }
+      write!(output, ")")?;
+    }
+    FormatInner::Quantity(_) => write!(output, "0.0")?,
+  }
+  Ok(())
+}
@@ -0,0 +1,264 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, Expression, ExpressionInner, KeyFormat,
+  Model, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  objects_getter::ObjectGetterState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::EXTENSION;
+
+use self::{
+  classes::gen_classes,
+  expressions::gen_expressions,
+  formats::{gen_empty, gen_format, gen_nullable_format},
+  zones::gen_zone,
+};
+
+mod classes;
+mod expressions;
+mod formats;
+mod zones;
+
+pub trait Sub<'a>:
+  Stated<ObjectGetterState<'a>>
+  + Stated<UpdatedAndListenersState<'a>>
+  + Stated<PropertiesResolverState<'a>>
+  + Stated<FormatsState<'a>>
+{
+}
+
+impl<'a, S> Sub<'a> for S where
+  S: Stated<ObjectGetterState<'a>>
+    + Stated<UpdatedAndListenersState<'a>>
+    + Stated<PropertiesResolverState<'a>>
+    + Stated<FormatsState<'a>>
+{
+}
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let term = component.term.as_str();
+
+  let mut imports = BTreeSet::from([
+    "androidx.compose.foundation.layout.Row",
+    "androidx.compose.runtime.Composable",
+  ]);
+  for child in &component.zone.as_ref().unwrap().blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(_) => {
+        imports.insert("androidx.compose.material3.Text");
+      }
+      ComponentChildInner::Input(_) => {
+        imports.insert("androidx.compose.material3.TextField");
+      }
+      ComponentChildInner::Extern(_) => {}
+    }
+  }
+  if component.variables.is_some() {
+    imports.extend([
+      "androidx.compose.runtime.getValue",
+      "androidx.compose.runtime.mutableStateOf",
+      "androidx.compose.runtime.remember",
+      "androidx.compose.runtime.setValue",
+    ]);
+  }
+  for import in imports {
+    write!(output, "import {import}")?;
+    output.line()?;
+  }
+  output.line()?;
+
+  // properties, updated ones are hoisted to their owner
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+  let is_external = notifiers.iter().any(|notifier| notifier.is_external);
+  write!(output, "@Composable")?;
+  output.line()?;
+  if component.properties.is_none() && !is_external {
+    write!(output, "fun {term}() {{")?;
+  } else {
+    output.open(&alloc::format!("fun {term}("))?;
+    if let Some(properties) = &component.properties {
+      gen_keys(
+        output,
+        id,
+        state,
+        &[],
+        &properties.required,
+        &properties.keys,
+        false,
+      )?;
+    }
+    for notifier in &notifiers {
+      if !notifier.is_external {
+        continue;
+      }
+      write!(output, "on")?;
+      write_getter_name(output, &notifier.getter)?;
+      write!(output, "Change: (")?;
+      gen_format(
+        output,
+        &[],
+        formats.format_of(id, &notifier.getter).unwrap(),
+      )?;
+      write!(output, ") -> Unit")?;
+      output.item()?;
+    }
+    output.close(") {")?;
+  }
+  output.indent();
+  output.line()?;
+
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      let format = key_format.format.as_ref().unwrap();
+      write!(output, "var {key} by remember {{ mutableStateOf<")?;
+      match variables.required.get(key) {
+        Some(default) if is_undefined(default) => {
+          gen_format(output, &[key], format)?;
+          write!(output, ">(")?;
+          gen_empty(output, &[key], format)?;
+        }
+        Some(default) => {
+          gen_format(output, &[key], format)?;
+          write!(output, ">(")?;
+          gen_expressions(output, id, state, &[key], false, default)?;
+        }
+        None => {
+          gen_nullable_format(output, &[key], format)?;
+          write!(output, ">(null")?;
+        }
+      }
+      write!(output, ") }}")?;
+      output.line()?;
+    }
+  }
+  gen_zone(output, id, state, component.zone.as_ref().unwrap())?;
+  output.close("}")?;
+  output.line()?;
+
+  gen_classes(output, id, state)
+}
+
+// `val`s of data classes when `is_field`, parameters otherwise
+fn gen_keys<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  required: &BTreeMap<String, Expression>,
+  keys: &[KeyFormat],
+  is_field: bool,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for key_format in keys {
+    let key = key_format.key.as_str();
+    let trace = &[trace, &[key]].concat();
+    let format = key_format.format.as_ref().unwrap();
+    if is_field {
+      write!(output, "val ")?;
+    }
+    write!(output, "{key}: ")?;
+    match required.get(key) {
+      Some(default) if is_undefined(default) => {
+        gen_format(output, trace, format)?;
+      }
+      Some(default) => {
+        gen_format(output, trace, format)?;
+        write!(output, " = ")?;
+        gen_expressions(output, component, state, trace, false, default)?;
+      }
+      None => {
+        gen_nullable_format(output, trace, format)?;
+        write!(output, " = null")?;
+      }
+    }
+    output.item()?;
+  }
+  Ok(())
+}
+
+pub fn is_undefined(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner.as_ref().unwrap(),
+    ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Undefined(_)),
+    })
+  )
+}
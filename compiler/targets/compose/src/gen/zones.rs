@@ -0,0 +1,151 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  format,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, Getter,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{
+  expressions::{gen_expressions, gen_getter, gen_rich_text, gen_setter},
+  Sub,
+};
+
+pub fn gen_zone<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("Row {")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "Text(text = ")?;
+        gen_rich_text(
+          output,
+          component,
+          state,
+          &[],
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, ")")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        let format = formats
+          .format_of(component, getter)
+          .and_then(|format| format.format_inner.as_ref());
+        output.open("TextField(")?;
+        write!(output, "value = ")?;
+        gen_getter(output, component, state, getter)?;
+        if let Some(FormatInner::Text(_)) = format {
+          write!(output, " ?: \"\"")?;
+        } else {
+          write!(output, "?.toString() ?: \"\"")?;
+        }
+        output.item()?;
+        if let Some(FormatInner::Quantity(_)) = format {
+          output
+            .open("onValueChange = { it.toDoubleOrNull()?.let { new_ ->")?;
+          gen_update(output, component, state, getter)?;
+          output.close("} }")?;
+        } else {
+          output.open("onValueChange = { new_ ->")?;
+          gen_update(output, component, state, getter)?;
+          output.close("}")?;
+        }
+        output.item()?;
+        output.close(")")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        output.open(&format!("{}(", to_upper_camelcase(&r#extern.id)))?;
+        for (key, value) in &r#extern.properties.as_ref().unwrap().values {
+          write!(output, "{key} = ")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          output.item()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "on")?;
+          write_getter_name(output, updated_by)?;
+          output.open("Change = { new_ ->")?;
+          gen_update(output, component, state, &notifier.getter)?;
+          output.close("}")?;
+          output.item()?;
+        }
+        output.close(")")?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()
+}
+
+// variables are set in place, properties are given back to their owner
+fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+  {
+    gen_setter(output, component, state, getter, "new_")?;
+  } else {
+    write!(output, "on")?;
+    write_getter_name(output, getter)?;
+    write!(output, "Change(new_)")?;
+  }
+  output.line()
+}
@@ -0,0 +1,59 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target_compose::Compose;
+use dropin_target_tests::{generate, HOME, OPTIONAL, USER};
+
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&Compose, HOME);
+  assert!(code.contains("count.mod(3.0)"), "{code}");
+  assert!(code.contains("count.mod(Math.abs(step))"), "{code}");
+}
+
+#[test]
+fn nested_keys() {
+  let code = generate(&Compose, USER);
+  assert!(
+    code.contains("mutableStateOf<UserObject>(UserObject(id = 0.0))"),
+    "{code}"
+  );
+  assert!(
+    code.contains("${user.friends?.getOrNull(0)?.name}"),
+    "{code}"
+  );
+  assert!(
+    code
+      .contains("apply { if (0 in indices) set(0, r1[0].copy(name = new_)) }"),
+    "{code}"
+  );
+}
+
+#[test]
+fn optional_operands() {
+  let code = generate(&Compose, OPTIONAL);
+  assert!(code.contains("name in (user?.tags ?: listOf())"), "{code}");
+  assert!(
+    code.contains("(user?.scores?.get(name) ?: 0.0) > 1.0"),
+    "{code}"
+  );
+  assert!(code.contains("(user?.age ?: 0.0) <= 3.0"), "{code}");
+}
@@ -1,22 +1,28 @@
 use alloc::{
-  collections::BTreeMap,
+  collections::{BTreeMap, BTreeSet},
   fmt::{self, Write},
+  format,
   string::String,
 };
-
-use crate::{
+use dropin_compiler_recipes::ir::FormatInner;
+use dropin_target_analysis::{
   objects_getter::{write_class_name, ObjectGetterState},
   Stated,
 };
+use dropin_target_gen::{writer::keys_source, CodeWriter};
 
 use super::{
   expressions::gen_expressions,
+  formats::gen_format,
+  json::{
+    gen_from_json, gen_to_json, is_collection, is_primitive, needs_conversion,
+  },
   keys::{gen_keys, is_undefined},
   Sub,
 };
 
 pub fn gen_classes<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
 ) -> fmt::Result
@@ -27,36 +33,54 @@ where
     .get(component)
     .unwrap_or(&BTreeMap::new())
   {
+    output.line()?;
+    let source = ["variables", "properties"]
+      .map(|root| keys_source(root, trace))
+      .into_iter()
+      .find(|source| output.has_source(source))
+      .unwrap_or_default();
+    output.source(&source)?;
     write!(output, "class ")?;
     write_class_name(output, trace)?;
-    write!(output, "{{")?;
+    output.open(" {")?;
     gen_keys(
       output,
       component,
       state,
       trace,
+      &source,
       false,
       false,
       &format.required,
       &format.keys,
     )?;
+    if !format.keys.is_empty() {
+      output.line()?;
+    }
 
     // constructor
     write_class_name(output, trace)?;
-    write!(output, "(")?;
-    if !format.keys.is_empty() {
-      write!(output, "{{")?;
+    if format.keys.is_empty() {
+      write!(output, "(")?;
+    } else {
+      output.soft_open("({")?;
     }
     let mut is_first = true;
     for key_format in &format.keys {
       if !is_first {
-        write!(output, ",")?;
+        output.soft_item()?;
       }
       is_first = false;
+      let default = format.required.get(&key_format.key);
+      if let Some(default) = default {
+        if is_undefined(default) {
+          write!(output, "required ")?;
+        }
+      }
       write!(output, "this.{}", key_format.key)?;
-      if let Some(default) = format.required.get(&key_format.key) {
+      if let Some(default) = default {
         if !is_undefined(default) {
-          write!(output, "=")?;
+          write!(output, " = ")?;
           gen_expressions(
             output,
             component,
@@ -68,11 +92,262 @@ where
         }
       }
     }
-    if !format.keys.is_empty() {
-      write!(output, "}}")?;
+    if format.keys.is_empty() {
+      write!(output, ");")?;
+    } else {
+      output.soft_close("})")?;
+      write!(output, ";")?;
+    }
+    output.line()?;
+    output.line()?;
+
+    // json
+    write!(output, "factory ")?;
+    write_class_name(output, trace)?;
+    output.open(".fromJson(Map<String, dynamic> json) {")?;
+    write!(output, "return ")?;
+    write_class_name(output, trace)?;
+    if format.keys.is_empty() {
+      write!(output, "(")?;
+    } else {
+      output.open("(")?;
+    }
+    for key_format in &format.keys {
+      let key = key_format.key.as_str();
+      let key_trace = &[trace.as_slice(), &[key]].concat();
+      let key_format = key_format.format.as_ref().unwrap();
+      let value = format!("json['{key}']");
+      write!(output, "{key}: ")?;
+      match format.required.get(key) {
+        Some(default) if is_undefined(default) => {
+          gen_from_json(output, state, key_trace, key_format, &value, 0)?;
+        }
+        Some(default) => {
+          write!(output, "{value} == null")?;
+          output.soft_space()?;
+          write!(output, "? ")?;
+          gen_expressions(output, component, state, key_trace, false, default)?;
+          output.soft_space()?;
+          write!(output, ": ")?;
+          gen_from_json(output, state, key_trace, key_format, &value, 0)?;
+        }
+        None if is_primitive(key_format) => {
+          gen_from_json(output, state, key_trace, key_format, &value, 0)?;
+          if !matches!(key_format.format_inner, Some(FormatInner::Any(_))) {
+            write!(output, "?")?;
+          }
+        }
+        None => {
+          write!(output, "{value} == null")?;
+          output.soft_space()?;
+          write!(output, "? null")?;
+          output.soft_space()?;
+          write!(output, ": ")?;
+          gen_from_json(output, state, key_trace, key_format, &value, 0)?;
+        }
+      }
+      output.item()?;
+    }
+    if format.keys.is_empty() {
+      write!(output, ");")?;
+    } else {
+      output.close(");")?;
+    }
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.line()?;
+    output.open("Map<String, dynamic> toJson() {")?;
+    if format.keys.is_empty() {
+      write!(output, "return {{")?;
+    } else {
+      output.open("return {")?;
+    }
+    for key_format in &format.keys {
+      let key = key_format.key.as_str();
+      let key_format = key_format.format.as_ref().unwrap();
+      if format.required.contains_key(key) {
+        write!(output, "'{key}': ")?;
+        gen_to_json(output, key_format, key, 0)?;
+      } else {
+        write!(output, "if ({key} != null) '{key}':")?;
+        output.soft_space()?;
+        if needs_conversion(key_format) {
+          gen_to_json(output, key_format, &format!("{key}!"), 0)?;
+        } else {
+          write!(output, "{key}")?;
+        }
+      }
+      output.item()?;
+    }
+    if format.keys.is_empty() {
+      write!(output, "}};")?;
+    } else {
+      output.close("};")?;
+    }
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.line()?;
+
+    // copy
+    write_class_name(output, trace)?;
+    if format.keys.is_empty() {
+      write!(output, " copyWith(")?;
+    } else {
+      output.soft_open(" copyWith({")?;
+    }
+    let mut is_first = true;
+    for key_format in &format.keys {
+      if !is_first {
+        output.soft_item()?;
+      }
+      is_first = false;
+      let key = key_format.key.as_str();
+      let key_format = key_format.format.as_ref().unwrap();
+      gen_format(
+        output,
+        state,
+        &[trace.as_slice(), &[key]].concat(),
+        key_format,
+      )?;
+      if !matches!(key_format.format_inner, Some(FormatInner::Any(_))) {
+        write!(output, "?")?;
+      }
+      write!(output, " {key}")?;
     }
+    if format.keys.is_empty() {
+      output.open(") {")?;
+    } else {
+      output.soft_close("})")?;
+      output.open(" {")?;
+    }
+    write!(output, "return ")?;
+    write_class_name(output, trace)?;
+    if format.keys.is_empty() {
+      write!(output, "(")?;
+    } else {
+      output.open("(")?;
+    }
+    for key_format in &format.keys {
+      write!(output, "{0}: {0} ?? this.{0}", key_format.key)?;
+      output.item()?;
+    }
+    if format.keys.is_empty() {
+      write!(output, ");")?;
+    } else {
+      output.close(");")?;
+    }
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.line()?;
+
+    // equality
+    write!(output, "@override")?;
+    output.line_or_space()?;
+    write!(output, "bool operator ==(Object other) =>")?;
+    output.soft_space()?;
+    write!(output, "identical(this, other) ||")?;
+    output.soft_space()?;
+    write!(output, "other is ")?;
+    write_class_name(output, trace)?;
+    for key_format in &format.keys {
+      let key = key_format.key.as_str();
+      write!(output, " &&")?;
+      output.soft_space()?;
+      if is_collection(key_format.format.as_ref().unwrap()) {
+        write!(
+          output,
+          "const DeepCollectionEquality().equals({key}, other.{key})"
+        )?;
+      } else {
+        write!(output, "{key} == other.{key}")?;
+      }
+    }
+    write!(output, ";")?;
+    output.line()?;
+    output.line()?;
+    write!(output, "@override")?;
+    output.line_or_space()?;
+    output.soft_open("int get hashCode => Object.hashAll([")?;
+    let mut is_first = true;
+    for key_format in &format.keys {
+      if !is_first {
+        output.soft_item()?;
+      }
+      is_first = false;
+      let key = key_format.key.as_str();
+      if is_collection(key_format.format.as_ref().unwrap()) {
+        write!(output, "const DeepCollectionEquality().hash({key})")?;
+      } else {
+        write!(output, "{key}")?;
+      }
+    }
+    output.soft_close("])")?;
+    write!(output, ";")?;
+    output.line()?;
+    output.line()?;
+    write!(output, "@override")?;
+    output.line_or_space()?;
+    write!(output, "String toString() =>")?;
+    output.soft_space()?;
+    write!(output, "'")?;
+    write_class_name(output, trace)?;
+    write!(output, "(")?;
+    let mut is_first = true;
+    for key_format in &format.keys {
+      if !is_first {
+        write!(output, ", ")?;
+      }
+      is_first = false;
+      write!(output, "{0}: ${0}", key_format.key)?;
+    }
+    write!(output, ")';")?;
+    output.line()?;
+    output.line()?;
 
-    write!(output, ");}}")?;
+    // dynamic access
+    let mut value_formats = BTreeSet::new();
+    for key_format in &format.keys {
+      let mut value_format = String::new();
+      gen_format(
+        &mut value_format,
+        state,
+        &[trace.as_slice(), &[&key_format.key]].concat(),
+        key_format.format.as_ref().unwrap(),
+      )?;
+      if !format.required.contains_key(&key_format.key) {
+        write!(value_format, "?")?;
+      }
+      value_formats.insert(value_format);
+    }
+    let value_format = if value_formats.len() == 1 {
+      value_formats.pop_first().unwrap()
+    } else {
+      "dynamic".into()
+    };
+    output.soft_open(&format!("Map<String, {value_format}> toMap() => {{"))?;
+    let mut is_first = true;
+    for key_format in &format.keys {
+      if !is_first {
+        output.soft_item()?;
+      }
+      is_first = false;
+      write!(output, "'{0}': {0}", key_format.key)?;
+    }
+    output.soft_close("}")?;
+    write!(output, ";")?;
+    output.line()?;
+    output.line()?;
+    write!(output, "{value_format}")?;
+    if value_format != "dynamic" && !value_format.ends_with('?') {
+      write!(output, "?")?;
+    }
+    write!(output, " operator [](String key) => toMap()[key];")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
   }
   Ok(())
 }
@@ -1,27 +1,19 @@
-use alloc::{
-  fmt::{self, Write},
-  string::String,
-};
-use dropin_compiler_recipes::ir::{Expression, ExpressionInner};
+use alloc::fmt;
+use dropin_compiler_recipes::ir::{Binary, Control, Expression, Value};
+use dropin_target_gen::{CodeWriter, ExpressionGen};
 
 pub use self::{
-  arithmetic::gen_arithmetic,
-  comparison::gen_comparison,
   control::gen_control,
-  logic::gen_logic,
   value::{gen_getter, gen_rich_text, gen_value},
 };
 
 use super::Sub;
 
-mod arithmetic;
-mod comparison;
 mod control;
-mod logic;
 mod value;
 
 pub fn gen_expressions<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   trace: &[&str],
@@ -31,32 +23,56 @@ pub fn gen_expressions<'a, S>(
 where
   S: Sub<'a>,
 {
-  let start = output.len();
-  let is_parenthesized = match expression.expression_inner.as_ref().unwrap() {
-    ExpressionInner::Value(value) => {
-      gen_value(output, component, state, trace, value)?;
-      false
-    }
-    ExpressionInner::Comparison(comparison) => {
-      gen_comparison(output, component, state, comparison)?;
-      true
-    }
-    ExpressionInner::Logic(logic) => {
-      gen_logic(output, component, state, logic)?;
-      true
-    }
-    ExpressionInner::Control(control) => {
-      gen_control(output, component, state, control)?;
-      false
-    }
-    ExpressionInner::Arithmetic(arithmetic) => {
-      gen_arithmetic(output, component, state, arithmetic)?;
-      true
-    }
-  };
-  if is_nested && is_parenthesized {
-    output.insert(start, '(');
-    write!(output, ")")?;
+  Expressions { component, state }
+    .gen_expression(output, trace, is_nested, expression)
+}
+
+struct Expressions<'s, S> {
+  component: &'s str,
+  state: &'s S,
+}
+
+impl<'a, S> ExpressionGen for Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    gen_value(output, self.component, self.state, trace, value)
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result {
+    gen_control(output, self.component, self.state, control)
+  }
+
+  fn is_control_parenthesized(&self, _control: &Control) -> bool {
+    false
+  }
+
+  // dart's `%` is already euclidean
+  fn gen_mod(
+    &self,
+    output: &mut CodeWriter,
+    _trace: &[&str],
+    binary: &Binary,
+  ) -> fmt::Result {
+    self.gen_binary(output, binary, " % ")
+  }
+
+  // `dart:math` is imported by the components using it
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_call(output, "pow", binary)
+  }
+
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_contains(output, binary, ".contains(")
   }
-  Ok(())
 }
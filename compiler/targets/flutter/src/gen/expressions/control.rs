@@ -1,15 +1,13 @@
-use alloc::{
-  fmt::{self, Write},
-  string::String,
-};
+use alloc::fmt::{self, Write};
 use dropin_compiler_recipes::ir::{Control, ControlInner};
+use dropin_target_gen::CodeWriter;
 
 use crate::gen::Sub;
 
 use super::gen_expressions;
 
 pub fn gen_control<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   control: &Control,
@@ -19,7 +17,7 @@ where
 {
   match control.control_inner.as_ref().unwrap() {
     ControlInner::If(control) => {
-      write!(output, "if(")?;
+      write!(output, "if (")?;
       gen_expressions(
         output,
         component,
@@ -48,12 +46,12 @@ where
       let mut is_first = true;
       for arg in &control.args {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         write!(output, "{arg}")?;
       }
-      write!(output, "{{ return ")?;
+      write!(output, ") {{ return ")?;
       gen_expressions(
         output,
         component,
@@ -69,7 +67,7 @@ where
       let mut is_first = true;
       for arg in &control.args {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         write!(output, "{arg}")?;
@@ -78,12 +76,12 @@ where
       let mut is_first = true;
       for arg in &control.args {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         write!(output, "{arg}")?;
       }
-      write!(output, "{{ return ")?;
+      write!(output, ") {{ return ")?;
       gen_expressions(
         output,
         component,
@@ -96,7 +94,7 @@ where
       is_first = true;
       for arg in &control.args {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         write!(output, "{arg}")?;
@@ -116,7 +114,7 @@ where
       let mut is_first = true;
       for arg in &control.args {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         gen_expressions(output, component, state, &[], false, arg)?;
@@ -1,13 +1,12 @@
-use alloc::{
-  fmt::{self, Write},
-  string::String,
-};
+use alloc::fmt::{self, Write};
 use dropin_compiler_recipes::ir::{Value, ValueInner};
-
-use crate::{
-  gen::Sub, objects_getter::write_class_name,
-  objects_getter::ObjectGetterState, Stated,
+use dropin_target_analysis::{
+  objects_getter::{write_class_name, ObjectGetterState},
+  Stated,
 };
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
 
 use super::gen_expressions;
 
@@ -18,7 +17,7 @@ mod getter;
 mod text;
 
 pub fn gen_value<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   trace: &[&str],
@@ -46,7 +45,7 @@ where
       let trace_current = &[trace, &["*"]].concat();
       for value in &values.values {
         if !is_first {
-          write!(output, ",")?;
+          write!(output, ", ")?;
         }
         is_first = false;
         gen_expressions(output, component, state, trace_current, false, value)?;
@@ -64,7 +63,7 @@ where
         let mut is_first = true;
         for (key, value) in &value.values {
           if !is_first {
-            write!(output, ",")?;
+            write!(output, ", ")?;
           }
           is_first = false;
           write!(output, "{key}: ")?;
@@ -83,10 +82,10 @@ where
         let mut is_first = true;
         for (key, value) in &value.values {
           if !is_first {
-            write!(output, ",")?;
+            write!(output, ", ")?;
           }
           is_first = false;
-          write!(output, "{key}:")?;
+          write!(output, "{key}: ")?;
           gen_expressions(
             output,
             component,
@@ -1,22 +1,19 @@
 use alloc::{
   fmt::{self, Write},
-  string::String,
   vec::Vec,
 };
-use dropin_compiler_recipes::ir::{
-  ExpressionInner, Getter, RichText, RichTextInner, RichTextPart, Value,
-  ValueInner,
-};
-
-use crate::{
-  gen::{expressions::gen_expressions, keys::is_undefined, Sub},
-  objects_getter::ObjectGetterState,
+use dropin_compiler_recipes::ir::Getter;
+use dropin_target_analysis::{
+  objects_getter::{static_key, ObjectGetterState},
   properties_resolver::PropertiesResolverState,
   Stated,
 };
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::{expressions::gen_expressions, keys::is_undefined, Sub};
 
 pub fn gen_getter<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   value: &Getter,
@@ -35,37 +32,34 @@ where
     let mut trace_current = Vec::new();
     trace_current.push(value.ident.as_str());
     for key in &value.indexes {
-      let mut trace_key = "*";
-      if let ExpressionInner::Value(Value {
-        value_inner: Some(ValueInner::Text(RichText { parts })),
-      }) = key.expression_inner.as_ref().unwrap()
-      {
-        if parts.len() == 1 {
-          if let RichTextPart {
-            rich_text_inner: Some(RichTextInner::Static(part)),
-          } = &parts[0]
-          {
-            trace_key = part;
-          }
-        }
-      }
+      let trace_key = static_key(key).unwrap_or("*");
       if let Some(format) = objects
         .get(component)
         .and_then(|objects| objects.get(&trace_current))
       {
         if trace_key == "*" {
-          panic!("Objects cannot be indexed dynamically")
-        }
-        let mut is_required = false;
-        if let Some(default) = format.required.get(trace_key) {
-          if is_undefined(default) {
-            is_required = true;
+          write!(output, "?[")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &trace_current,
+            false,
+            key,
+          )?;
+          write!(output, "]")?;
+        } else {
+          let mut is_required = false;
+          if let Some(default) = format.required.get(trace_key) {
+            if is_undefined(default) {
+              is_required = true;
+            }
           }
+          if !is_required {
+            write!(output, "?")?;
+          }
+          write!(output, ".{trace_key}")?;
         }
-        if !is_required {
-          write!(output, "?")?;
-        }
-        write!(output, ".{trace_key}")?;
       } else {
         write!(output, "[")?;
         gen_expressions(output, component, state, &trace_current, false, key)?;
@@ -1,17 +1,15 @@
-use alloc::{
-  fmt::{self, Write},
-  string::String,
-};
+use alloc::fmt::{self, Write};
 use dropin_compiler_recipes::ir::{
   ExpressionInner, Getter, RichText, RichTextInner, Value, ValueInner,
 };
+use dropin_target_gen::CodeWriter;
 
 use crate::gen::Sub;
 
 use super::gen_expressions;
 
 pub fn gen_rich_text<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   trace: &[&str],
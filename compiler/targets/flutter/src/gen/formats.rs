@@ -1,15 +1,11 @@
-use alloc::{
-  fmt::{self, Write},
-  string::String,
-};
+use alloc::fmt::{self, Write};
 use dropin_compiler_recipes::ir::{Format, FormatInner};
-
-use crate::objects_getter::write_class_name;
+use dropin_target_analysis::objects_getter::write_class_name;
 
 use super::Sub;
 
 pub fn gen_format<'a, S>(
-  output: &mut String,
+  output: &mut impl Write,
   state: &S,
   trace: &[&str],
   format: &Format,
@@ -21,10 +17,10 @@ where
   match format {
     FormatInner::Any(_) => write!(output, "dynamic")?,
     FormatInner::Boolean(_) => write!(output, "bool")?,
-    FormatInner::Choices(_) => todo!(),
-    FormatInner::Date(_) => todo!(),
+    FormatInner::Choices(_) => write!(output, "String")?,
+    FormatInner::Date(_) => write!(output, "DateTime")?,
     FormatInner::Index(sub) => {
-      write!(output, "Map<String,")?;
+      write!(output, "Map<String, ")?;
       gen_format(
         output,
         state,
@@ -0,0 +1,138 @@
+use alloc::{
+  fmt::{self, Write},
+  format,
+};
+use dropin_compiler_recipes::ir::{Format, FormatInner};
+use dropin_target_analysis::objects_getter::write_class_name;
+use dropin_target_gen::CodeWriter;
+
+use super::{formats::gen_format, Sub};
+
+pub fn gen_from_json<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  trace: &[&str],
+  format: &Format,
+  value: &str,
+  depth: usize,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Any(_) => write!(output, "{value}")?,
+    FormatInner::Boolean(_)
+    | FormatInner::Choices(_)
+    | FormatInner::Quantity(_)
+    | FormatInner::Text(_) => {
+      write!(output, "{value} as ")?;
+      gen_format(output, state, trace, format)?;
+    }
+    FormatInner::Date(_) => {
+      write!(output, "DateTime.parse({value} as String)")?;
+    }
+    FormatInner::Index(sub) => {
+      output.soft_open(&format!("({value} as Map<String, dynamic>).map("))?;
+      write!(output, "(k{depth}, e{depth}) => MapEntry(k{depth}, ")?;
+      gen_from_json(
+        output,
+        state,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+        &format!("e{depth}"),
+        depth + 1,
+      )?;
+      write!(output, ")")?;
+      output.soft_close(")")?;
+    }
+    FormatInner::List(sub) => {
+      output.soft_open(&format!("({value} as List).map("))?;
+      write!(output, "(e{depth}) => ")?;
+      gen_from_json(
+        output,
+        state,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+        &format!("e{depth}"),
+        depth + 1,
+      )?;
+      output.soft_close(")")?;
+      write!(output, ".toList()")?;
+    }
+    FormatInner::Object(_) => {
+      write_class_name(output, trace)?;
+      write!(output, ".fromJson({value} as Map<String, dynamic>)")?;
+    }
+  }
+  Ok(())
+}
+
+pub fn gen_to_json(
+  output: &mut CodeWriter,
+  format: &Format,
+  value: &str,
+  depth: usize,
+) -> fmt::Result {
+  if !needs_conversion(format) {
+    write!(output, "{value}")?;
+    return Ok(());
+  }
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Date(_) => write!(output, "{value}.toIso8601String()")?,
+    FormatInner::Index(sub) => {
+      output.soft_open(&format!("{value}.map("))?;
+      write!(output, "(k{depth}, e{depth}) => MapEntry(k{depth}, ")?;
+      gen_to_json(
+        output,
+        sub.format.as_ref().unwrap(),
+        &format!("e{depth}"),
+        depth + 1,
+      )?;
+      write!(output, ")")?;
+      output.soft_close(")")?;
+    }
+    FormatInner::List(sub) => {
+      output.soft_open(&format!("{value}.map("))?;
+      write!(output, "(e{depth}) => ")?;
+      gen_to_json(
+        output,
+        sub.format.as_ref().unwrap(),
+        &format!("e{depth}"),
+        depth + 1,
+      )?;
+      output.soft_close(")")?;
+      write!(output, ".toList()")?;
+    }
+    FormatInner::Object(_) => write!(output, "{value}.toJson()")?,
+    _ => unreachable!(),
+  }
+  Ok(())
+}
+
+// formats whose dart value isn't already a json value
+pub fn needs_conversion(format: &Format) -> bool {
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Index(sub) => needs_conversion(sub.format.as_ref().unwrap()),
+    FormatInner::List(sub) => needs_conversion(sub.format.as_ref().unwrap()),
+    FormatInner::Date(_) | FormatInner::Object(_) => true,
+    _ => false,
+  }
+}
+
+pub fn is_collection(format: &Format) -> bool {
+  matches!(
+    format.format_inner.as_ref().unwrap(),
+    FormatInner::Index(_) | FormatInner::List(_)
+  )
+}
+
+pub fn is_primitive(format: &Format) -> bool {
+  matches!(
+    format.format_inner.as_ref().unwrap(),
+    FormatInner::Any(_)
+      | FormatInner::Boolean(_)
+      | FormatInner::Choices(_)
+      | FormatInner::Quantity(_)
+      | FormatInner::Text(_)
+  )
+}
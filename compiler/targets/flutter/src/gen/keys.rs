@@ -1,19 +1,22 @@
 use alloc::{
   collections::BTreeMap,
   fmt::{self, Write},
+  format,
   string::String,
 };
 use dropin_compiler_recipes::ir::{
   Expression, ExpressionInner, KeyFormat, Value, ValueInner,
 };
+use dropin_target_gen::CodeWriter;
 
 use super::{expressions::gen_expressions, formats::gen_format, Sub};
 
 pub fn gen_keys<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   trace: &[&str],
+  source: &str,
   write_default: bool,
   is_final: bool,
   required: &BTreeMap<String, Expression>,
@@ -23,6 +26,7 @@ where
   S: Sub<'a>,
 {
   for key_format in keys {
+    output.source(&format!("{source}.keys.{}", key_format.key))?;
     if is_final {
       write!(output, "final ")?;
     }
@@ -41,7 +45,7 @@ where
     if write_default {
       if let Some(default) = default {
         if !is_undefined(default) {
-          write!(output, "=")?;
+          write!(output, " = ")?;
           gen_expressions(
             output,
             component,
@@ -54,6 +58,7 @@ where
       }
     }
     write!(output, ";")?;
+    output.line()?;
   }
   Ok(())
 }
@@ -1,22 +1,24 @@
 use alloc::{
   collections::BTreeMap,
   fmt::{self, Write},
+  format,
   string::String,
   vec::Vec,
 };
 use dropin_compiler_recipes::ir::Model;
-use formats::gen_format;
-
-use crate::{
+use dropin_target_analysis::{
   formats::FormatsState,
-  imports::ImportsState,
   objects_getter::ObjectGetterState,
   properties_resolver::PropertiesResolverState,
   updated_listeners::{
     write_notifier_name, write_updater_name, UpdatedAndListenersState,
   },
-  Stated, EXTENSION,
+  Stated,
 };
+use dropin_target_gen::CodeWriter;
+use formats::gen_format;
+
+use crate::{imports::ImportsState, Options, EXTENSION};
 
 use self::{
   classes::gen_classes,
@@ -28,6 +30,7 @@ use self::{
 mod classes;
 mod expressions;
 mod formats;
+mod json;
 mod keys;
 mod zones;
 
@@ -55,14 +58,15 @@ where
   S: Sub<'a>,
 {
   sub: &'a S,
+  options: &'a Options,
 }
 
 impl<'a, S> Gen<'a, S>
 where
   S: Sub<'a>,
 {
-  pub fn new(sub: &'a S) -> Self {
-    Self { sub }
+  pub fn new(sub: &'a S, options: &'a Options) -> Self {
+    Self { sub, options }
   }
 
   pub fn gen(
@@ -71,7 +75,11 @@ where
   ) -> Result<BTreeMap<String, String>, fmt::Error> {
     let mut files = BTreeMap::new();
     for component in &ir.components {
-      let mut file = String::new();
+      let source = component
+        .source
+        .as_ref()
+        .filter(|_| self.options.source_map);
+      let mut file = CodeWriter::new(self.options.compact, source);
       let term = component.term.as_str();
       let id = component.id.as_str();
       {
@@ -81,30 +89,45 @@ where
           .unwrap_or(&Vec::new())
         {
           write!(file, "import '{import}';")?;
+          file.line()?;
         }
-        write!(file, "class {term}_State extends State<{term}> {{")?;
+        file.line()?;
+        file.source("")?;
+        file.open(&format!("class {term}_State extends State<{term}> {{"))?;
         if let Some(variables) = &component.variables {
           gen_keys(
             file,
             id,
             self.sub,
             &[],
+            "variables",
             true,
             false,
             &variables.required,
             &variables.keys,
           )?;
+          if !variables.keys.is_empty() {
+            file.line()?;
+          }
         }
 
-        write!(
-          file,
-          "{term}_State();\
-          @override Widget build(BuildContext context){{ \
-          return "
-        )?;
+        write!(file, "{term}_State();")?;
+        file.line()?;
+        file.line()?;
+        write!(file, "@override")?;
+        file.line_or_space()?;
+        file.open("Widget build(BuildContext context) {")?;
+        write!(file, "return ")?;
         gen_zone(file, id, self.sub, &[], component.zone.as_ref().unwrap())?;
-        write!(file, ";}}}}")?;
-        write!(file, "class {term} extends StatefulWidget {{")?;
+        write!(file, ";")?;
+        file.line()?;
+        file.close("}")?;
+        file.line()?;
+        file.close("}")?;
+        file.line()?;
+        file.line()?;
+        file.source("")?;
+        file.open(&format!("class {term} extends StatefulWidget {{"))?;
 
         let updated_listeners =
           <S as Stated<UpdatedAndListenersState>>::state(&self.sub);
@@ -116,40 +139,48 @@ where
           write!(file, "final ChangeNotifier ")?;
           write_notifier_name(file, &notifier.getter)?;
           if !notifier.is_external {
-            write!(file, "= ChangeNotifier()")?;
+            write!(file, " = ChangeNotifier()")?;
           } else {
-            write!(
-              file,
-              ";\
-              final void Function("
-            )?;
+            write!(file, ";")?;
+            file.line()?;
+            write!(file, "final void Function(")?;
             gen_format(file, self.sub, &[], &format)?;
             write!(file, ") ")?;
             write_updater_name(file, &notifier.getter)?;
           }
           write!(file, ";")?;
+          file.line()?;
         }
 
+        let mut has_fields = !notifiers.is_empty();
         if let Some(properties) = &component.properties {
           gen_keys(
             file,
             id,
             self.sub,
             &[],
+            "properties",
             false,
             true,
             &properties.required,
             &properties.keys,
           )?;
+          has_fields |= !properties.keys.is_empty();
+        }
+        if has_fields {
+          file.line()?;
         }
-        write!(
-          file,
-          "@override State<{term}> createState() => {term}_State();\
-          {term}({{super.key",
-        )?;
+        write!(file, "@override")?;
+        file.line_or_space()?;
+        write!(file, "State<{term}> createState() => {term}_State();")?;
+        file.line()?;
+        file.line()?;
+        write!(file, "{term}")?;
+        file.soft_open("({")?;
+        write!(file, "super.key")?;
         if let Some(properties) = &component.properties {
           for key_format in &properties.keys {
-            write!(file, ",")?;
+            file.soft_item()?;
             let default = properties.required.get(&key_format.key);
             if let Some(default) = default {
               if is_undefined(default) {
@@ -159,7 +190,7 @@ where
             write!(file, "this.{}", key_format.key)?;
             if let Some(default) = default {
               if !is_undefined(default) {
-                write!(file, "=")?;
+                write!(file, " = ")?;
                 gen_expressions(file, id, self.sub, &[], false, default)?;
               }
             }
@@ -167,20 +198,24 @@ where
         }
         for notifier in notifiers {
           if notifier.is_external {
-            write!(file, ",")?;
+            file.soft_item()?;
             write!(file, "required this.")?;
             write_notifier_name(file, &notifier.getter)?;
-            write!(file, ",")?;
+            file.soft_item()?;
             write!(file, "required this.")?;
             write_updater_name(file, &notifier.getter)?;
           }
         }
-        write!(file, "}});}}")?;
+        file.soft_close("})")?;
+        write!(file, ";")?;
+        file.line()?;
+        file.close("}")?;
+        file.line()?;
         gen_classes(file, id, self.sub)?;
       }
       let mut file_path = String::with_capacity(id.len() + EXTENSION.len());
       write!(&mut file_path, "{id}{EXTENSION}")?;
-      files.insert(file_path, file);
+      files.insert(file_path, file.into_code());
     }
     Ok(files)
   }
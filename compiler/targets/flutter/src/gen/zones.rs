@@ -1,21 +1,22 @@
 use alloc::{
   fmt::{self, Write},
-  string::String,
+  format,
   vec::Vec,
 };
 use dropin_compiler_common::to_upper_camelcase;
 use dropin_compiler_recipes::ir::{ComponentChildInner, ComponentZone};
-
-use crate::{
+use dropin_target_analysis::{
   formats::FormatsState,
-  gen::expressions::gen_rich_text,
   objects_getter::ObjectGetterState,
   properties_resolver::PropertiesResolverState,
   updated_listeners::{
-    write_notifier_name, write_updater_name, UpdatedAndListenersState,
+    write_notifier_name, write_updater_name, Listener, UpdatedAndListenersState,
   },
   Stated,
 };
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::expressions::gen_rich_text;
 
 use super::{
   expressions::{gen_expressions, gen_getter},
@@ -24,7 +25,7 @@ use super::{
 };
 
 pub fn gen_zone<'a, S>(
-  output: &mut String,
+  output: &mut CodeWriter,
   component: &str,
   state: &S,
   trace: &[usize],
@@ -33,26 +34,31 @@ pub fn gen_zone<'a, S>(
 where
   S: Sub<'a>,
 {
-  write!(output, "Row(children: [")?;
+  output.open("Row(")?;
+  output.open("children: [")?;
   let updated_listeners = <S as Stated<UpdatedAndListenersState>>::state(state);
   let notifiers = &updated_listeners.get_notifiers(component);
   for (i, child) in zone.blocks.iter().enumerate() {
-    if i != 0 {
-      write!(output, ",")?;
-    }
     let trace = &[trace, &[i]].concat();
+    output.source(&format!("blocks.{i}"))?;
     let updated_listeners = updated_listeners
       .get_listeners(component, trace)
       .map(|listeners| {
-        listeners
-          .iter()
-          .filter(|listener| {
-            notifiers
+        let mut updated_listeners = Vec::<&Listener>::new();
+        for listener in listeners {
+          let is_updated = notifiers
+            .iter()
+            .any(|updated| updated.getter.as_ref() == listener.getter);
+          // a getter read twice is listened to once
+          if is_updated
+            && !updated_listeners
               .iter()
-              .position(|updated| updated.getter.as_ref() == listener.getter)
-              .is_some()
-          })
-          .collect::<Vec<_>>()
+              .any(|listened| listened.getter == listener.getter)
+          {
+            updated_listeners.push(listener);
+          }
+        }
+        updated_listeners
       });
     let is_listenable = if let ComponentChildInner::Extern(_) =
       child.component_child_inner.as_ref().unwrap()
@@ -62,23 +68,25 @@ where
       let mut is_listenable = false;
       if let Some(updated_listeners) = updated_listeners {
         if !updated_listeners.is_empty() {
-          assert_eq!(
-            updated_listeners.len(),
-            1,
-            "TODO: add Listenable.merge()"
-          );
           is_listenable = true;
-          let listener = &updated_listeners[0];
-          write!(
-            output,
-            "ListenableBuilder(\
-            listenable:",
-          )?;
-          write_notifier_name(output, listener.getter)?;
-          write!(
-            output,
-            ", builder: (BuildContext context, Widget? child) => "
-          )?;
+          output.open("ListenableBuilder(")?;
+          write!(output, "listenable: ")?;
+          if let [listener] = updated_listeners.as_slice() {
+            write_notifier_name(output, listener.getter)?;
+          } else {
+            // rebuilds when any of the listened getters is updated
+            output.soft_open("Listenable.merge([")?;
+            for (i, listener) in updated_listeners.iter().enumerate() {
+              if i > 0 {
+                output.soft_item()?;
+              }
+              write_notifier_name(output, listener.getter)?;
+            }
+            output.soft_close("])")?;
+          }
+          output.item()?;
+          write!(output, "builder: (BuildContext context, Widget? child) =>")?;
+          output.soft_space()?;
         }
       }
       is_listenable
@@ -96,32 +104,32 @@ where
         write!(output, ")")?;
       }
       ComponentChildInner::Input(input) => {
-        write!(
-          output,
-          "SizedBox(width: 250, child: TextFormField(initialValue:"
-        )?;
+        output.open("SizedBox(")?;
+        write!(output, "width: 250")?;
+        output.item()?;
+        output.open("child: TextFormField(")?;
+        write!(output, "initialValue: ")?;
         gen_getter(
           output,
           component,
           state,
           input.on_change.as_ref().unwrap(),
         )?;
-        write!(output, ", onChanged: widget.")?;
+        output.item()?;
+        write!(output, "onChanged: widget.")?;
         write_updater_name(output, input.on_change.as_ref().unwrap())?;
-        write!(output, "))")?;
+        output.item()?;
+        output.close(")")?;
+        output.item()?;
+        output.close(")")?;
       }
       ComponentChildInner::Extern(r#extern) => {
-        write!(output, "{}(", to_upper_camelcase(&r#extern.id))?;
-        let mut is_first = true;
+        output.open(&format!("{}(", to_upper_camelcase(&r#extern.id)))?;
         let objects = <S as Stated<ObjectGetterState>>::state(state);
         let resolver = <S as Stated<PropertiesResolverState>>::state(state);
         let formats = <S as Stated<FormatsState>>::state(state);
         for (key, value) in &r#extern.properties.as_ref().unwrap().values {
-          if !is_first {
-            write!(output, ",")?;
-          }
-          is_first = false;
-          write!(output, "{key}:")?;
+          write!(output, "{key}: ")?;
           gen_expressions(
             output,
             component,
@@ -133,18 +141,18 @@ where
           if objects.contains_object(&r#extern.id, &[key]) {
             write!(output, " as dynamic")?;
           }
+          output.item()?;
         }
         for updated_getter in notifiers {
           if let Some(updated_by) =
             updated_getter.updated_by.get(r#extern.id.as_str())
           {
-            write!(output, ",")?;
             write_notifier_name(output, updated_by)?;
             write!(output, ": widget.")?;
             write_notifier_name(output, &updated_getter.getter)?;
-            write!(output, ",")?;
+            output.item()?;
             write_updater_name(output, updated_by)?;
-            write!(output, ":")?;
+            write!(output, ": ")?;
             if resolver
               .is_variable(component, updated_getter.getter.ident.as_str())
             {
@@ -153,28 +161,33 @@ where
                 .unwrap();
               write!(output, "(")?;
               gen_format(output, state, &[], &format)?;
-              write!(output, " new_) {{")?;
+              output.open(" new_) {")?;
               gen_getter(output, component, state, &updated_getter.getter)?;
-              write!(
-                output,
-                "= new_;\
-                widget."
-              )?;
+              write!(output, " = new_;")?;
+              output.line()?;
+              write!(output, "widget.")?;
               write_notifier_name(output, &updated_getter.getter)?;
-              write!(output, ".notifyListeners();}}")?;
+              write!(output, ".notifyListeners();")?;
+              output.line()?;
+              output.close("}")?;
             } else {
               write!(output, "widget.")?;
               write_updater_name(output, &updated_getter.getter)?;
             }
+            output.item()?;
           }
         }
-        write!(output, ")")?;
+        output.close(")")?;
       }
     }
     if is_listenable {
-      write!(output, ")")?;
+      output.item()?;
+      output.close(")")?;
     }
+    output.item()?;
   }
-  write!(output, "])")?;
+  output.close("]")?;
+  output.item()?;
+  output.close(")")?;
   Ok(())
 }
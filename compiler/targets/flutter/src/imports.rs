@@ -1,13 +1,15 @@
 use core::ops::Deref;
 
 use alloc::{collections::BTreeMap, fmt::Write, string::String, vec::Vec};
-use dropin_compiler_recipes::ir::{Component, ComponentExtern};
-
-use crate::{
-  visit::{ComponentChildTrace, Visit},
-  EXTENSION,
+use dropin_compiler_recipes::ir::{
+  Binary, Component, ComponentExtern, FormatInner, FormatObject,
+};
+use dropin_target_analysis::visit::{
+  ComponentChildTrace, ExpressionTrace, FormatTrace, Visit,
 };
 
+use crate::EXTENSION;
+
 #[derive(Debug)]
 pub struct ImportsState<'a>(BTreeMap<&'a str, Vec<String>>);
 
@@ -42,9 +44,39 @@ impl<'a> Visit<'a, ImportsState<'a>> for Imports<'a> {
     _trace: &ComponentChildTrace,
   ) {
     let imports = self.imports.get_mut(self.component.unwrap()).unwrap();
-    let mut import =
-      String::with_capacity(r#extern.id.len() + EXTENSION.len());
+    let mut import = String::with_capacity(r#extern.id.len() + EXTENSION.len());
     write!(&mut import, "{}{EXTENSION}", r#extern.id).unwrap();
     imports.push(import);
   }
+
+  fn visit_format_object(
+    &mut self,
+    format: &'a FormatObject,
+    _trace: &FormatTrace<'a>,
+  ) {
+    // generated classes compare their collections deeply
+    let has_collection = format.keys.iter().any(|key_format| {
+      matches!(
+        key_format.format.as_ref().unwrap().format_inner,
+        Some(FormatInner::Index(_) | FormatInner::List(_))
+      )
+    });
+    if !has_collection {
+      return;
+    }
+    self.push("package:collection/collection.dart");
+  }
+
+  fn visit_pow(&mut self, _pow: &'a Binary, _trace: &ExpressionTrace<'a, '_>) {
+    self.push("dart:math");
+  }
+}
+
+impl<'a> Imports<'a> {
+  fn push(&mut self, import: &str) {
+    let imports = self.imports.get_mut(self.component.unwrap()).unwrap();
+    if !imports.iter().any(|current| current == import) {
+      imports.push(import.into());
+    }
+  }
 }
@@ -5,40 +5,97 @@ extern crate alloc;
 #[global_allocator]
 static GLOBAL: GlobalDlmalloc = GlobalDlmalloc;
 
-use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use alloc::borrow::Cow;
 use dlmalloc::GlobalDlmalloc;
 use dropin_compiler_recipes::ir::Model;
+use dropin_target::{
+  Diagnostics, Generated, OptionSchema, Options as TargetOptions, Target,
+};
+use dropin_target_analysis::{
+  stage::Stage,
+  stage0::Stage0,
+  stage1::{Stage1, Stage1State},
+  Stated,
+};
 use gen::Gen;
-use prost::Message;
 
-use crate::{stage::Stage, stage0::Stage0, stage1::Stage1, visit::Visit};
+use crate::imports::{Imports, ImportsState};
 
 pub const EXTENSION: &str = ".dart";
 
-trait Stated<S> {
-  fn state(&self) -> &S;
-}
-
-mod dependencies;
-mod formats;
 mod gen;
 mod imports;
-mod objects_getter;
-mod properties_resolver;
-mod stage;
-mod stage0;
-mod stage1;
-mod updated_listeners;
-mod visit;
-
-#[no_mangle]
-pub fn codegen(protobuf: *mut [u8]) -> *mut BTreeMap<String, String> {
-  let protobuf = unsafe { Box::from_raw(protobuf) };
-  let model = Model::decode(protobuf.as_ref()).unwrap();
-  let stage0 = Stage::new(Stage0::default()).build(&model);
-  let stage1 = Stage::new(Stage1::new(&stage0)).build(&model);
-  let gen = Gen::new(&stage1);
-  Box::into_raw(Box::new(gen.gen(&model).unwrap()))
+
+#[derive(Debug, Default)]
+pub struct Options {
+  // keeps the dart code on a single line
+  pub compact: bool,
+  // comments each class, field and widget with its recipe location
+  pub source_map: bool,
+}
+
+pub struct Flutter;
+
+impl Target for Flutter {
+  fn name(&self) -> &str {
+    "flutter"
+  }
+
+  fn options(&self) -> &[OptionSchema] {
+    const OPTIONS: &[OptionSchema] = &[
+      OptionSchema {
+        name: Cow::Borrowed("compact"),
+        description: Cow::Borrowed("keeps the dart code on a single line"),
+      },
+      OptionSchema {
+        name: Cow::Borrowed("source-map"),
+        description: Cow::Borrowed(
+          "comments the generated code with recipe locations",
+        ),
+      },
+    ];
+    OPTIONS
+  }
+
+  fn generate(
+    &self,
+    model: &Model,
+    options: &TargetOptions,
+  ) -> Result<Generated, Diagnostics> {
+    let options = Options {
+      compact: options.is_set("compact"),
+      source_map: options.is_set("source-map"),
+    };
+    let stage0 = Stage::new(Stage0::default()).build(model);
+    let stage1 = Stage::new(Stage1::new(&stage0)).build(model);
+    let state = State {
+      stage1: &stage1,
+      imports: Stage::new(Imports::default()).build(model),
+    };
+    Diagnostics::from(stage1.diagnostics())
+      .generate(self.name(), || Gen::new(&state, &options).gen(model))
+  }
+}
+
+// the analysis, with the dart imports of each component
+struct State<'a, 'b> {
+  stage1: &'b Stage1State<'a, 'b>,
+  imports: ImportsState<'a>,
+}
+
+impl<'a, 'b, T> Stated<T> for State<'a, 'b>
+where
+  Stage1State<'a, 'b>: Stated<T>,
+{
+  fn state(&self) -> &T {
+    self.stage1.state()
+  }
+}
+
+impl<'a, 'b> Stated<ImportsState<'a>> for State<'a, 'b> {
+  fn state(&self) -> &ImportsState<'a> {
+    &self.imports
+  }
 }
 
 // #[cfg(debug_assertions)]
@@ -205,13 +205,14 @@ impl<'a> ListenersState<'a> {
           ArithmeticInner::Opposite(opposite) => {
             self.expression(name, trace, opposite)
           }
-          ArithmeticInner::Add(add) => {
-            self.expression(name, trace, add.left.as_ref().unwrap());
-            self.expression(name, trace, add.right.as_ref().unwrap());
-          }
-          ArithmeticInner::Sub(sub) => {
-            self.expression(name, trace, sub.left.as_ref().unwrap());
-            self.expression(name, trace, sub.right.as_ref().unwrap());
+          ArithmeticInner::Add(binary)
+          | ArithmeticInner::Sub(binary)
+          | ArithmeticInner::Mul(binary)
+          | ArithmeticInner::Div(binary)
+          | ArithmeticInner::Mod(binary)
+          | ArithmeticInner::Pow(binary) => {
+            self.expression(name, trace, binary.left.as_ref().unwrap());
+            self.expression(name, trace, binary.right.as_ref().unwrap());
           }
         }
       }
@@ -0,0 +1,128 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target::{Options, Target};
+use dropin_target_flutter::Flutter;
+
+const MODEL: &str = r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+
+const HOME: &str = r#"
+variables:
+  required:
+    user: ''
+  keys:
+    user:
+      type: object
+      required:
+        name: '"ada"'
+        tags: ''
+      keys:
+        name:
+          type: text
+        born:
+          type: date
+        role:
+          type: choices
+        tags:
+          type: list
+          format:
+            type: text
+blocks:
+  - type: text
+    content: "${user.name} ${user.born} ${user.role} ${user.tags}"
+"#;
+
+fn generate() -> String {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  let generated = Flutter.generate(&model, &Options::default()).unwrap();
+  generated
+    .files
+    .into_iter()
+    .map(|file| file.content)
+    .collect()
+}
+
+// dates travel as ISO 8601 strings, choices as their string
+#[test]
+fn json() {
+  let code = generate();
+  for expected in [
+    "name: json['name'] == null ? 'ada' : json['name'] as String,",
+    "born: json['born'] == null\n          ? null\n          \
+    : DateTime.parse(json['born'] as String),",
+    "role: json['role'] as String?,",
+    "tags: (json['tags'] as List).map((e0) => e0 as String).toList(),",
+    "'name': name,",
+    "if (born != null) 'born': born!.toIso8601String(),",
+    "if (role != null) 'role': role,",
+    "'tags': tags,",
+  ] {
+    assert!(code.contains(expected), "{expected}\n{code}");
+  }
+}
+
+#[test]
+fn copy_with() {
+  let code = generate();
+  for expected in [
+    "UserObject copyWith({\n    String? name,\n    DateTime? born,\n    \
+    String? role,\n    List<String>? tags,\n  })",
+    "name: name ?? this.name,",
+    "born: born ?? this.born,",
+    "role: role ?? this.role,",
+    "tags: tags ?? this.tags,",
+  ] {
+    assert!(code.contains(expected), "{expected}\n{code}");
+  }
+}
+
+// collections are compared by their content
+#[test]
+fn equality() {
+  let code = generate();
+  for expected in [
+    "other is UserObject &&",
+    "name == other.name &&",
+    "born == other.born &&",
+    "role == other.role &&",
+    "const DeepCollectionEquality().equals(tags, other.tags);",
+    "int get hashCode => Object.hashAll([\n    name,\n    born,\n    role,\n    \
+    const DeepCollectionEquality().hash(tags),\n  ]);",
+  ] {
+    assert!(code.contains(expected), "{expected}\n{code}");
+  }
+}
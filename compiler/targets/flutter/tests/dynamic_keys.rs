@@ -0,0 +1,155 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target::{Generated, Options, Target};
+use dropin_target_flutter::Flutter;
+
+const MODEL: &str = r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+
+const HOME: &str = r#"
+variables:
+  required:
+    key: '"a"'
+    user: ''
+    scores: ''
+  keys:
+    key:
+      type: text
+    user:
+      type: object
+      required:
+        name: '"ada"'
+      keys:
+        name:
+          type: text
+        age:
+          type: quantity
+    scores:
+      type: object
+      required:
+        a: '1'
+        b: '2'
+      keys:
+        a:
+          type: quantity
+        b:
+          type: quantity
+blocks:
+  - type: text
+    content: "${user[key]} ${scores[key]}"
+  - type: input
+    on_change: key
+"#;
+
+fn generate(home: &str) -> Generated {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), home.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  Flutter.generate(&model, &Options::default()).unwrap()
+}
+
+fn code(generated: &Generated) -> String {
+  generated
+    .files
+    .iter()
+    .map(|file| file.content.as_str())
+    .collect()
+}
+
+#[test]
+fn dynamic_index() {
+  let generated = generate(HOME);
+  let code = code(&generated);
+  assert!(
+    code.contains("Text('${user?[key]} ${scores?[key]}')"),
+    "{code}"
+  );
+  let warnings = generated.warnings.to_string();
+  for object in ["scores", "user"] {
+    let expected = format!(
+      "object `{object}` is indexed with a dynamic key, the value read has \
+      the format shared by its keys, `any` when they differ"
+    );
+    assert!(warnings.contains(&expected), "{warnings}");
+  }
+}
+
+// the values are typed as the format shared by all keys, `dynamic` otherwise
+#[test]
+fn keys_union() {
+  let code = code(&generate(HOME));
+  for expected in [
+    "Map<String, num> toMap() => {'a': a, 'b': b};",
+    "num? operator [](String key) => toMap()[key];",
+    "Map<String, dynamic> toMap() => {'name': name, 'age': age};",
+    "dynamic operator [](String key) => toMap()[key];",
+  ] {
+    assert!(code.contains(expected), "{expected}\n{code}");
+  }
+}
+
+// the text is rebuilt when the key is updated
+#[test]
+fn listened_key() {
+  let code = code(&generate(HOME));
+  assert!(
+    code.contains(
+      "ListenableBuilder(\n          listenable: notifierKey_,\n          \
+      builder: (BuildContext context, Widget? child) =>\n              \
+      Text('${user?[key]} ${scores?[key]}'),"
+    ),
+    "{code}",
+  );
+}
+
+#[test]
+fn merged_listeners() {
+  let home = HOME
+    .replace(
+      "${user[key]} ${scores[key]}",
+      "${key} ${user[key]} ${user.name}",
+    )
+    .replace(
+      "on_change: key",
+      "on_change: key\n  - type: input\n    on_change: user.name",
+    );
+  let code = code(&generate(&home));
+  assert!(
+    code.contains(
+      "listenable: Listenable.merge([notifierKey_, notifierUserName_])"
+    ),
+    "{code}",
+  );
+}
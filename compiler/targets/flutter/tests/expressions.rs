@@ -0,0 +1,47 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target::{Options, Target};
+use dropin_target_flutter::Flutter;
+use dropin_target_tests::{generate, model, HOME};
+
+// dart's `%` is already euclidean
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&Flutter, HOME);
+  assert!(code.contains(r#"${count % 3} ${count % step}"#), "{code}");
+}
+
+// the analysis errors come back before any code is written
+#[test]
+fn diagnosed_errors() {
+  let home = HOME.replace("${count % step}", "${missing}");
+  let diagnostics = Flutter
+    .generate(&model(&home), &Options::default())
+    .unwrap_err();
+  assert!(diagnostics.has_errors());
+  assert!(
+    diagnostics
+      .to_string()
+      .contains("`missing` is neither a property"),
+    "{diagnostics}",
+  );
+}
@@ -0,0 +1,337 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::BTreeSet,
+  fmt::{self, Write},
+  format,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, ComponentCommon, FormatInner, Getter,
+  RichTextInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_expressions, gen_getter, gen_initial, gen_rich_text, is_undefined, Sub,
+};
+
+use super::{
+  class_name,
+  getters::{collect_getters, collect_text_getters},
+  SCOPE,
+};
+
+pub fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+
+  output.open(&format!("function {}(root, properties) {{", component.term))?;
+  output.open("const component = new dropin.Component(")?;
+  write!(output, "root")?;
+  output.item()?;
+  write!(output, "{{ ")?;
+  if let Some(properties) = &component.properties {
+    for (key, default) in &properties.required {
+      if is_undefined(default) {
+        continue;
+      }
+      write!(output, "{key}: ")?;
+      gen_expressions(output, id, state, &SCOPE, &[key], false, default)?;
+      write!(output, ", ")?;
+    }
+  }
+  write!(output, "...properties }}")?;
+  output.item()?;
+  write!(output, "{{")?;
+  let mut is_first = true;
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      let Some(default) = variables.required.get(key) else {
+        continue;
+      };
+      if !is_first {
+        write!(output, ",")?;
+      }
+      is_first = false;
+      write!(output, " {key}: ")?;
+      gen_initial(
+        output,
+        id,
+        state,
+        &SCOPE,
+        &[key],
+        key_format.format.as_ref().unwrap(),
+        default,
+      )?;
+    }
+  }
+  if !is_first {
+    write!(output, " ")?;
+  }
+  write!(output, "}}")?;
+  output.item()?;
+  output.close(");")?;
+  output.line()?;
+  let zone = component.zone.as_ref().unwrap();
+  gen_classes(
+    output,
+    id,
+    state,
+    "component.zone",
+    &class_name(id),
+    zone.common.as_ref(),
+  )?;
+
+  for (i, child) in zone.blocks.iter().enumerate() {
+    output.line()?;
+    let block = format!("block{i}");
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        let content = text.content.as_ref().unwrap();
+        write!(output, "const {block} = component.element(\"span\");")?;
+        output.line()?;
+        gen_classes(output, id, state, &block, "", text.common.as_ref())?;
+        let mut getters = BTreeSet::new();
+        collect_text_getters(content, &mut getters);
+        write!(output, "component.listen(")?;
+        write_getters(output, &getters)?;
+        output.open(", () => {")?;
+        write!(output, "{block}.textContent = ")?;
+        gen_rich_text(output, id, state, &SCOPE, &[], content)?;
+        write!(output, ";")?;
+        output.line()?;
+        output.close("});")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        write!(output, "const {block} = component.element(\"input\");")?;
+        output.line()?;
+        gen_classes(output, id, state, &block, "", input.common.as_ref())?;
+        output
+          .open(&format!("{block}.addEventListener(\"input\", () => {{"))?;
+        let is_quantity = matches!(
+          formats
+            .format_of(id, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        let value = if is_quantity {
+          format!("Number({block}.value)")
+        } else {
+          format!("{block}.value")
+        };
+        gen_update(output, id, state, getter, &value)?;
+        output.close("});")?;
+        output.line()?;
+        write!(output, "component.listen([\"{}\"]", getter.ident)?;
+        output.open(", () => {")?;
+        write!(output, "const value = String(")?;
+        gen_getter(output, id, state, &SCOPE, false, getter)?;
+        write!(output, " ?? \"\");")?;
+        output.line()?;
+        output.open(&format!("if ({block}.value !== value) {{"))?;
+        write!(output, "{block}.value = value;")?;
+        output.line()?;
+        output.close("}")?;
+        output.line()?;
+        output.close("});")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let properties = &r#extern.properties.as_ref().unwrap().values;
+        let term = to_upper_camelcase(&r#extern.id);
+        write!(output, "const {block} = {term}(component.zone, ")?;
+        output.open("{")?;
+        for (key, value) in properties {
+          write!(output, "{key}: ")?;
+          gen_expressions(output, id, state, &SCOPE, &[key], false, value)?;
+          output.item()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "update")?;
+          write_getter_name(output, updated_by)?;
+          output.open(": (value) => {")?;
+          gen_update(output, id, state, &notifier.getter, "value")?;
+          output.close("}")?;
+          output.item()?;
+        }
+        output.close("});")?;
+        output.line()?;
+        gen_classes(
+          output,
+          id,
+          state,
+          &format!("{block}.zone"),
+          &class_name(&r#extern.id),
+          r#extern.common.as_ref(),
+        )?;
+        // the owner gives its properties back on each update
+        for (key, value) in properties {
+          let mut getters = BTreeSet::new();
+          collect_getters(value, &mut getters);
+          if getters.is_empty() {
+            continue;
+          }
+          write!(output, "component.listen(")?;
+          write_getters(output, &getters)?;
+          write!(output, ", () => {block}.set(\"{key}\", ")?;
+          gen_expressions(output, id, state, &SCOPE, &[key], false, value)?;
+          write!(output, "));")?;
+          output.line()?;
+        }
+        continue;
+      }
+    }
+    output.line()?;
+  }
+  write!(output, "return component;")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()
+}
+
+// static classes are set once, dynamic ones on each update of their getters
+fn gen_classes<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  element: &str,
+  base: &str,
+  common: Option<&ComponentCommon>,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let classes = common
+    .map(|common| common.classes.as_slice())
+    .unwrap_or(&[]);
+  if base.is_empty() && classes.is_empty() {
+    return Ok(());
+  }
+  let mut getters = BTreeSet::new();
+  for class in classes {
+    collect_text_getters(class, &mut getters);
+  }
+  if getters.is_empty() {
+    write!(output, "{element}.className = \"{base}")?;
+    let mut is_first = base.is_empty();
+    for class in classes {
+      if !is_first {
+        write!(output, " ")?;
+      }
+      is_first = false;
+      for part in &class.parts {
+        if let Some(RichTextInner::Static(part)) = &part.rich_text_inner {
+          for c in part.chars() {
+            match c {
+              '\\' | '"' => write!(output, "\\{c}")?,
+              _ => output.write_char(c)?,
+            }
+          }
+        }
+      }
+    }
+    write!(output, "\";")?;
+    return output.line();
+  }
+  write!(output, "component.listen(")?;
+  write_getters(output, &getters)?;
+  output.open(", () => {")?;
+  write!(output, "{element}.className = [")?;
+  let mut is_first = true;
+  if !base.is_empty() {
+    write!(output, "\"{base}\"")?;
+    is_first = false;
+  }
+  for class in classes {
+    if !is_first {
+      write!(output, ", ")?;
+    }
+    is_first = false;
+    gen_rich_text(output, component, state, &SCOPE, &[], class)?;
+  }
+  write!(output, "].join(\" \");")?;
+  output.line()?;
+  output.close("});")?;
+  output.line()
+}
+
+fn write_getters(
+  output: &mut CodeWriter,
+  getters: &BTreeSet<&str>,
+) -> fmt::Result {
+  write!(output, "[")?;
+  let mut is_first = true;
+  for getter in getters {
+    if !is_first {
+      write!(output, ", ")?;
+    }
+    is_first = false;
+    write!(output, "\"{getter}\"")?;
+  }
+  write!(output, "]")
+}
+
+// variables are set in place, properties are given back to their owner
+fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+  {
+    gen_getter(output, component, state, &SCOPE, true, getter)?;
+    write!(output, " = {value};")?;
+    output.line()?;
+    write!(output, "component.notify(\"{}\");", getter.ident)?;
+  } else {
+    write!(output, "{}update", SCOPE.properties)?;
+    write_getter_name(output, getter)?;
+    write!(output, "?.({value});")?;
+  }
+  output.line()
+}
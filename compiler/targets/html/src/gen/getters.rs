@@ -0,0 +1,131 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::collections::BTreeSet;
+use dropin_compiler_recipes::ir::{
+  ArithmeticInner, ComparisonInner, ControlInner, Expression, ExpressionInner,
+  LogicInner, RichText, RichTextInner, ValueInner,
+};
+
+// idents whose updates change the value of `expression`
+pub fn collect_getters<'a>(
+  expression: &'a Expression,
+  getters: &mut BTreeSet<&'a str>,
+) {
+  match expression.expression_inner.as_ref().unwrap() {
+    ExpressionInner::Value(value) => {
+      match value.value_inner.as_ref().unwrap() {
+        ValueInner::Text(text) => collect_text_getters(text, getters),
+        ValueInner::Getter(getter) => {
+          getters.insert(&getter.ident);
+          for index in &getter.indexes {
+            collect_getters(index, getters);
+          }
+        }
+        ValueInner::List(list) => {
+          for value in &list.values {
+            collect_getters(value, getters);
+          }
+        }
+        ValueInner::Object(object) => {
+          for value in object.values.values() {
+            collect_getters(value, getters);
+          }
+        }
+        ValueInner::Quantity(_)
+        | ValueInner::Boolean(_)
+        | ValueInner::Undefined(_) => {}
+      }
+    }
+    ExpressionInner::Comparison(comparison) => {
+      let (ComparisonInner::EqualsTo(binary)
+      | ComparisonInner::DifferentFrom(binary)
+      | ComparisonInner::In(binary)
+      | ComparisonInner::LessThan(binary)
+      | ComparisonInner::MoreThan(binary)
+      | ComparisonInner::AtLeast(binary)
+      | ComparisonInner::AtMost(binary)) =
+        comparison.comparison_inner.as_ref().unwrap();
+      collect_getters(binary.left.as_ref().unwrap(), getters);
+      collect_getters(binary.right.as_ref().unwrap(), getters);
+    }
+    ExpressionInner::Logic(logic) => {
+      match logic.logic_inner.as_ref().unwrap() {
+        LogicInner::And(operands) | LogicInner::Or(operands) => {
+          for operand in &operands.operands {
+            collect_getters(operand, getters);
+          }
+        }
+        LogicInner::Not(operand) | LogicInner::Exists(operand) => {
+          collect_getters(operand, getters)
+        }
+      }
+    }
+    ExpressionInner::Control(control) => {
+      match control.control_inner.as_ref().unwrap() {
+        ControlInner::If(control) => {
+          collect_getters(control.condition.as_ref().unwrap(), getters);
+          collect_getters(control.then.as_ref().unwrap(), getters);
+          if let Some(r#else) = &control.r#else {
+            collect_getters(r#else, getters);
+          }
+        }
+        ControlInner::AnonymousFunction(control) => {
+          collect_getters(control.body.as_ref().unwrap(), getters)
+        }
+        ControlInner::NamedFunction(control) => {
+          collect_getters(control.body.as_ref().unwrap(), getters)
+        }
+        ControlInner::FunctionCall(control) => {
+          collect_getters(control.function.as_ref().unwrap(), getters);
+          for arg in &control.args {
+            collect_getters(arg, getters);
+          }
+        }
+      }
+    }
+    ExpressionInner::Arithmetic(arithmetic) => {
+      match arithmetic.arithmetic_inner.as_ref().unwrap() {
+        ArithmeticInner::Opposite(operand) => collect_getters(operand, getters),
+        ArithmeticInner::Add(binary)
+        | ArithmeticInner::Sub(binary)
+        | ArithmeticInner::Mul(binary)
+        | ArithmeticInner::Div(binary)
+        | ArithmeticInner::Mod(binary)
+        | ArithmeticInner::Pow(binary) => {
+          collect_getters(binary.left.as_ref().unwrap(), getters);
+          collect_getters(binary.right.as_ref().unwrap(), getters);
+        }
+      }
+    }
+  }
+}
+
+pub fn collect_text_getters<'a>(
+  text: &'a RichText,
+  getters: &mut BTreeSet<&'a str>,
+) {
+  for part in &text.parts {
+    if let Some(RichTextInner::Dynamic(expression)) = &part.rich_text_inner {
+      collect_getters(expression, getters);
+    }
+  }
+}
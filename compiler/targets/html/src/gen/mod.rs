@@ -0,0 +1,128 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::BTreeMap,
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_recipes::ir::Model;
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{Scope, Sub};
+
+use crate::EXTENSION;
+
+use self::{
+  components::gen_component, pages::gen_page, runtime::RUNTIME,
+  styles::gen_styles,
+};
+
+mod components;
+mod getters;
+mod pages;
+mod runtime;
+mod styles;
+
+const SCOPE: Scope = Scope {
+  variables: "component.variables.",
+  properties: "component.properties.",
+  is_typed: false,
+};
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    files.insert("runtime.js".into(), RUNTIME.into());
+
+    let mut scripts = CodeWriter::new(false, None);
+    for (i, component) in ir.components.iter().enumerate() {
+      if i != 0 {
+        scripts.line()?;
+      }
+      gen_component(&mut scripts, self.sub, component)?;
+    }
+    files.insert("components.js".into(), scripts.into_code());
+    let mut styles = CodeWriter::new(false, None);
+    gen_styles(&mut styles, &ir.components)?;
+    files.insert("styles.css".into(), styles.into_code());
+
+    for (key, page) in &ir.pages {
+      let mut file = CodeWriter::new(false, None);
+      gen_page(&mut file, page, &ir.components[page.component as usize])?;
+      let mut name = String::with_capacity(key.len());
+      sanitize(&mut name, key);
+      if name.is_empty() {
+        name.push_str("page");
+      }
+      // keys differing only by their sanitized characters get a suffix
+      let mut file_path = String::new();
+      for i in 1.. {
+        file_path.clear();
+        write!(&mut file_path, "{name}")?;
+        if i > 1 {
+          write!(&mut file_path, "-{i}")?;
+        }
+        write!(&mut file_path, "{EXTENSION}")?;
+        if !files.contains_key(&file_path) {
+          break;
+        }
+      }
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+// css class of the zone of a component
+fn class_name(id: &str) -> String {
+  let mut name = String::from("dropin-");
+  sanitize(&mut name, id);
+  name
+}
+
+// only the characters safe in file names, urls and css classes are kept
+fn sanitize(output: &mut String, name: &str) {
+  for c in name.chars() {
+    output.push(if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+      c
+    } else {
+      '-'
+    });
+  }
+}
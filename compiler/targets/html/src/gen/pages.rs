@@ -0,0 +1,70 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{Component, Page};
+use dropin_target_gen::CodeWriter;
+
+pub fn gen_page(
+  output: &mut CodeWriter,
+  page: &Page,
+  component: &Component,
+) -> fmt::Result {
+  write!(output, "<!DOCTYPE html>")?;
+  output.line()?;
+  output.open("<html lang=\"en\">")?;
+  output.open("<head>")?;
+  write!(output, "<meta charset=\"utf-8\">")?;
+  output.line()?;
+  write!(
+    output,
+    "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+  )?;
+  output.line()?;
+  write!(output, "<title>")?;
+  for c in page.title.chars() {
+    match c {
+      '&' => write!(output, "&amp;")?,
+      '<' => write!(output, "&lt;")?,
+      '>' => write!(output, "&gt;")?,
+      _ => output.write_char(c)?,
+    }
+  }
+  write!(output, "</title>")?;
+  output.line()?;
+  write!(output, "<link rel=\"stylesheet\" href=\"styles.css\">")?;
+  output.line()?;
+  write!(output, "<script src=\"runtime.js\"></script>")?;
+  output.line()?;
+  write!(output, "<script src=\"components.js\"></script>")?;
+  output.line()?;
+  output.close("</head>")?;
+  output.line()?;
+  output.open("<body>")?;
+  output.open("<script>")?;
+  write!(output, "{}(document.body, {{}});", component.term)?;
+  output.line()?;
+  output.close("</script>")?;
+  output.line()?;
+  output.close("</body>")?;
+  output.line()?;
+  output.close("</html>")
+}
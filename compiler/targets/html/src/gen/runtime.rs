@@ -0,0 +1,66 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// shared by every page, instances notify the updates listening to a getter
+pub const RUNTIME: &str = r#"const dropin = (() => {
+  class Component {
+    constructor(root, properties, variables) {
+      this.properties = properties;
+      this.variables = variables;
+      this.listeners = new Map();
+      this.zone = document.createElement("div");
+      root.append(this.zone);
+    }
+
+    element(tag) {
+      const element = document.createElement(tag);
+      this.zone.append(element);
+      return element;
+    }
+
+    listen(getters, update) {
+      for (const getter of getters) {
+        if (!this.listeners.has(getter)) {
+          this.listeners.set(getter, []);
+        }
+        this.listeners.get(getter).push(update);
+      }
+      update();
+    }
+
+    notify(getter) {
+      for (const update of this.listeners.get(getter) ?? []) {
+        update();
+      }
+    }
+
+    set(property, value) {
+      if (this.properties[property] === value) {
+        return;
+      }
+      this.properties[property] = value;
+      this.notify(property);
+    }
+  }
+
+  return { Component };
+})();
+"#;
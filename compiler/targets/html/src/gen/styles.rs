@@ -0,0 +1,124 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::BTreeSet,
+  fmt::{self, Write},
+  format,
+  string::String,
+  vec,
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, ComponentCommon, RichTextInner,
+};
+use dropin_target_gen::CodeWriter;
+
+// the classes the stylesheet knows, other ones are left to the page styles
+pub fn gen_styles(
+  output: &mut CodeWriter,
+  components: &[Component],
+) -> fmt::Result {
+  let mut classes = BTreeSet::new();
+  for component in components {
+    let zone = component.zone.as_ref().unwrap();
+    collect_classes(zone.common.as_ref(), &mut classes);
+    for child in &zone.blocks {
+      let common = match child.component_child_inner.as_ref().unwrap() {
+        ComponentChildInner::Text(text) => text.common.as_ref(),
+        ComponentChildInner::Input(input) => input.common.as_ref(),
+        ComponentChildInner::Extern(r#extern) => r#extern.common.as_ref(),
+      };
+      collect_classes(common, &mut classes);
+    }
+  }
+  let mut is_first = true;
+  for class in classes {
+    let declarations = declarations_of(class);
+    if declarations.is_empty() {
+      continue;
+    }
+    if !is_first {
+      output.line()?;
+    }
+    is_first = false;
+    output.open(&format!(".{class} {{"))?;
+    for declaration in declarations {
+      write!(output, "{declaration};")?;
+      output.line()?;
+    }
+    output.close("}")?;
+    output.line()?;
+  }
+  Ok(())
+}
+
+// the static parts only, dynamic classes are known at runtime
+fn collect_classes<'a>(
+  common: Option<&'a ComponentCommon>,
+  classes: &mut BTreeSet<&'a str>,
+) {
+  let Some(common) = common else {
+    return;
+  };
+  for class in &common.classes {
+    for part in &class.parts {
+      if let Some(RichTextInner::Static(part)) = &part.rich_text_inner {
+        classes.extend(part.split_whitespace());
+      }
+    }
+  }
+}
+
+// spacings count in steps of 4px
+fn declarations_of(class: &str) -> Vec<String> {
+  let declarations: &[&str] = match class {
+    "row" => &[
+      "display: flex",
+      "flex-direction: row",
+      "align-items: center",
+    ],
+    "column" => &["display: flex", "flex-direction: column"],
+    "center" => &["align-items: center", "justify-content: center"],
+    "wrap" => &["flex-wrap: wrap"],
+    "grow" => &["flex-grow: 1"],
+    "hidden" => &["display: none"],
+    "bold" => &["font-weight: bold"],
+    "italic" => &["font-style: italic"],
+    _ => &[],
+  };
+  if !declarations.is_empty() {
+    return declarations.iter().map(|&d| String::from(d)).collect();
+  }
+  let Some((prefix, steps)) = class.split_once('-') else {
+    return vec![];
+  };
+  let property = match prefix {
+    "gap" => "gap",
+    "p" => "padding",
+    "m" => "margin",
+    _ => return vec![],
+  };
+  let Ok(steps) = steps.parse::<u32>() else {
+    return vec![];
+  };
+  vec![format!("{property}: {}px", steps * 4)]
+}
@@ -0,0 +1,53 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![no_std]
+
+extern crate alloc;
+
+use dropin_compiler_recipes::ir::Model;
+use dropin_target::{Diagnostics, Generated, Options, Target};
+use dropin_target_analysis::{stage::Stage, stage0::Stage0, stage1::Stage1};
+
+use crate::gen::Gen;
+
+pub const EXTENSION: &str = ".html";
+
+pub mod gen;
+
+pub struct Html;
+
+impl Target for Html {
+  fn name(&self) -> &str {
+    "html"
+  }
+
+  fn generate(
+    &self,
+    model: &Model,
+    _options: &Options,
+  ) -> Result<Generated, Diagnostics> {
+    let stage0 = Stage::new(Stage0::default()).build(model);
+    let stage1 = Stage::new(Stage1::new(&stage0)).build(model);
+    Diagnostics::from(stage1.diagnostics())
+      .generate(self.name(), || Gen::new(&stage1).gen(model))
+  }
+}
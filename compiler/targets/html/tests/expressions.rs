@@ -0,0 +1,141 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target_html::Html;
+use dropin_target_tests::{
+  generate, generate_model, model, model_of, HOME, USER,
+};
+
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&Html, HOME);
+  assert!(
+    code.contains("((component.variables.count % 3) + 3) % 3"),
+    "{code}"
+  );
+  assert!(
+    code.contains(
+      "((a, b) => ((a % b) + Math.abs(b)) % Math.abs(b))\
+      (component.variables.count, component.variables.step)"
+    ),
+    "{code}"
+  );
+}
+
+#[test]
+fn nested_keys() {
+  let code = generate(&Html, USER);
+  assert!(
+    code.contains("{ user: { id: 0, first: `anon` } }"),
+    "{code}"
+  );
+  assert!(
+    code.contains("${component.variables.user.friends?.[0]?.name}"),
+    "{code}"
+  );
+  assert!(
+    code.contains(
+      "((component.variables.user.friends ??= [])[0] ??= {}).name = \
+       block1.value"
+    ),
+    "{code}"
+  );
+}
+
+// only the known classes used by the recipes get a rule
+#[test]
+fn styles() {
+  let files = generate_model(
+    &Html,
+    &model(
+      r#"
+variables:
+  required:
+    size: '2'
+  keys:
+    size:
+      type: quantity
+classes:
+  - row gap-2
+blocks:
+  - type: text
+    classes:
+      - bold
+      - custom
+      - p-${size}
+    content: Hello
+"#,
+    ),
+  );
+  assert_eq!(
+    files["styles.css"],
+    ".bold {\n  font-weight: bold;\n}\n\n\
+     .gap-2 {\n  gap: 8px;\n}\n\n\
+     .row {\n  display: flex;\n  flex-direction: row;\n  \
+     align-items: center;\n}\n"
+  );
+  let components = &files["components.js"];
+  assert!(
+    components
+      .contains("component.zone.className = \"dropin-home row gap-2\";"),
+    "{components}"
+  );
+}
+
+// keys are sanitized, the ones colliding get a suffix
+#[test]
+fn page_files() {
+  let files = generate_model(
+    &Html,
+    &model_of(
+      r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    ../home:
+      component: home
+      title: Home
+      path: /
+    a/b:
+      component: other
+      title: Other
+      path: /other
+    a b:
+      component: a
+      title: A <b>
+      path: /a
+"#,
+      &[
+        ("home", "blocks: []"),
+        ("other", "blocks: []"),
+        ("a", "blocks: []"),
+      ],
+    ),
+  );
+  let pages = files
+    .keys()
+    .filter(|path| path.ends_with(".html"))
+    .map(String::as_str)
+    .collect::<Vec<_>>();
+  assert_eq!(pages, ["---home.html", "a-b-2.html", "a-b.html"]);
+  assert!(files["a-b.html"].contains("<title>A &lt;b&gt;</title>"));
+}
@@ -0,0 +1,162 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{
+  Binary, Control, ControlInner, Expression, ExpressionInner, FormatInner,
+  Value, ValueInner,
+};
+use dropin_target_analysis::{formats::FormatsState, Stated};
+use dropin_target_gen::{CodeWriter, ExpressionGen};
+
+pub use self::{
+  control::gen_control,
+  value::{
+    gen_getter, gen_list_index, gen_rich_text, gen_value, is_root_optional,
+  },
+};
+
+use super::Sub;
+
+mod control;
+mod value;
+
+pub fn gen_expressions<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  is_nested: bool,
+  expression: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  Expressions { component, state }
+    .gen_expression(output, trace, is_nested, expression)
+}
+
+struct Expressions<'s, S> {
+  component: &'s str,
+  state: &'s S,
+}
+
+impl<'a, S> ExpressionGen for Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    gen_value(output, self.component, self.state, trace, value)
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result {
+    gen_control(output, self.component, self.state, control)
+  }
+
+  fn is_control_parenthesized(&self, control: &Control) -> bool {
+    matches!(
+      control.control_inner,
+      Some(ControlInner::If(_) | ControlInner::AnonymousFunction(_))
+    )
+  }
+
+  fn gen_exists(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    self.gen_expression(output, &[], true, operand)?;
+    write!(output, ".is_some()")
+  }
+
+  fn gen_mod(
+    &self,
+    output: &mut CodeWriter,
+    _trace: &[&str],
+    binary: &Binary,
+  ) -> fmt::Result {
+    self.gen_binary(output, binary, ".rem_euclid(")?;
+    write!(output, ")")
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_binary(output, binary, ".powf(")?;
+    write!(output, ")")
+  }
+
+  // the element is borrowed, so it is parenthesized as an operand
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_or_empty(output, binary.right.as_ref().unwrap())?;
+    write!(output, ".contains(&")?;
+    self.gen_expression(output, &[], true, binary.left.as_ref().unwrap())?;
+    write!(output, ")")
+  }
+
+  // the options of the formats implementing `Default`, structs don't
+  fn is_optional(&self, operand: &Expression) -> bool {
+    let ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Getter(getter)),
+    }) = operand.expression_inner.as_ref().unwrap()
+    else {
+      return false;
+    };
+    let mut read = CodeWriter::new(true, None);
+    let Ok(true) = gen_getter(&mut read, self.component, self.state, getter)
+    else {
+      return false;
+    };
+    let format = <S as Stated<FormatsState>>::state(self.state)
+      .format_of(self.component, getter)
+      .and_then(|format| format.format_inner.as_ref());
+    matches!(
+      format,
+      Some(
+        FormatInner::Boolean(_)
+          | FormatInner::Choices(_)
+          | FormatInner::Index(_)
+          | FormatInner::List(_)
+          | FormatInner::Quantity(_)
+          | FormatInner::Text(_)
+      )
+    )
+  }
+
+  fn gen_or_empty(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    self.gen_expression(output, &[], true, operand)?;
+    if self.is_optional(operand) {
+      write!(output, ".unwrap_or_default()")?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,132 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{Value, ValueInner};
+use dropin_target_analysis::{
+  objects_getter::{write_class_name, ObjectGetterState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::gen_expressions;
+
+pub use self::getter::{gen_getter, gen_list_index, is_root_optional};
+pub use self::text::gen_rich_text;
+
+mod getter;
+mod text;
+
+pub fn gen_value<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  value: &Value,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match value.value_inner.as_ref().unwrap() {
+    ValueInner::Text(value) => {
+      gen_rich_text(output, component, state, trace, value)?
+    }
+    // quantities are floats
+    ValueInner::Quantity(value) => write!(output, "{value:?}")?,
+    ValueInner::Boolean(value) => {
+      if *value {
+        write!(output, "true")?;
+      } else {
+        write!(output, "false")?;
+      }
+    }
+    ValueInner::Getter(value) => {
+      gen_getter(output, component, state, value)?;
+    }
+    ValueInner::List(values) => {
+      write!(output, "vec![")?;
+      let mut is_first = true;
+      let trace_current = &[trace, &["*"]].concat();
+      for value in &values.values {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        gen_expressions(output, component, state, trace_current, false, value)?;
+      }
+      write!(output, "]")?;
+    }
+    ValueInner::Object(value) => {
+      let object = <S as Stated<ObjectGetterState>>::state(state)
+        .get(component)
+        .and_then(|component_objects| component_objects.get(trace));
+      if let Some(object) = object {
+        // missing keys are filled by the derived default
+        write_class_name(output, trace)?;
+        write!(output, " {{ ")?;
+        for (key, value) in &value.values {
+          write!(output, "{key}: ")?;
+          let is_optional = !object.required.contains_key(key);
+          if is_optional {
+            write!(output, "Some(")?;
+          }
+          gen_expressions(
+            output,
+            component,
+            state,
+            &[trace, &[key]].concat(),
+            false,
+            value,
+          )?;
+          if is_optional {
+            write!(output, ")")?;
+          }
+          write!(output, ", ")?;
+        }
+        write!(output, "..Default::default() }}")?;
+      } else {
+        write!(output, "std::collections::BTreeMap::from([")?;
+        let mut is_first = true;
+        for (key, value) in &value.values {
+          if !is_first {
+            write!(output, ", ")?;
+          }
+          is_first = false;
+          write!(output, "(String::from(\"{key}\"), ")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &[trace, &[key]].concat(),
+            false,
+            value,
+          )?;
+          write!(output, ")")?;
+        }
+        write!(output, "])")?;
+      }
+    }
+    ValueInner::Undefined(_) => write!(output, "None")?,
+  }
+  Ok(())
+}
@@ -0,0 +1,171 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Expression, ExpressionInner, FormatInner, Getter, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key,
+  properties_resolver::PropertiesResolverState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::{expressions::gen_expressions, is_undefined, Sub};
+
+// returns whether the value read is an option, signals are read by copy
+pub fn gen_getter<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  value: &Getter,
+) -> Result<bool, fmt::Error>
+where
+  S: Sub<'a>,
+{
+  write!(output, "{}.get()", value.ident)?;
+  let mut is_optional = is_root_optional(component, state, &value.ident);
+  let mut trace_current = Vec::with_capacity(value.indexes.len() + 1);
+  trace_current.push(value.ident.as_str());
+  for (i, key) in value.indexes.iter().enumerate() {
+    let trace_key = static_key(key).unwrap_or("*");
+    let receiver = receiver_of(component, state, value, i);
+    // options are chained through closures
+    let is_mapped = match &receiver {
+      Receiver::Object(required) if trace_key != "*" => {
+        required.contains(&trace_key)
+      }
+      _ => false,
+    };
+    let is_chained = is_optional;
+    if is_chained {
+      if is_mapped {
+        write!(output, ".map(|inner_| inner_")?;
+      } else {
+        write!(output, ".and_then(|inner_| inner_")?;
+      }
+    }
+    match receiver {
+      Receiver::Object(_) if trace_key != "*" => {
+        write!(output, ".{trace_key}")?;
+        is_optional |= !is_mapped;
+      }
+      Receiver::Object(_) => {
+        write!(output, ".get(&")?;
+        gen_expressions(output, component, state, &trace_current, true, key)?;
+        write!(output, ")")?;
+        is_optional = true;
+      }
+      Receiver::Index | Receiver::Any => {
+        write!(output, ".get(&")?;
+        gen_expressions(output, component, state, &trace_current, true, key)?;
+        write!(output, ").cloned()")?;
+        is_optional = true;
+      }
+      Receiver::List => {
+        write!(output, ".get(")?;
+        gen_list_index(output, component, state, &trace_current, key)?;
+        write!(output, ").cloned()")?;
+        is_optional = true;
+      }
+    }
+    if is_chained {
+      write!(output, ")")?;
+    }
+    trace_current.push(trace_key);
+  }
+  Ok(is_optional)
+}
+
+enum Receiver<'a> {
+  Object(Vec<&'a str>),
+  List,
+  Index,
+  Any,
+}
+
+// format of the getter value indexed by its `depth`th key
+fn receiver_of<'a, 'b, S>(
+  component: &str,
+  state: &'b S,
+  getter: &Getter,
+  depth: usize,
+) -> Receiver<'b>
+where
+  'a: 'b,
+  S: Sub<'a>,
+{
+  let receiver = Getter {
+    ident: getter.ident.clone(),
+    indexes: getter.indexes[..depth].to_vec(),
+  };
+  let format = <S as Stated<FormatsState>>::state(state)
+    .format_of(component, &receiver)
+    .and_then(|format| format.format_inner.as_ref());
+  match format {
+    Some(FormatInner::Object(object)) => {
+      Receiver::Object(object.required.keys().map(String::as_str).collect())
+    }
+    Some(FormatInner::List(_)) => Receiver::List,
+    Some(FormatInner::Index(_)) => Receiver::Index,
+    _ => Receiver::Any,
+  }
+}
+
+// quantities are floats, vector indexes are usizes
+pub fn gen_list_index<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  key: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if let ExpressionInner::Value(Value {
+    value_inner: Some(ValueInner::Quantity(index)),
+  }) = key.expression_inner.as_ref().unwrap()
+  {
+    return write!(output, "{}", *index as usize);
+  }
+  gen_expressions(output, component, state, trace, true, key)?;
+  write!(output, " as usize")
+}
+
+// properties without default and unset variables hold `None`
+pub fn is_root_optional<'a, S>(component: &str, state: &S, ident: &str) -> bool
+where
+  S: Sub<'a>,
+{
+  match <S as Stated<FormatsState>>::state(state).default_of(component, ident) {
+    None => true,
+    Some(default) if is_undefined(default) => {
+      <S as Stated<PropertiesResolverState>>::state(state)
+        .is_variable(component, ident)
+    }
+    Some(_) => false,
+  }
+}
@@ -0,0 +1,101 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{
+  Expression, ExpressionInner, RichText, RichTextInner, Value, ValueInner,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::{super::gen_expressions, gen_getter};
+
+// static texts are owned strings, dynamic ones are formatted
+pub fn gen_rich_text<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  value: &RichText,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let is_dynamic = value.parts.iter().any(|part| {
+    matches!(part.rich_text_inner, Some(RichTextInner::Dynamic(_)))
+  });
+  if is_dynamic {
+    write!(output, "format!(\"")?;
+  } else {
+    write!(output, "String::from(\"")?;
+  }
+  for part in &value.parts {
+    match part.rich_text_inner.as_ref().unwrap() {
+      RichTextInner::Static(part) => {
+        for c in part.chars() {
+          match c {
+            '\\' | '"' => write!(output, "\\{c}")?,
+            '\n' => write!(output, "\\n")?,
+            '{' | '}' if is_dynamic => write!(output, "{c}{c}")?,
+            _ => output.write_char(c)?,
+          }
+        }
+      }
+      RichTextInner::Dynamic(_) => write!(output, "{{}}")?,
+    }
+  }
+  write!(output, "\"")?;
+  for part in &value.parts {
+    let Some(RichTextInner::Dynamic(expression)) = &part.rich_text_inner else {
+      continue;
+    };
+    write!(output, ", ")?;
+    gen_display(output, component, state, trace, expression)?;
+  }
+  write!(output, ")")
+}
+
+// options are displayed empty when unset
+fn gen_display<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  expression: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if let ExpressionInner::Value(Value {
+    value_inner: Some(ValueInner::Getter(getter)),
+  }) = expression.expression_inner.as_ref().unwrap()
+  {
+    if gen_getter(output, component, state, getter)? {
+      write!(
+        output,
+        ".map(|inner_| inner_.to_string()).unwrap_or_default()"
+      )?;
+    }
+    return Ok(());
+  }
+  gen_expressions(output, component, state, trace, false, expression)
+}
@@ -0,0 +1,261 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_common::{to_kebab_case, to_upper_camelcase};
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, Expression, ExpressionInner, Getter, Model,
+  Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  objects_getter::ObjectGetterState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::EXTENSION;
+
+use self::{
+  expressions::gen_expressions,
+  formats::{gen_format, gen_optional_format},
+  structs::gen_structs,
+  views::gen_zone,
+};
+
+mod expressions;
+mod formats;
+mod structs;
+mod views;
+
+pub trait Sub<'a>:
+  Stated<ObjectGetterState<'a>>
+  + Stated<UpdatedAndListenersState<'a>>
+  + Stated<PropertiesResolverState<'a>>
+  + Stated<FormatsState<'a>>
+{
+}
+
+impl<'a, S> Sub<'a> for S where
+  S: Stated<ObjectGetterState<'a>>
+    + Stated<UpdatedAndListenersState<'a>>
+    + Stated<PropertiesResolverState<'a>>
+    + Stated<FormatsState<'a>>
+{
+}
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    let mut modules = BTreeMap::<String, BTreeSet<&str>>::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+
+      // each directory declares its modules
+      let mut directory = String::new();
+      for module in component.id.split('/') {
+        modules.entry(directory.clone()).or_default().insert(module);
+        directory.push_str(module);
+        directory.push('/');
+      }
+    }
+    for (directory, modules) in modules {
+      let mut file = String::new();
+      for module in modules {
+        writeln!(file, "pub mod {module};")?;
+      }
+      files.insert(alloc::format!("{directory}mod{EXTENSION}"), file);
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let formats = <S as Stated<FormatsState>>::state(state);
+  write!(output, "use leptos::*;")?;
+  output.line()?;
+
+  // externs are siblings in the module tree
+  let mut externs = BTreeSet::new();
+  for child in &component.zone.as_ref().unwrap().blocks {
+    if let ComponentChildInner::Extern(r#extern) =
+      child.component_child_inner.as_ref().unwrap()
+    {
+      externs.insert(r#extern.id.as_str());
+    }
+  }
+  for r#extern in &externs {
+    write!(output, "use ")?;
+    for _ in 0..=id.matches('/').count() {
+      write!(output, "super::")?;
+    }
+    write!(
+      output,
+      "{}::{};",
+      r#extern.replace('/', "::"),
+      to_upper_camelcase(r#extern)
+    )?;
+    output.line()?;
+  }
+  output.line()?;
+
+  write!(output, "#[component]")?;
+  output.line()?;
+  write!(output, "pub fn {}(", component.term)?;
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(id)
+    .into_iter()
+    .filter(|notifier| notifier.is_external)
+    .collect::<alloc::vec::Vec<_>>();
+  let has_params = component.properties.is_some() || !notifiers.is_empty();
+  if has_params {
+    output.open("")?;
+  }
+  if let Some(properties) = &component.properties {
+    for key_format in &properties.keys {
+      let key = key_format.key.as_str();
+      let format = key_format.format.as_ref().unwrap();
+      match properties.required.get(key) {
+        Some(default) if is_undefined(default) => {
+          write!(output, "#[prop(into)] {key}: Signal<")?;
+          gen_format(output, &[key], format)?;
+        }
+        Some(default) => {
+          write!(output, "#[prop(into, default = Signal::derive(|| ")?;
+          gen_expressions(output, id, state, &[key], false, default)?;
+          write!(output, "))] {key}: Signal<")?;
+          gen_format(output, &[key], format)?;
+        }
+        None => {
+          write!(
+            output,
+            "#[prop(into, default = Signal::derive(|| None))] {key}: Signal<"
+          )?;
+          gen_optional_format(output, &[key], format)?;
+        }
+      }
+      write!(output, ">")?;
+      output.item()?;
+    }
+  }
+  // external updates are given back to the owner
+  for notifier in &notifiers {
+    write!(output, "#[prop(into, optional)] ")?;
+    write_callback_name(output, &notifier.getter)?;
+    write!(output, ": Option<Callback<")?;
+    gen_format(
+      output,
+      &[],
+      formats.format_of(id, &notifier.getter).unwrap(),
+    )?;
+    write!(output, ">>")?;
+    output.item()?;
+  }
+  if has_params {
+    output.dedent();
+  }
+  output.open(") -> impl IntoView {")?;
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      let format = key_format.format.as_ref().unwrap();
+      write!(output, "let {key} = create_rw_signal::<")?;
+      match variables.required.get(key) {
+        Some(default) if !is_undefined(default) => {
+          gen_format(output, &[key], format)?;
+          write!(output, ">(")?;
+          gen_expressions(output, id, state, &[key], false, default)?;
+        }
+        _ => {
+          gen_optional_format(output, &[key], format)?;
+          write!(output, ">(None")?;
+        }
+      }
+      write!(output, ");")?;
+      output.line()?;
+    }
+  }
+  gen_zone(output, id, state, component.zone.as_ref().unwrap())?;
+  output.close("}")?;
+  output.line()?;
+
+  gen_structs(output, id, state)
+}
+
+// `on_{getter}_change`, in snake case
+fn write_callback_name(
+  output: &mut CodeWriter,
+  getter: &Getter,
+) -> fmt::Result {
+  let mut name = String::new();
+  write_getter_name(&mut name, getter)?;
+  write!(
+    output,
+    "on_{}_change",
+    to_kebab_case(&name).replace('-', "_")
+  )
+}
+
+pub fn is_undefined(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner.as_ref().unwrap(),
+    ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Undefined(_)),
+    })
+  )
+}
@@ -0,0 +1,124 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::BTreeMap,
+  fmt::{self, Write},
+};
+use dropin_target_analysis::{
+  objects_getter::{write_class_name, ObjectGetterState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{
+  expressions::gen_expressions,
+  formats::{gen_format, gen_optional_format},
+  is_undefined, Sub,
+};
+
+pub fn gen_structs<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for (trace, format) in <S as Stated<ObjectGetterState>>::state(state)
+    .get(component)
+    .unwrap_or(&BTreeMap::new())
+  {
+    output.line()?;
+    write!(
+      output,
+      "#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]"
+    )?;
+    output.line()?;
+    write!(output, "pub struct ")?;
+    write_class_name(output, trace)?;
+    output.open(" {")?;
+    for key_format in &format.keys {
+      let key = key_format.key.as_str();
+      let trace = &[trace.as_slice(), &[key]].concat();
+      write!(output, "pub {key}: ")?;
+      if format.required.contains_key(key) {
+        gen_format(output, trace, key_format.format.as_ref().unwrap())?;
+      } else {
+        gen_optional_format(
+          output,
+          trace,
+          key_format.format.as_ref().unwrap(),
+        )?;
+      }
+      write!(output, ",")?;
+      output.line()?;
+    }
+    output.close("}")?;
+    output.line()?;
+    output.line()?;
+
+    // required keys start from their default values
+    write!(output, "impl Default for ")?;
+    write_class_name(output, trace)?;
+    output.open(" {")?;
+    output.open("fn default() -> Self {")?;
+    output.open("Self {")?;
+    for key_format in &format.keys {
+      let key = key_format.key.as_str();
+      write!(output, "{key}: ")?;
+      match format.required.get(key) {
+        Some(default) if !is_undefined(default) => gen_expressions(
+          output,
+          component,
+          state,
+          &[trace.as_slice(), &[key]].concat(),
+          false,
+          default,
+        )?,
+        Some(_) => write!(output, "Default::default()")?,
+        None => write!(output, "None")?,
+      }
+      output.item()?;
+    }
+    output.close("}")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.line()?;
+
+    // dynamic access
+    write!(output, "impl ")?;
+    write_class_name(output, trace)?;
+    output.open(" {")?;
+    output
+      .open("pub fn get(&self, key: &str) -> Option<serde_json::Value> {")?;
+    write!(output, "serde_json::to_value(self).ok()?.get(key).cloned()")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+  }
+  Ok(())
+}
@@ -0,0 +1,263 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  format,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, Getter,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::UpdatedAndListenersState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{
+  expressions::{
+    gen_expressions, gen_getter, gen_list_index, gen_rich_text,
+    is_root_optional,
+  },
+  formats::gen_format,
+  is_undefined, write_callback_name, Sub,
+};
+
+pub fn gen_zone<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("view! {")?;
+  output
+    .open("<div style=\"display: flex; align-items: center; gap: 8px\">")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "<span>{{move || ")?;
+        gen_rich_text(
+          output,
+          component,
+          state,
+          &[],
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, "}}</span>")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        output.open("<input")?;
+        write!(output, "prop:value=move || ")?;
+        if gen_getter(output, component, state, getter)? {
+          write!(
+            output,
+            ".map(|inner_| inner_.to_string()).unwrap_or_default()"
+          )?;
+        } else {
+          write!(output, ".to_string()")?;
+        }
+        output.line()?;
+        output.open("on:input=move |ev| {")?;
+        let is_quantity = matches!(
+          formats
+            .format_of(component, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        write!(output, "let new_ = event_target_value(&ev)")?;
+        if is_quantity {
+          write!(output, ".parse::<f64>().unwrap_or_default()")?;
+        }
+        write!(output, ";")?;
+        output.line()?;
+        gen_update(output, component, state, getter)?;
+        output.close("}")?;
+        output.line()?;
+        output.close("/>")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        output.open(&format!("<{}", to_upper_camelcase(&r#extern.id)))?;
+        for (key, value) in &r#extern.properties.as_ref().unwrap().values {
+          write!(output, "{key}=Signal::derive(move || ")?;
+          // unset properties of the extern are options
+          let is_optional =
+            is_root_optional(&r#extern.id, state, key) && !is_undefined(value);
+          if is_optional {
+            write!(output, "Some(")?;
+          }
+          gen_expressions(
+            output,
+            component,
+            state,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          if is_optional {
+            write!(output, ")")?;
+          }
+          write!(output, ")")?;
+          output.line()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write_callback_name(output, updated_by)?;
+          write!(output, "=move |new_: ")?;
+          gen_format(
+            output,
+            &[],
+            formats.format_of(component, &notifier.getter).unwrap(),
+          )?;
+          output.open("| {")?;
+          gen_update(output, component, state, &notifier.getter)?;
+          output.close("}")?;
+          output.line()?;
+        }
+        output.close("/>")?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("</div>")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()
+}
+
+// writes `new_` to the getter, properties go through their callback
+fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if !<S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+  {
+    write!(output, "if let Some(callback) = ")?;
+    write_callback_name(output, getter)?;
+    output.open(" {")?;
+    write!(output, "callback.call(new_);")?;
+    output.line()?;
+    output.close("}")?;
+    return output.line();
+  }
+  let is_root_optional = is_root_optional(component, state, &getter.ident);
+  if getter.indexes.is_empty() {
+    if is_root_optional {
+      write!(output, "{}.set(Some(new_));", getter.ident)?;
+    } else {
+      write!(output, "{}.set(new_);", getter.ident)?;
+    }
+    return output.line();
+  }
+
+  // nested values are reached through mutable references
+  output.open(&format!("{}.update(|inner_0| {{", getter.ident))?;
+  let mut depth = 1;
+  if is_root_optional {
+    output.open("if let Some(inner_0) = inner_0 {")?;
+    depth += 1;
+  }
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let last = getter.indexes.len() - 1;
+  for (i, key) in getter.indexes.iter().enumerate() {
+    let receiver = Getter {
+      ident: getter.ident.clone(),
+      indexes: getter.indexes[..i].to_vec(),
+    };
+    let format = formats
+      .format_of(component, &receiver)
+      .and_then(|format| format.format_inner.as_ref());
+    let static_key = static_key(key);
+    match (format, static_key) {
+      (Some(FormatInner::Object(object)), Some(field)) => {
+        let is_optional = !object.required.contains_key(field);
+        if i == last {
+          if is_optional {
+            write!(output, "inner_{i}.{field} = Some(new_);")?;
+          } else {
+            write!(output, "inner_{i}.{field} = new_;")?;
+          }
+          output.line()?;
+        } else if is_optional {
+          output.open(&format!(
+            "if let Some(inner_{}) = &mut inner_{i}.{field} {{",
+            i + 1
+          ))?;
+          depth += 1;
+        } else {
+          write!(output, "let inner_{} = &mut inner_{i}.{field};", i + 1)?;
+          output.line()?;
+        }
+      }
+      (Some(FormatInner::Index(_)), _) if i == last => {
+        write!(output, "inner_{i}.insert(")?;
+        gen_expressions(output, component, state, &[], true, key)?;
+        write!(output, ", new_);")?;
+        output.line()?;
+      }
+      (Some(FormatInner::List(_)), _) => {
+        write!(output, "if let Some(inner_{}) = inner_{i}.get_mut(", i + 1)?;
+        gen_list_index(output, component, state, &[], key)?;
+        output.open(") {")?;
+        depth += 1;
+        if i == last {
+          write!(output, "*inner_{} = new_;", i + 1)?;
+          output.line()?;
+        }
+      }
+      _ if i == last => {
+        write!(output, "inner_{i}[&")?;
+        gen_expressions(output, component, state, &[], true, key)?;
+        write!(output, "] = new_.into();")?;
+        output.line()?;
+      }
+      _ => {
+        write!(output, "if let Some(inner_{}) = inner_{i}.get_mut(&", i + 1)?;
+        gen_expressions(output, component, state, &[], true, key)?;
+        output.open(") {")?;
+        depth += 1;
+      }
+    }
+  }
+  for _ in 1..depth {
+    output.close("}")?;
+    output.line()?;
+  }
+  output.close("});")?;
+  output.line()
+}
@@ -0,0 +1,191 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+  collections::BTreeMap,
+  fs::{create_dir_all, write},
+  path::Path,
+  process::Command,
+};
+
+use dropin_compiler_recipes::parser::parse_model_from_files;
+use dropin_target::{Generated, Options, Target};
+use dropin_target_leptos::Leptos;
+
+const MODEL: &str = r#"
+app:
+  name: test
+  version: 0.1.0
+  pages:
+    home:
+      component: home
+      title: Home
+      path: /
+"#;
+
+const HOME: &str = r#"
+variables:
+  required:
+    title: '"tasks"'
+    count: '1'
+    task: ''
+  keys:
+    title:
+      type: text
+    count:
+      type: quantity
+    task:
+      type: object
+      required:
+        label: '"todo"'
+      keys:
+        label:
+          type: text
+        tags:
+          type: list
+          format:
+            type: text
+blocks:
+  - type: text
+    content: "${title}: ${count * 2}"
+  - type: input
+    on_change: count
+  - type: input
+    on_change: task.tags[0]
+  - type: component
+    id: child
+    properties:
+      value: title
+"#;
+
+const CHILD: &str = r#"
+properties:
+  required:
+    value: '"x"'
+  keys:
+    value:
+      type: text
+    hint:
+      type: text
+blocks:
+  - type: text
+    content: "${hint}"
+  - type: input
+    on_change: value
+"#;
+
+const MANIFEST: &str = r#"
+[package]
+name = "dropin-leptos-check"
+version = "0.0.0"
+edition = "2021"
+
+[workspace]
+
+[dependencies]
+leptos = { version = "0.6", features = ["csr"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+"#;
+
+fn generate() -> Generated {
+  let files = BTreeMap::from([
+    ("model.dropin.yml".into(), MODEL.into()),
+    ("components/home.dropin.yml".into(), HOME.into()),
+    ("components/child.dropin.yml".into(), CHILD.into()),
+  ]);
+  let model = parse_model_from_files(&files).unwrap();
+  Leptos.generate(&model, &Options::default()).unwrap()
+}
+
+#[test]
+fn components() {
+  let generated = generate();
+  assert!(generated.warnings.is_empty());
+  let files = generated
+    .files
+    .into_iter()
+    .map(|file| (file.path, file.content))
+    .collect::<BTreeMap<_, _>>();
+  assert_eq!(files["mod.rs"], "pub mod child;\npub mod home;\n");
+  let child = &files["child.rs"];
+  assert!(
+    child.contains(
+      "#[prop(into, default = Signal::derive(|| String::from(\"x\")))] \
+       value: Signal<String>,"
+    ),
+    "{child}"
+  );
+  assert!(
+    child.contains(
+      "#[prop(into, default = Signal::derive(|| None))] \
+       hint: Signal<Option<String>>,"
+    ),
+    "{child}"
+  );
+  assert!(
+    child.contains(
+      "#[prop(into, optional)] on_value_change: Option<Callback<String>>,"
+    ),
+    "{child}"
+  );
+  let home = &files["home.rs"];
+  assert!(home.contains("use super::child::Child;"), "{home}");
+  assert!(
+    home.contains("create_rw_signal::<Option<TaskObject>>(None)"),
+    "{home}"
+  );
+  assert!(
+    home.contains("parse::<f64>().unwrap_or_default()"),
+    "{home}"
+  );
+  assert!(home.contains("inner_1.get_mut(0)"), "{home}");
+  assert!(
+    home.contains("on_value_change=move |new_: String|"),
+    "{home}"
+  );
+}
+
+// needs the network to fetch leptos
+#[test]
+#[ignore]
+fn cargo_check() {
+  let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("leptos-check");
+  let components = root.join("src").join("components");
+  create_dir_all(&components).unwrap();
+  write(root.join("Cargo.toml"), MANIFEST).unwrap();
+  write(
+    root.join("src").join("lib.rs"),
+    "#![allow(non_camel_case_types, non_snake_case)]\n\npub mod components;\n",
+  )
+  .unwrap();
+  for file in generate().files {
+    write(components.join(&file.path), &file.content).unwrap();
+  }
+  let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+  let status = Command::new(cargo)
+    .arg("check")
+    .current_dir(&root)
+    .env_remove("CARGO_TARGET_DIR")
+    .status()
+    .unwrap();
+  assert!(status.success());
+}
@@ -0,0 +1,50 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target_leptos::Leptos;
+use dropin_target_tests::{generate, HOME, OPTIONAL};
+
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&Leptos, HOME);
+  assert!(code.contains("count.get().rem_euclid(3.0)"), "{code}");
+  assert!(
+    code.contains("count.get().rem_euclid(step.get())"),
+    "{code}"
+  );
+}
+
+#[test]
+fn optional_operands() {
+  let code = generate(&Leptos, OPTIONAL);
+  assert!(
+    code.contains("inner_.tags).unwrap_or_default().contains(&name.get())"),
+    "{code}"
+  );
+  assert!(
+    code.contains(".cloned()).unwrap_or_default() > 1.0"),
+    "{code}"
+  );
+  assert!(
+    code.contains("inner_.age).unwrap_or_default() <= 3.0"),
+    "{code}"
+  );
+}
@@ -0,0 +1,22 @@
+use leptos::*;
+
+#[component]
+pub fn Child(
+  #[prop(into, default = Signal::derive(|| String::from("x")))] value: Signal<String>,
+  #[prop(into, optional)] on_value_change: Option<Callback<String>>,
+) -> impl IntoView {
+  view! {
+    <div style="display: flex; align-items: center; gap: 8px">
+      <span>{move || format!("Child {}", value.get())}</span>
+      <input
+        prop:value=move || value.get().to_string()
+        on:input=move |ev| {
+          let new_ = event_target_value(&ev);
+          if let Some(callback) = on_value_change {
+            callback.call(new_);
+          }
+        }
+      />
+    </div>
+  }
+}
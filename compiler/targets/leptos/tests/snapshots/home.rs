@@ -0,0 +1,93 @@
+use leptos::*;
+use super::child::Child;
+
+#[component]
+pub fn Home() -> impl IntoView {
+  let name = create_rw_signal::<String>(String::from("world"));
+  let count = create_rw_signal::<f64>(1.0);
+  let user = create_rw_signal::<Option<UserObject>>(None);
+  view! {
+    <div style="display: flex; align-items: center; gap: 8px">
+      <span>{move || format!("Hello {} {}", name.get(), count.get() + 2.0)}</span>
+      <input
+        prop:value=move || name.get().to_string()
+        on:input=move |ev| {
+          let new_ = event_target_value(&ev);
+          name.set(new_);
+        }
+      />
+      <input
+        prop:value=move || count.get().to_string()
+        on:input=move |ev| {
+          let new_ = event_target_value(&ev).parse::<f64>().unwrap_or_default();
+          count.set(new_);
+        }
+      />
+      <input
+        prop:value=move || user.get().and_then(|inner_| inner_.friends).and_then(|inner_| inner_.get(0).cloned()).and_then(|inner_| inner_.name).map(|inner_| inner_.to_string()).unwrap_or_default()
+        on:input=move |ev| {
+          let new_ = event_target_value(&ev);
+          user.update(|inner_0| {
+            if let Some(inner_0) = inner_0 {
+              if let Some(inner_1) = &mut inner_0.friends {
+                if let Some(inner_2) = inner_1.get_mut(0) {
+                  inner_2.name = Some(new_);
+                }
+              }
+            }
+          });
+        }
+      />
+      <Child
+        value=Signal::derive(move || name.get())
+        on_value_change=move |new_: String| {
+          name.set(new_);
+        }
+      />
+    </div>
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UserObject {
+  pub id: f64,
+  pub first: String,
+  pub friends: Option<Vec<UserFriends_Object>>,
+  pub scores: Option<std::collections::BTreeMap<String, f64>>,
+}
+
+impl Default for UserObject {
+  fn default() -> Self {
+    Self {
+      id: Default::default(),
+      first: String::from("anon"),
+      friends: None,
+      scores: None,
+    }
+  }
+}
+
+impl UserObject {
+  pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+    serde_json::to_value(self).ok()?.get(key).cloned()
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UserFriends_Object {
+  pub name: Option<String>,
+}
+
+impl Default for UserFriends_Object {
+  fn default() -> Self {
+    Self {
+      name: None,
+    }
+  }
+}
+
+impl UserFriends_Object {
+  pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+    serde_json::to_value(self).ok()?.get(key).cloned()
+  }
+}
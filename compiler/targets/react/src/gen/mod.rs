@@ -0,0 +1,199 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  format,
+  string::String,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{Component, ComponentChildInner, Model};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_expressions, gen_fields, gen_format, gen_initial, gen_interfaces,
+  is_undefined, Scope, Sub,
+};
+
+use crate::EXTENSION;
+
+use self::zones::gen_zone;
+
+mod zones;
+
+// variables are state hooks, properties are read from the merged props
+const SCOPE: Scope = Scope {
+  variables: "",
+  properties: "props.",
+  is_typed: true,
+};
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let term = component.term.as_str();
+
+  let mut externs = BTreeSet::new();
+  for child in &component.zone.as_ref().unwrap().blocks {
+    if let ComponentChildInner::Extern(r#extern) =
+      child.component_child_inner.as_ref().unwrap()
+    {
+      externs.insert(r#extern.id.as_str());
+    }
+  }
+  if component.variables.is_some() {
+    write!(output, "import {{ useState }} from \"react\";")?;
+    output.line()?;
+  }
+  for r#extern in &externs {
+    let extern_term = to_upper_camelcase(r#extern);
+    write!(output, "import {{ {extern_term} }} from \"./{extern}\";")?;
+    output.line()?;
+  }
+  if component.variables.is_some() || !externs.is_empty() {
+    output.line()?;
+  }
+
+  // props
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+  output.open(&format!("export interface {term}Props {{"))?;
+  if let Some(properties) = &component.properties {
+    gen_fields(output, properties, true)?;
+  }
+  for notifier in &notifiers {
+    if !notifier.is_external {
+      continue;
+    }
+    write!(output, "update")?;
+    write_getter_name(output, &notifier.getter)?;
+    write!(output, ": (value: ")?;
+    gen_format(
+      output,
+      &[],
+      formats.format_of(id, &notifier.getter).unwrap(),
+    )?;
+    write!(output, ") => void;")?;
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()?;
+  output.line()?;
+
+  // function component
+  if let Some(properties) = &component.properties {
+    output.open(&format!(
+      "export function {term}(properties: {term}Props) {{"
+    ))?;
+    write!(output, "const props = {{ ")?;
+    for (key, default) in &properties.required {
+      if is_undefined(default) {
+        continue;
+      }
+      write!(output, "{key}: ")?;
+      gen_expressions(output, id, state, &SCOPE, &[key], false, default)?;
+      write!(output, ", ")?;
+    }
+    write!(output, "...properties }};")?;
+    output.line()?;
+  } else {
+    output.open(&format!("export function {term}(props: {term}Props) {{"))?;
+  }
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      write!(
+        output,
+        "const [{key}, set{}] = useState<",
+        to_upper_camelcase(key)
+      )?;
+      gen_format(output, &[key], key_format.format.as_ref().unwrap())?;
+      write!(output, ">(")?;
+      if let Some(default) = variables.required.get(key) {
+        gen_initial(
+          output,
+          id,
+          state,
+          &SCOPE,
+          &[key],
+          key_format.format.as_ref().unwrap(),
+          default,
+        )?;
+      }
+      write!(output, ");")?;
+      output.line()?;
+    }
+  }
+  output.open("return (")?;
+  gen_zone(output, id, state, component.zone.as_ref().unwrap())?;
+  output.close(");")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+
+  gen_interfaces(output, id, state)?;
+  Ok(())
+}
@@ -0,0 +1,172 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, Getter,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_expressions, gen_format, gen_getter, gen_rich_text, Sub,
+};
+
+use super::SCOPE;
+
+pub fn gen_zone<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("<div style={{ display: \"flex\" }}>")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "<span>{{")?;
+        gen_rich_text(
+          output,
+          component,
+          state,
+          &SCOPE,
+          &[],
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, "}}</span>")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        output.open("<input")?;
+        write!(output, "value={{")?;
+        gen_getter(output, component, state, &SCOPE, false, getter)?;
+        write!(output, " ?? \"\"}}")?;
+        output.line()?;
+        output.open("onChange={(event) => {")?;
+        let is_quantity = matches!(
+          formats
+            .format_of(component, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        let value = if is_quantity {
+          "Number(event.target.value)"
+        } else {
+          "event.target.value"
+        };
+        gen_update(output, component, state, getter, value)?;
+        output.close("}}")?;
+        output.line()?;
+        output.close("/>")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let properties = &r#extern.properties.as_ref().unwrap().values;
+        output
+          .open(&alloc::format!("<{}", to_upper_camelcase(&r#extern.id)))?;
+        for (key, value) in properties {
+          write!(output, "{key}={{")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &SCOPE,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          write!(output, "}}")?;
+          output.line()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "update")?;
+          write_getter_name(output, updated_by)?;
+          write!(output, "={{(value: ")?;
+          gen_format(
+            output,
+            &[],
+            formats.format_of(component, &notifier.getter).unwrap(),
+          )?;
+          output.open(") => {")?;
+          gen_update(output, component, state, &notifier.getter, "value")?;
+          output.close("}}")?;
+          output.line()?;
+        }
+        output.close("/>")?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("</div>")?;
+  output.line()
+}
+
+// variables go through their state setter, properties back to their owner
+fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let ident = getter.ident.as_str();
+  if !<S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, ident)
+  {
+    write!(output, "{}update", SCOPE.properties)?;
+    write_getter_name(output, getter)?;
+    write!(output, "({value});")?;
+  } else if getter.indexes.is_empty() {
+    write!(output, "set{}({value});", to_upper_camelcase(ident))?;
+  } else {
+    // nested keys are set on a copy, the state itself is never mutated
+    output.open(&alloc::format!(
+      "set{}(({ident}) => {{",
+      to_upper_camelcase(ident)
+    ))?;
+    write!(output, "{ident} = structuredClone({ident});")?;
+    output.line()?;
+    gen_getter(output, component, state, &SCOPE, true, getter)?;
+    write!(output, " = {value};")?;
+    output.line()?;
+    write!(output, "return {ident};")?;
+    output.line()?;
+    output.close("});")?;
+  }
+  output.line()
+}
@@ -0,0 +1,156 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{
+  Binary, Control, Expression, ExpressionInner, Value, ValueInner,
+};
+use dropin_target_analysis::{formats::FormatsState, Stated};
+use dropin_target_gen::{
+  expressions::is_positive_quantity, CodeWriter, ExpressionGen,
+};
+
+pub use self::{
+  control::gen_control,
+  value::{gen_getter, gen_rich_text, gen_value},
+};
+
+use super::{formats::zero_of, Sub};
+
+mod control;
+mod value;
+
+pub fn gen_expressions<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  is_nested: bool,
+  expression: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  Expressions { component, state }
+    .gen_expression(output, trace, is_nested, expression)
+}
+
+struct Expressions<'s, S> {
+  component: &'s str,
+  state: &'s S,
+}
+
+impl<'a, S> Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  // the zero of an optional getter
+  fn zero_of(&self, operand: &Expression) -> Option<&'static str> {
+    let ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Getter(getter)),
+    }) = operand.expression_inner.as_ref().unwrap()
+    else {
+      return None;
+    };
+    let mut read = CodeWriter::new(true, None);
+    if !gen_getter(&mut read, self.component, self.state, getter).ok()? {
+      return None;
+    }
+    <S as Stated<FormatsState>>::state(self.state)
+      .format_of(self.component, getter)
+      .and_then(zero_of)
+  }
+}
+
+impl<'a, S> ExpressionGen for Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  const NULL: &'static str = "nil";
+  const MOD: &'static str = "{ (a: Double, b: Double) in \
+    (a.truncatingRemainder(dividingBy: b) + abs(b))\
+    .truncatingRemainder(dividingBy: abs(b)) }";
+
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    gen_value(output, self.component, self.state, trace, value)
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result {
+    gen_control(output, self.component, self.state, control)
+  }
+
+  // `%` is only defined on integers, the remainder is truncated by
+  // `truncatingRemainder(dividingBy:)`
+  fn gen_mod(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    binary: &Binary,
+  ) -> fmt::Result {
+    let left = binary.left.as_ref().unwrap();
+    let right = binary.right.as_ref().unwrap();
+    if is_positive_quantity(right) {
+      write!(output, "(")?;
+      self.gen_expression(output, trace, true, left)?;
+      write!(output, ".truncatingRemainder(dividingBy: ")?;
+      self.gen_expression(output, trace, false, right)?;
+      write!(output, ") + ")?;
+      self.gen_expression(output, trace, true, right)?;
+      write!(output, ").truncatingRemainder(dividingBy: ")?;
+      self.gen_expression(output, trace, false, right)?;
+      return write!(output, ")");
+    }
+    write!(output, "{}(", Self::MOD)?;
+    self.gen_expression(output, trace, false, left)?;
+    write!(output, ", ")?;
+    self.gen_expression(output, trace, false, right)?;
+    write!(output, ")")
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_call(output, "pow", binary)
+  }
+
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_contains(output, binary, ".contains(")
+  }
+
+  fn is_optional(&self, operand: &Expression) -> bool {
+    self.zero_of(operand).is_some()
+  }
+
+  fn gen_empty(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    write!(output, "{}", self.zero_of(operand).unwrap())
+  }
+}
@@ -0,0 +1,161 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Expression, ExpressionInner, FormatInner, Getter, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key,
+  properties_resolver::PropertiesResolverState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::{expressions::gen_expressions, is_undefined, Sub};
+
+// returns whether the value read is optional, assignments use the same chain
+pub fn gen_getter<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  value: &Getter,
+) -> Result<bool, fmt::Error>
+where
+  S: Sub<'a>,
+{
+  let start = output.len();
+  write!(output, "{}", value.ident)?;
+  let mut is_optional = is_root_optional(component, state, &value.ident);
+  let mut is_chained = false;
+  let mut trace_current = Vec::with_capacity(value.indexes.len() + 1);
+  trace_current.push(value.ident.as_str());
+  for (i, key) in value.indexes.iter().enumerate() {
+    let trace_key = static_key(key).unwrap_or("*");
+    if is_optional {
+      is_chained = true;
+    }
+    let chain = if is_optional { "?" } else { "" };
+    match receiver_of(component, state, value, i) {
+      Receiver::Object(required) if trace_key != "*" => {
+        write!(output, "{chain}.{trace_key}")?;
+        is_optional = !required.contains(&trace_key);
+      }
+      Receiver::Object(_) | Receiver::Index => {
+        write!(output, "{chain}[")?;
+        gen_expressions(output, component, state, &trace_current, false, key)?;
+        write!(output, "]")?;
+        is_optional = true;
+      }
+      Receiver::List => {
+        write!(output, "{chain}[")?;
+        gen_list_index(output, component, state, &trace_current, key)?;
+        write!(output, "]")?;
+        is_optional = false;
+      }
+      Receiver::Any => {
+        output.insert(start, '(');
+        write!(output, " as? [String: Any?])?[")?;
+        gen_expressions(output, component, state, &trace_current, false, key)?;
+        write!(output, "]")?;
+        is_chained = true;
+        is_optional = true;
+      }
+    }
+    trace_current.push(trace_key);
+  }
+  Ok(is_chained || is_optional)
+}
+
+enum Receiver<'a> {
+  Object(Vec<&'a str>),
+  List,
+  Index,
+  Any,
+}
+
+// format of the getter value indexed by its `depth`th key
+fn receiver_of<'a, 'b, S>(
+  component: &str,
+  state: &'b S,
+  getter: &Getter,
+  depth: usize,
+) -> Receiver<'b>
+where
+  'a: 'b,
+  S: Sub<'a>,
+{
+  let receiver = Getter {
+    ident: getter.ident.clone(),
+    indexes: getter.indexes[..depth].to_vec(),
+  };
+  let format = <S as Stated<FormatsState>>::state(state)
+    .format_of(component, &receiver)
+    .and_then(|format| format.format_inner.as_ref());
+  match format {
+    Some(FormatInner::Object(object)) => {
+      Receiver::Object(object.required.keys().map(String::as_str).collect())
+    }
+    Some(FormatInner::List(_)) => Receiver::List,
+    Some(FormatInner::Index(_)) => Receiver::Index,
+    _ => Receiver::Any,
+  }
+}
+
+// quantities are doubles, array indexes are ints
+fn gen_list_index<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  key: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if let ExpressionInner::Value(Value {
+    value_inner: Some(ValueInner::Quantity(index)),
+  }) = key.expression_inner.as_ref().unwrap()
+  {
+    return write!(output, "{}", *index as i64);
+  }
+  write!(output, "Int(")?;
+  gen_expressions(output, component, state, trace, false, key)?;
+  write!(output, ")")
+}
+
+// properties without default and unset variables hold nil
+pub fn is_root_optional<'a, S>(component: &str, state: &S, ident: &str) -> bool
+where
+  S: Sub<'a>,
+{
+  match <S as Stated<FormatsState>>::state(state).default_of(component, ident) {
+    None => true,
+    Some(default) if is_undefined(default) => {
+      <S as Stated<PropertiesResolverState>>::state(state)
+        .is_variable(component, ident)
+    }
+    Some(_) => false,
+  }
+}
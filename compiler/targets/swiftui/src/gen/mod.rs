@@ -0,0 +1,307 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{
+  Component, Expression, ExpressionInner, KeyFormat, Model, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::ObjectGetterState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::UpdatedAndListenersState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::EXTENSION;
+
+use self::{
+  expressions::gen_expressions,
+  formats::{gen_format, gen_optional_format},
+  structs::gen_structs,
+  views::gen_zone,
+};
+
+mod expressions;
+mod formats;
+mod structs;
+mod views;
+
+pub trait Sub<'a>:
+  Stated<ObjectGetterState<'a>>
+  + Stated<UpdatedAndListenersState<'a>>
+  + Stated<PropertiesResolverState<'a>>
+  + Stated<FormatsState<'a>>
+{
+}
+
+impl<'a, S> Sub<'a> for S where
+  S: Stated<ObjectGetterState<'a>>
+    + Stated<UpdatedAndListenersState<'a>>
+    + Stated<PropertiesResolverState<'a>>
+    + Stated<FormatsState<'a>>
+{
+}
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  write!(output, "import SwiftUI")?;
+  output.line()?;
+  output.line()?;
+
+  output.open(&alloc::format!("struct {}: View {{", component.term))?;
+  let bindings = get_bindings(state, id);
+  if let Some(properties) = &component.properties {
+    gen_fields(
+      output,
+      id,
+      state,
+      &[],
+      &properties.required,
+      &properties.keys,
+      |key| {
+        if bindings.contains(key) {
+          "@Binding "
+        } else {
+          ""
+        }
+      },
+    )?;
+  }
+  if let Some(variables) = &component.variables {
+    gen_fields(
+      output,
+      id,
+      state,
+      &[],
+      &variables.required,
+      &variables.keys,
+      |_| "@State ",
+    )?;
+  }
+  if component.properties.is_some() || component.variables.is_some() {
+    output.line()?;
+  }
+  if let Some(properties) = &component.properties {
+    if properties.keys.iter().any(|key_format| {
+      let key = key_format.key.as_str();
+      bindings.contains(key)
+        && !properties.required.get(key).is_some_and(is_undefined)
+    }) {
+      gen_init(
+        output,
+        id,
+        state,
+        &properties.required,
+        &properties.keys,
+        &bindings,
+      )?;
+      output.line()?;
+    }
+  }
+  output.open("var body: some View {")?;
+  gen_zone(output, id, state, component.zone.as_ref().unwrap())?;
+  output.close("}")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+
+  gen_structs(output, id, state)
+}
+
+// properties updated by children are bound to their owner
+fn get_bindings<'a, S>(state: &S, component: &str) -> BTreeSet<String>
+where
+  S: Sub<'a>,
+{
+  <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component)
+    .into_iter()
+    .filter(|notifier| notifier.is_external)
+    .map(|notifier| notifier.getter.ident.clone())
+    .collect()
+}
+
+// sorted by key, the memberwise initializer follows the declaration order
+fn gen_fields<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  trace: &[&str],
+  required: &BTreeMap<String, Expression>,
+  keys: &[KeyFormat],
+  wrapper: impl Fn(&str) -> &'static str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let mut keys = keys.iter().collect::<Vec<_>>();
+  keys.sort_by_key(|key_format| key_format.key.as_str());
+  for key_format in keys {
+    let key = key_format.key.as_str();
+    let trace = &[trace, &[key]].concat();
+    let format = key_format.format.as_ref().unwrap();
+    let wrapper = wrapper(key);
+    write!(output, "{wrapper}var {key}: ")?;
+    match required.get(key) {
+      Some(default) if is_undefined(default) => {
+        if wrapper == "@State " {
+          gen_optional_format(output, trace, format)?;
+          write!(output, " = nil")?;
+        } else {
+          gen_format(output, trace, format)?;
+        }
+      }
+      // bindings get their defaults in the init
+      Some(_) if wrapper == "@Binding " => gen_format(output, trace, format)?,
+      Some(default) => {
+        gen_format(output, trace, format)?;
+        write!(output, " = ")?;
+        gen_expressions(output, component, state, trace, false, default)?;
+      }
+      None if wrapper == "@Binding " => {
+        gen_optional_format(output, trace, format)?
+      }
+      None => {
+        gen_optional_format(output, trace, format)?;
+        write!(output, " = nil")?;
+      }
+    }
+    output.line()?;
+  }
+  Ok(())
+}
+
+// the memberwise initializer cannot default a binding
+fn gen_init<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  required: &BTreeMap<String, Expression>,
+  keys: &[KeyFormat],
+  bindings: &BTreeSet<String>,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let mut keys = keys.iter().collect::<Vec<_>>();
+  keys.sort_by_key(|key_format| key_format.key.as_str());
+  write!(output, "init(")?;
+  for (i, key_format) in keys.iter().enumerate() {
+    let key = key_format.key.as_str();
+    let trace = &[key];
+    let format = key_format.format.as_ref().unwrap();
+    let is_binding = bindings.contains(key);
+    if i > 0 {
+      write!(output, ", ")?;
+    }
+    write!(output, "{key}: ")?;
+    if is_binding {
+      write!(output, "Binding<")?;
+    }
+    match required.get(key) {
+      Some(_) => gen_format(output, trace, format)?,
+      None => gen_optional_format(output, trace, format)?,
+    }
+    if is_binding {
+      write!(output, ">")?;
+    }
+    match required.get(key) {
+      Some(default) if is_undefined(default) => {}
+      Some(default) if is_binding => {
+        write!(output, " = .constant(")?;
+        gen_expressions(output, component, state, trace, false, default)?;
+        write!(output, ")")?;
+      }
+      Some(default) => {
+        write!(output, " = ")?;
+        gen_expressions(output, component, state, trace, false, default)?;
+      }
+      None if is_binding => write!(output, " = .constant(nil)")?,
+      None => write!(output, " = nil")?,
+    }
+  }
+  output.open(") {")?;
+  for key_format in keys {
+    let key = key_format.key.as_str();
+    if bindings.contains(key) {
+      write!(output, "self._{key} = {key}")?;
+    } else {
+      write!(output, "self.{key} = {key}")?;
+    }
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()
+}
+
+pub fn is_undefined(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner.as_ref().unwrap(),
+    ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Undefined(_)),
+    })
+  )
+}
@@ -0,0 +1,164 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, Format, FormatInner, Getter,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, properties_resolver::PropertiesResolverState,
+  updated_listeners::UpdatedAndListenersState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{
+  expressions::{gen_expressions, gen_getter, gen_rich_text},
+  formats::zero_of,
+  get_bindings, Sub,
+};
+
+pub fn gen_zone<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("HStack {")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "Text(")?;
+        gen_rich_text(
+          output,
+          component,
+          state,
+          &[],
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, ")")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        let format = formats.format_of(component, getter);
+        if let Some(FormatInner::Quantity(_)) =
+          format.and_then(|format| format.format_inner.as_ref())
+        {
+          write!(output, "TextField(\"\", value: ")?;
+          gen_binding(output, component, state, getter, format)?;
+          write!(output, ", format: .number)")?;
+        } else {
+          write!(output, "TextField(\"\", text: ")?;
+          gen_binding(output, component, state, getter, format)?;
+          write!(output, ")")?;
+        }
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let bindings = get_bindings(state, &r#extern.id);
+        write!(output, "{}(", to_upper_camelcase(&r#extern.id))?;
+        let mut is_first = true;
+        for (key, value) in &r#extern.properties.as_ref().unwrap().values {
+          if !is_first {
+            write!(output, ", ")?;
+          }
+          is_first = false;
+          write!(output, "{key}: ")?;
+          if !bindings.contains(key) {
+            gen_expressions(
+              output,
+              component,
+              state,
+              &[key.as_str()],
+              false,
+              value,
+            )?;
+            continue;
+          }
+          let updated = notifiers.iter().find(|notifier| {
+            notifier
+              .updated_by
+              .get(r#extern.id.as_str())
+              .is_some_and(|updated_by| &updated_by.ident == key)
+          });
+          if let Some(updated) = updated {
+            gen_binding(
+              output,
+              component,
+              state,
+              &updated.getter,
+              formats.format_of(component, &updated.getter),
+            )?;
+          } else {
+            // nothing to write back to
+            write!(output, ".constant(")?;
+            gen_expressions(
+              output,
+              component,
+              state,
+              &[key.as_str()],
+              false,
+              value,
+            )?;
+            write!(output, ")")?;
+          }
+        }
+        write!(output, ")")?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()
+}
+
+// `$` projections for whole values, get/set closures through optionals
+fn gen_binding<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  format: Option<&Format>,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let mut read = CodeWriter::new(true, None);
+  let is_optional = gen_getter(&mut read, component, state, getter)?;
+  let read = read.into_code();
+  let is_owned = <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+    || get_bindings(state, component).contains(&getter.ident);
+  if getter.indexes.is_empty() && !is_optional && is_owned {
+    return write!(output, "${read}");
+  }
+  write!(output, "Binding(get: {{ {read}")?;
+  if is_optional {
+    let zero = format.and_then(zero_of).unwrap_or("nil");
+    write!(output, " ?? {zero}")?;
+  }
+  write!(output, " }}, set: {{ {read} = $0 }})")
+}
@@ -0,0 +1,50 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target_swiftui::SwiftUi;
+use dropin_target_tests::{generate, HOME, OPTIONAL};
+
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&SwiftUi, HOME);
+  assert!(
+    code.contains(
+      "(count.truncatingRemainder(dividingBy: 3.0) + 3.0)\
+      .truncatingRemainder(dividingBy: 3.0)"
+    ),
+    "{code}"
+  );
+  assert!(
+    code.contains(
+      "{ (a: Double, b: Double) in (a.truncatingRemainder(dividingBy: b) + \
+      abs(b)).truncatingRemainder(dividingBy: abs(b)) }(count, step)"
+    ),
+    "{code}"
+  );
+}
+
+#[test]
+fn optional_operands() {
+  let code = generate(&SwiftUi, OPTIONAL);
+  assert!(code.contains("(user?.tags ?? []).contains(name)"), "{code}");
+  assert!(code.contains("(user?.scores?[name] ?? 0) > 1.0"), "{code}");
+  assert!(code.contains("(user?.age ?? 0) <= 3.0"), "{code}");
+}
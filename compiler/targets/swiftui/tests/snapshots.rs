@@ -0,0 +1,79 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+  fs::{read_to_string, write},
+  path::Path,
+};
+
+use dropin_compiler_recipes::parser::parse_model;
+use dropin_target::{Options, Target};
+use dropin_target_swiftui::SwiftUi;
+
+// set UPDATE_SNAPSHOTS to rewrite the expected sources
+#[test]
+fn snapshots() {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+  let model = parse_model(&root.join("fixtures")).unwrap();
+  let generated = SwiftUi.generate(&model, &Options::default()).unwrap();
+  assert!(generated.warnings.is_empty());
+  assert_eq!(generated.files.len(), 2);
+  for file in &generated.files {
+    let (path, code) = (&file.path, &file.content);
+    let snapshot = root.join("snapshots").join(path);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+      write(&snapshot, code).unwrap();
+      continue;
+    }
+    assert_eq!(&read_to_string(&snapshot).unwrap(), code, "{path}");
+  }
+}
+
+// bindings keep their defaults through an explicit init
+#[test]
+fn bindings() {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+  let model = parse_model(&root.join("fixtures")).unwrap();
+  let generated = SwiftUi.generate(&model, &Options::default()).unwrap();
+  let code = generated
+    .files
+    .iter()
+    .map(|file| file.content.as_str())
+    .collect::<String>();
+  assert!(code.contains("@Binding var value: String\n"), "{code}");
+  assert!(
+    code.contains(
+      "init(label: String, \
+       value: Binding<String> = .constant(\"x\"))"
+    ),
+    "{code}"
+  );
+  assert!(code.contains("self._value = value"), "{code}");
+  assert!(
+    code.contains("Child(label: \"Name\", value: $name)"),
+    "{code}"
+  );
+  assert!(
+    code.contains("TextField(\"\", value: $count, format: .number)"),
+    "{code}"
+  );
+  assert!(code.contains("set: { profile?.nick = $0 }"), "{code}");
+}
@@ -0,0 +1,18 @@
+import SwiftUI
+
+struct Child: View {
+  var label: String
+  @Binding var value: String
+
+  init(label: String, value: Binding<String> = .constant("x")) {
+    self.label = label
+    self._value = value
+  }
+
+  var body: some View {
+    HStack {
+      Text("\(label): \(value)")
+      TextField("", text: $value)
+    }
+  }
+}
@@ -0,0 +1,29 @@
+import SwiftUI
+
+struct Home: View {
+  @State var count: Double = 1.0
+  @State var name: String = "world"
+  @State var profile: ProfileObject? = nil
+
+  var body: some View {
+    HStack {
+      Text("Hello \(name), \(count * 2.0) times")
+      TextField("", value: $count, format: .number)
+      TextField("", text: Binding(get: { profile?.nick ?? "" }, set: { profile?.nick = $0 }))
+      Child(label: "Name", value: $name)
+    }
+  }
+}
+
+struct ProfileObject {
+  var age: Double? = nil
+  var nick: String = "anon"
+
+  func toMap() -> [String: Any?] {
+    ["nick": nick, "age": age]
+  }
+
+  subscript(key: String) -> Any? {
+    toMap()[key] ?? nil
+  }
+}
@@ -0,0 +1,171 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{fmt, vec::Vec};
+use dropin_compiler_recipes::ir::{
+  Binary, Control, Expression, ExpressionInner, Format, FormatInner, Value,
+  ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, objects_getter::static_key, Stated,
+};
+use dropin_target_gen::{CodeWriter, ExpressionGen};
+
+pub use self::{
+  control::gen_control,
+  value::{
+    creates_containers, gen_getter, gen_rich_text, gen_value, is_optional,
+  },
+};
+use super::gen_empty;
+
+use super::Sub;
+
+mod control;
+mod value;
+
+// prefixes of the getters, by owner of the getter
+#[derive(Debug, Clone, Copy)]
+pub struct Scope<'s> {
+  pub variables: &'s str,
+  pub properties: &'s str,
+  // casts are left out of plain javascript
+  pub is_typed: bool,
+}
+
+pub fn gen_expressions<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  trace: &[&str],
+  is_nested: bool,
+  expression: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  Expressions {
+    component,
+    state,
+    scope,
+  }
+  .gen_expression(output, trace, is_nested, expression)
+}
+
+struct Expressions<'s, S> {
+  component: &'s str,
+  state: &'s S,
+  scope: &'s Scope<'s>,
+}
+
+impl<'a, 's, S> Expressions<'s, S>
+where
+  'a: 's,
+  S: Sub<'a>,
+{
+  // the format of a getter that may be undefined, when it has an empty value
+  fn optional_format(&self, operand: &Expression) -> Option<&'s Format> {
+    let ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Getter(getter)),
+    }) = operand.expression_inner.as_ref().unwrap()
+    else {
+      return None;
+    };
+    if !is_optional(self.component, self.state, getter) {
+      return None;
+    }
+    <S as Stated<FormatsState>>::state(self.state)
+      .format_of(self.component, getter)
+      .filter(|format| {
+        !matches!(format.format_inner, Some(FormatInner::Any(_)))
+      })
+  }
+}
+
+// conditions become ternaries, parenthesized as the other targets' `if`
+impl<'a, S> ExpressionGen for Expressions<'_, S>
+where
+  S: Sub<'a>,
+{
+  const EQUALS_TO: &'static str = " === ";
+  const DIFFERENT_FROM: &'static str = " !== ";
+
+  fn gen_value(
+    &self,
+    output: &mut CodeWriter,
+    trace: &[&str],
+    value: &Value,
+  ) -> fmt::Result {
+    gen_value(output, self.component, self.state, self.scope, trace, value)
+  }
+
+  fn gen_control(
+    &self,
+    output: &mut CodeWriter,
+    control: &Control,
+  ) -> fmt::Result {
+    gen_control(output, self.component, self.state, self.scope, control)
+  }
+
+  fn gen_pow(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_binary(output, binary, " ** ")
+  }
+
+  fn gen_in(&self, output: &mut CodeWriter, binary: &Binary) -> fmt::Result {
+    self.gen_contains(output, binary, ".includes(")
+  }
+
+  fn is_optional(&self, operand: &Expression) -> bool {
+    self.optional_format(operand).is_some()
+  }
+
+  fn gen_empty(
+    &self,
+    output: &mut CodeWriter,
+    operand: &Expression,
+  ) -> fmt::Result {
+    let Some(ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Getter(getter)),
+    })) = operand.expression_inner.as_ref()
+    else {
+      unreachable!();
+    };
+    let trace = [getter.ident.as_str()]
+      .into_iter()
+      .chain(
+        getter
+          .indexes
+          .iter()
+          .map(|key| static_key(key).unwrap_or("*")),
+      )
+      .collect::<Vec<_>>();
+    let format = self.optional_format(operand).unwrap();
+    gen_empty(
+      output,
+      self.component,
+      self.state,
+      self.scope,
+      &trace,
+      format,
+    )
+  }
+}
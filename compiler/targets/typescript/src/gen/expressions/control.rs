@@ -0,0 +1,135 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_recipes::ir::{Control, ControlInner};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::{gen_expressions, Scope};
+
+pub fn gen_control<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  control: &Control,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match control.control_inner.as_ref().unwrap() {
+    ControlInner::If(control) => {
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &[],
+        true,
+        control.condition.as_ref().unwrap(),
+      )?;
+      write!(output, " ? ")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &[],
+        true,
+        control.then.as_ref().unwrap(),
+      )?;
+      write!(output, " : ")?;
+      if let Some(r#else) = &control.r#else {
+        gen_expressions(output, component, state, scope, &[], true, r#else)?;
+      } else {
+        write!(output, "undefined")?;
+      }
+    }
+    ControlInner::AnonymousFunction(control) => {
+      gen_args(output, &control.args)?;
+      write!(output, " => ")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &[],
+        true,
+        control.body.as_ref().unwrap(),
+      )?;
+    }
+    ControlInner::NamedFunction(control) => {
+      write!(output, "function {}", control.name)?;
+      gen_args(output, &control.args)?;
+      write!(output, " {{ return ")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &[],
+        false,
+        control.body.as_ref().unwrap(),
+      )?;
+      write!(output, "; }}")?;
+    }
+    ControlInner::FunctionCall(control) => {
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &[],
+        true,
+        control.function.as_ref().unwrap(),
+      )?;
+      write!(output, "(")?;
+      let mut is_first = true;
+      for arg in &control.args {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        gen_expressions(output, component, state, scope, &[], false, arg)?;
+      }
+      write!(output, ")")?;
+    }
+  }
+  Ok(())
+}
+
+fn gen_args(output: &mut CodeWriter, args: &[String]) -> fmt::Result {
+  write!(output, "(")?;
+  let mut is_first = true;
+  for arg in args {
+    if !is_first {
+      write!(output, ", ")?;
+    }
+    is_first = false;
+    write!(output, "{arg}")?;
+  }
+  write!(output, ")")
+}
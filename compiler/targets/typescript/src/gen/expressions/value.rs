@@ -0,0 +1,111 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{Value, ValueInner};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::{gen_expressions, Scope};
+
+pub use self::getter::{creates_containers, gen_getter, is_optional};
+pub use self::text::gen_rich_text;
+
+mod getter;
+mod text;
+
+pub fn gen_value<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  trace: &[&str],
+  value: &Value,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match value.value_inner.as_ref().unwrap() {
+    ValueInner::Text(value) => {
+      gen_rich_text(output, component, state, scope, trace, value)?
+    }
+    ValueInner::Quantity(value) => write!(output, "{value}")?,
+    ValueInner::Boolean(value) => {
+      if *value {
+        write!(output, "true")?;
+      } else {
+        write!(output, "false")?;
+      }
+    }
+    ValueInner::Getter(value) => {
+      gen_getter(output, component, state, scope, false, value)?
+    }
+    ValueInner::List(values) => {
+      write!(output, "[")?;
+      let mut is_first = true;
+      let trace_current = &[trace, &["*"]].concat();
+      for value in &values.values {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        gen_expressions(
+          output,
+          component,
+          state,
+          scope,
+          trace_current,
+          false,
+          value,
+        )?;
+      }
+      write!(output, "]")?;
+    }
+    ValueInner::Object(value) => {
+      if value.values.is_empty() {
+        write!(output, "{{}}")?;
+        return Ok(());
+      }
+      write!(output, "{{ ")?;
+      let mut is_first = true;
+      for (key, value) in &value.values {
+        if !is_first {
+          write!(output, ", ")?;
+        }
+        is_first = false;
+        write!(output, "{key}: ")?;
+        gen_expressions(
+          output,
+          component,
+          state,
+          scope,
+          &[trace, &[key]].concat(),
+          false,
+          value,
+        )?;
+      }
+      write!(output, " }}")?;
+    }
+    ValueInner::Undefined(_) => write!(output, "undefined")?,
+  }
+  Ok(())
+}
@@ -0,0 +1,170 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  fmt::{self, Write},
+  vec::Vec,
+};
+use dropin_compiler_recipes::ir::{Format, FormatInner, Getter};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  objects_getter::{static_key, ObjectGetterState},
+  properties_resolver::PropertiesResolverState,
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::{expressions::gen_expressions, gen_empty, Sub};
+
+use super::super::Scope;
+
+// chains are optional after the values that may be missing, assignments
+// create them instead
+pub fn gen_getter<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  is_target: bool,
+  value: &Getter,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let start = output.len();
+  if <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &value.ident)
+  {
+    write!(output, "{}", scope.variables)?;
+  } else {
+    write!(output, "{}", scope.properties)?;
+  }
+  write!(output, "{}", value.ident)?;
+  let objects = <S as Stated<ObjectGetterState>>::state(state);
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let mut indexed = Getter {
+    ident: value.ident.clone(),
+    indexes: Vec::with_capacity(value.indexes.len()),
+  };
+  // required keys are given or initialized
+  let mut is_optional = formats.default_of(component, &value.ident).is_none();
+  let mut trace_current = Vec::with_capacity(value.indexes.len() + 1);
+  trace_current.push(value.ident.as_str());
+  for key in &value.indexes {
+    let format = formats.format_of(component, &indexed);
+    if is_target && is_optional {
+      output.insert(start, '(');
+      write!(output, " ??= ")?;
+      match format {
+        Some(format) if !is_any(format) => {
+          gen_empty(output, component, state, scope, &trace_current, format)?;
+        }
+        _ => write!(output, "{{}}")?,
+      }
+      write!(output, ")")?;
+    }
+    let chain = if is_optional && !is_target { "?." } else { "" };
+    let trace_key = static_key(key).unwrap_or("*");
+    let is_object = objects
+      .get(component)
+      .is_some_and(|objects| objects.contains_key(&trace_current));
+    if is_object && trace_key != "*" {
+      let dot = if chain.is_empty() { "." } else { chain };
+      write!(output, "{dot}{trace_key}")?;
+    } else {
+      if is_object && scope.is_typed {
+        // interfaces have no index signature
+        output.insert(start, '(');
+        write!(output, " as Record<string, any>)")?;
+      }
+      write!(output, "{chain}[")?;
+      gen_expressions(
+        output,
+        component,
+        state,
+        scope,
+        &trace_current,
+        false,
+        key,
+      )?;
+      write!(output, "]")?;
+    }
+    is_optional = may_be_missing(format, trace_key);
+    indexed.indexes.push(key.clone());
+    trace_current.push(trace_key);
+  }
+  Ok(())
+}
+
+// whether assigning to `getter` creates some of the values it goes through
+pub fn creates_containers<'a, S>(
+  component: &str,
+  state: &S,
+  getter: &Getter,
+) -> bool
+where
+  S: Sub<'a>,
+{
+  let Some((_, receiver)) = getter.indexes.split_last() else {
+    return false;
+  };
+  let receiver = Getter {
+    ident: getter.ident.clone(),
+    indexes: receiver.to_vec(),
+  };
+  is_optional(component, state, &receiver)
+}
+
+// whether reading `getter` may give `undefined`
+pub fn is_optional<'a, S>(component: &str, state: &S, getter: &Getter) -> bool
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let mut indexed = Getter {
+    ident: getter.ident.clone(),
+    indexes: Vec::with_capacity(getter.indexes.len()),
+  };
+  let mut is_optional = formats.default_of(component, &getter.ident).is_none();
+  for key in &getter.indexes {
+    if is_optional {
+      return true;
+    }
+    let format = formats.format_of(component, &indexed);
+    is_optional = may_be_missing(format, static_key(key).unwrap_or("*"));
+    indexed.indexes.push(key.clone());
+  }
+  is_optional
+}
+
+// whether indexing a value of `format` with `key` may give nothing
+fn may_be_missing(format: Option<&Format>, key: &str) -> bool {
+  match format.and_then(|format| format.format_inner.as_ref()) {
+    Some(FormatInner::Object(object)) => {
+      key == "*" || !object.required.contains_key(key)
+    }
+    _ => true,
+  }
+}
+
+fn is_any(format: &Format) -> bool {
+  matches!(format.format_inner, Some(FormatInner::Any(_)))
+}
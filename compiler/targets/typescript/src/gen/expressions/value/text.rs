@@ -0,0 +1,65 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{RichText, RichTextInner};
+use dropin_target_gen::CodeWriter;
+
+use crate::gen::Sub;
+
+use super::super::{gen_expressions, Scope};
+
+pub fn gen_rich_text<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  trace: &[&str],
+  value: &RichText,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  write!(output, "`")?;
+  for part in &value.parts {
+    match part.rich_text_inner.as_ref().unwrap() {
+      RichTextInner::Static(part) => {
+        let mut chars = part.chars().peekable();
+        while let Some(c) = chars.next() {
+          match c {
+            '\\' | '`' => write!(output, "\\{c}")?,
+            '$' if chars.peek() == Some(&'{') => write!(output, "\\$")?,
+            _ => output.write_char(c)?,
+          }
+        }
+      }
+      RichTextInner::Dynamic(expression) => {
+        write!(output, "${{")?;
+        gen_expressions(
+          output, component, state, scope, trace, false, expression,
+        )?;
+        write!(output, "}}")?;
+      }
+    }
+  }
+  write!(output, "`")?;
+  Ok(())
+}
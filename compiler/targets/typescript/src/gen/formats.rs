@@ -0,0 +1,59 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{Format, FormatInner};
+use dropin_target_analysis::objects_getter::write_class_name;
+
+pub fn gen_format(
+  output: &mut impl Write,
+  trace: &[&str],
+  format: &Format,
+) -> fmt::Result {
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Any(_) => write!(output, "any")?,
+    FormatInner::Boolean(_) => write!(output, "boolean")?,
+    FormatInner::Choices(_) => write!(output, "string")?,
+    FormatInner::Date(_) => write!(output, "Date")?,
+    FormatInner::Index(sub) => {
+      write!(output, "Record<string, ")?;
+      gen_format(
+        output,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+      )?;
+      write!(output, ">")?;
+    }
+    FormatInner::List(sub) => {
+      write!(output, "Array<")?;
+      gen_format(
+        output,
+        &[trace, &["*"]].concat(),
+        sub.format.as_ref().unwrap(),
+      )?;
+      write!(output, ">")?;
+    }
+    FormatInner::Object(_) => write_class_name(output, trace)?,
+    FormatInner::Quantity(_) => write!(output, "number")?,
+    FormatInner::Text(_) => write!(output, "string")?,
+  }
+  Ok(())
+}
@@ -0,0 +1,67 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{collections::BTreeMap, fmt};
+use core::fmt::Write;
+use dropin_target_analysis::{
+  objects_getter::{write_class_name, ObjectGetterState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{formats::gen_format, Sub};
+
+// an interface for each object format of the component
+pub fn gen_interfaces<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for (trace, format) in <S as Stated<ObjectGetterState>>::state(state)
+    .get(component)
+    .unwrap_or(&BTreeMap::new())
+  {
+    output.line()?;
+    write!(output, "export interface ")?;
+    write_class_name(output, trace)?;
+    output.open(" {")?;
+    for key_format in &format.keys {
+      write!(output, "{}", key_format.key)?;
+      if !format.required.contains_key(&key_format.key) {
+        write!(output, "?")?;
+      }
+      write!(output, ": ")?;
+      gen_format(
+        output,
+        &[trace.as_slice(), &[&key_format.key]].concat(),
+        key_format.format.as_ref().unwrap(),
+      )?;
+      write!(output, ";")?;
+      output.line()?;
+    }
+    output.close("}")?;
+    output.line()?;
+  }
+  Ok(())
+}
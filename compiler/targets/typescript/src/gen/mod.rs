@@ -0,0 +1,361 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  format,
+  string::String,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, Expression, ExpressionInner, Format,
+  FormatInner, Keys, Model, Value, ValueInner,
+};
+use dropin_target_analysis::{
+  dependencies::DependenciesState,
+  formats::FormatsState,
+  objects_getter::ObjectGetterState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use crate::EXTENSION;
+
+pub use self::{
+  expressions::{
+    creates_containers, gen_expressions, gen_getter, gen_rich_text, Scope,
+  },
+  formats::gen_format,
+  interfaces::gen_interfaces,
+};
+
+use self::zones::gen_zone;
+
+mod expressions;
+mod formats;
+mod interfaces;
+mod zones;
+
+pub trait Sub<'a>:
+  Stated<ObjectGetterState<'a>>
+  + Stated<UpdatedAndListenersState<'a>>
+  + Stated<PropertiesResolverState<'a>>
+  + Stated<FormatsState<'a>>
+  + Stated<DependenciesState<'a>>
+{
+}
+
+impl<'a, S> Sub<'a> for S where
+  S: Stated<ObjectGetterState<'a>>
+    + Stated<UpdatedAndListenersState<'a>>
+    + Stated<PropertiesResolverState<'a>>
+    + Stated<FormatsState<'a>>
+    + Stated<DependenciesState<'a>>
+{
+}
+
+const SCOPE: Scope = Scope {
+  variables: "this.",
+  properties: "this.properties.",
+  is_typed: true,
+};
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let term = component.term.as_str();
+
+  let mut externs = BTreeSet::new();
+  for child in &component.zone.as_ref().unwrap().blocks {
+    if let ComponentChildInner::Extern(r#extern) =
+      child.component_child_inner.as_ref().unwrap()
+    {
+      externs.insert(r#extern.id.as_str());
+    }
+  }
+  for r#extern in &externs {
+    let extern_term = to_upper_camelcase(r#extern);
+    write!(output, "import {{ {extern_term} }} from \"./{extern}\";")?;
+    output.line()?;
+  }
+  if !externs.is_empty() {
+    output.line()?;
+  }
+
+  // properties
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+  output.open(&format!("export interface {term}Properties {{"))?;
+  if let Some(properties) = &component.properties {
+    gen_fields(output, properties, true)?;
+  }
+  for notifier in &notifiers {
+    if !notifier.is_external {
+      continue;
+    }
+    write!(output, "update")?;
+    write_getter_name(output, &notifier.getter)?;
+    write!(output, ": (value: ")?;
+    gen_format(
+      output,
+      &[],
+      formats.format_of(id, &notifier.getter).unwrap(),
+    )?;
+    write!(output, ") => void;")?;
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()?;
+  output.line()?;
+
+  // class
+  output.open(&format!("export class {term} {{"))?;
+  write!(
+    output,
+    "readonly root: HTMLElement = document.createElement(\"div\");"
+  )?;
+  output.line()?;
+  write!(output, "readonly properties: {term}Properties;")?;
+  output.line()?;
+  if let Some(variables) = &component.variables {
+    gen_fields(output, variables, false)?;
+  }
+  write!(output, "private readonly updates: Array<() => void> = [];")?;
+  output.line()?;
+  output.line()?;
+
+  output.open(&format!("constructor(properties: {term}Properties) {{"))?;
+  write!(output, "this.properties = {{ ")?;
+  if let Some(properties) = &component.properties {
+    gen_defaults(output, state, id, &properties.required)?;
+  }
+  write!(output, "...properties }};")?;
+  output.line()?;
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      let Some(default) = variables.required.get(key) else {
+        continue;
+      };
+      write!(output, "this.{key} = ")?;
+      gen_initial(
+        output,
+        id,
+        state,
+        &SCOPE,
+        &[key],
+        key_format.format.as_ref().unwrap(),
+        default,
+      )?;
+      write!(output, ";")?;
+      output.line()?;
+    }
+  }
+  write!(output, "this.root.style.display = \"flex\";")?;
+  output.line()?;
+  gen_zone(output, id, state, component.zone.as_ref().unwrap())?;
+  write!(output, "this.update();")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+  output.line()?;
+  output.open("update(): void {")?;
+  output.open("for (const update of this.updates) {")?;
+  write!(output, "update();")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+
+  gen_interfaces(output, id, state)?;
+  Ok(())
+}
+
+// `name: type;` for each key, properties with a default value are optional
+pub fn gen_fields(
+  output: &mut CodeWriter,
+  keys: &Keys,
+  is_property: bool,
+) -> fmt::Result {
+  for key_format in &keys.keys {
+    write!(output, "{}", key_format.key)?;
+    match keys.required.get(&key_format.key) {
+      Some(_) => {
+        if is_property {
+          write!(output, "?")?;
+        }
+      }
+      None => write!(output, "?")?,
+    }
+    write!(output, ": ")?;
+    gen_format(
+      output,
+      &[key_format.key.as_str()],
+      key_format.format.as_ref().unwrap(),
+    )?;
+    write!(output, ";")?;
+    output.line()?;
+  }
+  Ok(())
+}
+
+// default values of `required`, undefined ones are left to the caller
+fn gen_defaults<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &str,
+  required: &BTreeMap<String, Expression>,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for (key, default) in required {
+    if is_undefined(default) {
+      continue;
+    }
+    write!(output, "{key}: ")?;
+    gen_expressions(output, component, state, &SCOPE, &[key], false, default)?;
+    write!(output, ", ")?;
+  }
+  Ok(())
+}
+
+// the default of a required key, the empty value of its format when the recipe
+// gives none
+pub fn gen_initial<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  trace: &[&str],
+  format: &Format,
+  default: &Expression,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if is_undefined(default) {
+    gen_empty(output, component, state, scope, trace, format)
+  } else {
+    gen_expressions(output, component, state, scope, trace, false, default)
+  }
+}
+
+// objects are built with their required keys
+pub fn gen_empty<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  scope: &Scope,
+  trace: &[&str],
+  format: &Format,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  match format.format_inner.as_ref().unwrap() {
+    FormatInner::Any(_) => write!(output, "undefined")?,
+    FormatInner::Boolean(_) => write!(output, "false")?,
+    FormatInner::Choices(_) | FormatInner::Text(_) => write!(output, "\"\"")?,
+    FormatInner::Date(_) => write!(output, "new Date(0)")?,
+    FormatInner::Index(_) => write!(output, "{{}}")?,
+    FormatInner::List(_) => write!(output, "[]")?,
+    FormatInner::Object(object) => {
+      write!(output, "{{")?;
+      let mut is_empty = true;
+      for key_format in &object.keys {
+        let key = key_format.key.as_str();
+        let Some(default) = object.required.get(key) else {
+          continue;
+        };
+        write!(output, "{}{key}: ", if is_empty { " " } else { ", " })?;
+        is_empty = false;
+        gen_initial(
+          output,
+          component,
+          state,
+          scope,
+          &[trace, &[key]].concat(),
+          key_format.format.as_ref().unwrap(),
+          default,
+        )?;
+      }
+      write!(output, "{}}}", if is_empty { "" } else { " " })?;
+    }
+    FormatInner::Quantity(_) => write!(output, "0")?,
+  }
+  Ok(())
+}
+
+pub fn is_undefined(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner.as_ref().unwrap(),
+    ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::Undefined(_)),
+    })
+  )
+}
@@ -0,0 +1,206 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, Getter,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+
+use super::{
+  gen_expressions, gen_format, gen_getter, gen_rich_text, Sub, SCOPE,
+};
+
+pub fn gen_zone<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  for (i, child) in zone.blocks.iter().enumerate() {
+    output.line()?;
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "const block{i} = document.createElement(\"span\");")?;
+        output.line()?;
+        output.open("this.updates.push(() => {")?;
+        write!(output, "block{i}.textContent = ")?;
+        gen_rich_text(
+          output,
+          component,
+          state,
+          &SCOPE,
+          &[],
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, ";")?;
+        output.line()?;
+        output.close("});")?;
+        output.line()?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        write!(
+          output,
+          "const block{i} = document.createElement(\"input\");"
+        )?;
+        output.line()?;
+        output.open(&alloc::format!(
+          "block{i}.addEventListener(\"input\", () => {{"
+        ))?;
+        let is_quantity = matches!(
+          formats
+            .format_of(component, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        let value = if is_quantity {
+          alloc::format!("Number(block{i}.value)")
+        } else {
+          alloc::format!("block{i}.value")
+        };
+        gen_update(output, component, state, getter, &value)?;
+        output.close("});")?;
+        output.line()?;
+        output.open("this.updates.push(() => {")?;
+        write!(output, "const value = String(")?;
+        gen_getter(output, component, state, &SCOPE, false, getter)?;
+        write!(output, " ?? \"\");")?;
+        output.line()?;
+        output.open(&alloc::format!("if (block{i}.value !== value) {{"))?;
+        write!(output, "block{i}.value = value;")?;
+        output.line()?;
+        output.close("}")?;
+        output.line()?;
+        output.close("});")?;
+        output.line()?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let properties = &r#extern.properties.as_ref().unwrap().values;
+        write!(
+          output,
+          "const block{i} = new {}(",
+          to_upper_camelcase(&r#extern.id)
+        )?;
+        output.open("{")?;
+        for (key, value) in properties {
+          write!(output, "{key}: ")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &SCOPE,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          output.item()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "update")?;
+          write_getter_name(output, updated_by)?;
+          write!(output, ": (value: ")?;
+          gen_format(
+            output,
+            &[],
+            formats.format_of(component, &notifier.getter).unwrap(),
+          )?;
+          output.open(") => {")?;
+          gen_update(output, component, state, &notifier.getter, "value")?;
+          output.close("}")?;
+          output.item()?;
+        }
+        output.close("});")?;
+        output.line()?;
+        if !properties.is_empty() {
+          output.open("this.updates.push(() => {")?;
+          for (key, value) in properties {
+            write!(output, "block{i}.properties.{key} = ")?;
+            gen_expressions(
+              output,
+              component,
+              state,
+              &SCOPE,
+              &[key.as_str()],
+              false,
+              value,
+            )?;
+            write!(output, ";")?;
+            output.line()?;
+          }
+          write!(output, "block{i}.update();")?;
+          output.line()?;
+          output.close("});")?;
+          output.line()?;
+        }
+        write!(output, "this.root.append(block{i}.root);")?;
+        output.line()?;
+        continue;
+      }
+    }
+    write!(output, "this.root.append(block{i});")?;
+    output.line()?;
+  }
+  Ok(())
+}
+
+// variables are set in place, properties are given back to their owner
+fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+  {
+    gen_getter(output, component, state, &SCOPE, true, getter)?;
+    write!(output, " = {value};")?;
+    output.line()?;
+    write!(output, "this.update();")?;
+  } else {
+    write!(output, "{}update", SCOPE.properties)?;
+    write_getter_name(output, getter)?;
+    write!(output, "({value});")?;
+  }
+  output.line()
+}
@@ -0,0 +1,64 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use dropin_target_tests::{generate, HOME, OPTIONAL, USER};
+use dropin_target_typescript::Typescript;
+
+#[test]
+fn euclidean_modulo() {
+  let code = generate(&Typescript, HOME);
+  assert!(code.contains("((this.count % 3) + 3) % 3"), "{code}");
+  assert!(
+    code.contains(
+      "((a, b) => ((a % b) + Math.abs(b)) % Math.abs(b))\
+      (this.count, this.step)"
+    ),
+    "{code}"
+  );
+}
+
+#[test]
+fn nested_keys() {
+  let code = generate(&Typescript, USER);
+  assert!(
+    code.contains("this.user = { id: 0, first: `anon` };"),
+    "{code}"
+  );
+  assert!(code.contains("${this.user.friends?.[0]?.name}"), "{code}");
+  assert!(
+    code.contains("((this.user.friends ??= [])[0] ??= {}).name = block1.value"),
+    "{code}"
+  );
+}
+
+#[test]
+fn optional_operands() {
+  let code = generate(&Typescript, OPTIONAL);
+  assert!(
+    code.contains("(this.user?.tags ?? []).includes(this.name)"),
+    "{code}"
+  );
+  assert!(
+    code.contains("(this.user?.scores?.[this.name] ?? 0) > 1"),
+    "{code}"
+  );
+  assert!(code.contains("(this.user?.age ?? 0) <= 3"), "{code}");
+}
@@ -0,0 +1,246 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  string::String,
+};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, Expression, ExpressionInner, Getter, Model,
+  Value, ValueInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_expressions, gen_fields, gen_format, gen_initial, gen_interfaces,
+  is_undefined, Scope, Sub,
+};
+
+use crate::EXTENSION;
+
+use self::template::gen_template;
+
+mod template;
+
+// refs are unwrapped in templates, properties are read from the props
+const SCOPE: Scope = Scope {
+  variables: "",
+  properties: "props.",
+  is_typed: true,
+};
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let term = component.term.as_str();
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+
+  // types, exports aren't allowed in the setup script
+  write!(output, "<script lang=\"ts\">")?;
+  output.line()?;
+  output.open(&alloc::format!("export interface {term}Props {{"))?;
+  if let Some(properties) = &component.properties {
+    gen_fields(output, properties, true)?;
+  }
+  output.close("}")?;
+  output.line()?;
+  gen_interfaces(output, id, state)?;
+  write!(output, "</script>")?;
+  output.line()?;
+  output.line()?;
+
+  write!(output, "<script setup lang=\"ts\">")?;
+  output.line()?;
+  let mut externs = BTreeSet::new();
+  for child in &component.zone.as_ref().unwrap().blocks {
+    if let ComponentChildInner::Extern(r#extern) =
+      child.component_child_inner.as_ref().unwrap()
+    {
+      externs.insert(r#extern.id.as_str());
+    }
+  }
+  if component.variables.is_some() {
+    write!(output, "import {{ ref }} from \"vue\";")?;
+    output.line()?;
+  }
+  for r#extern in &externs {
+    let extern_term = to_upper_camelcase(r#extern);
+    write!(
+      output,
+      "import {extern_term} from \"./{extern}{EXTENSION}\";"
+    )?;
+    output.line()?;
+  }
+  if component.variables.is_some() || !externs.is_empty() {
+    output.line()?;
+  }
+
+  // props
+  write!(output, "const props = ")?;
+  let defaults = component
+    .properties
+    .as_ref()
+    .map(|properties| {
+      properties
+        .required
+        .iter()
+        .filter(|(_, default)| !is_undefined(default))
+        .collect::<alloc::vec::Vec<_>>()
+    })
+    .unwrap_or_default();
+  if defaults.is_empty() {
+    write!(output, "defineProps<{term}Props>();")?;
+  } else {
+    output.open(&alloc::format!(
+      "withDefaults(defineProps<{term}Props>(), {{"
+    ))?;
+    for (key, default) in defaults {
+      write!(output, "{key}: ")?;
+      // mutable defaults are built by factories
+      if is_mutable(default) {
+        write!(output, "() => ")?;
+      }
+      gen_expressions(output, id, state, &SCOPE, &[key], false, default)?;
+      output.item()?;
+    }
+    output.close("});")?;
+  }
+  output.line()?;
+
+  // emits
+  let emits = notifiers
+    .iter()
+    .filter(|notifier| notifier.is_external)
+    .collect::<alloc::vec::Vec<_>>();
+  if !emits.is_empty() {
+    output.open("const emit = defineEmits<{")?;
+    for notifier in emits {
+      write!(output, "\"")?;
+      write_event_name(output, &notifier.getter)?;
+      write!(output, "\": [value: ")?;
+      gen_format(
+        output,
+        &[],
+        formats.format_of(id, &notifier.getter).unwrap(),
+      )?;
+      write!(output, "];")?;
+      output.line()?;
+    }
+    output.close("}>();")?;
+    output.line()?;
+  }
+
+  // variables
+  if let Some(variables) = &component.variables {
+    for key_format in &variables.keys {
+      let key = key_format.key.as_str();
+      write!(output, "const {key} = ref<")?;
+      gen_format(output, &[key], key_format.format.as_ref().unwrap())?;
+      write!(output, ">(")?;
+      if let Some(default) = variables.required.get(key) {
+        gen_initial(
+          output,
+          id,
+          state,
+          &SCOPE,
+          &[key],
+          key_format.format.as_ref().unwrap(),
+          default,
+        )?;
+      }
+      write!(output, ");")?;
+      output.line()?;
+    }
+  }
+  write!(output, "</script>")?;
+  output.line()?;
+  output.line()?;
+
+  gen_template(output, id, state, component.zone.as_ref().unwrap())
+}
+
+// `update:` events, named like the v-model of the updated getter
+fn write_event_name(output: &mut CodeWriter, getter: &Getter) -> fmt::Result {
+  let mut name = String::new();
+  write_getter_name(&mut name, getter)?;
+  let mut chars = name.chars();
+  write!(output, "update:")?;
+  if let Some(first) = chars.next() {
+    output.write_char(first.to_ascii_lowercase())?;
+  }
+  write!(output, "{}", chars.as_str())
+}
+
+fn is_mutable(expression: &Expression) -> bool {
+  matches!(
+    expression.expression_inner.as_ref().unwrap(),
+    ExpressionInner::Value(Value {
+      value_inner: Some(ValueInner::List(_) | ValueInner::Object(_)),
+    })
+  )
+}
@@ -0,0 +1,221 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_common::to_upper_camelcase;
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, Getter, RichText,
+  RichTextInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, properties_resolver::PropertiesResolverState,
+  updated_listeners::UpdatedAndListenersState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  creates_containers, gen_expressions, gen_format, gen_getter, Sub,
+};
+
+use super::{write_event_name, SCOPE};
+
+pub fn gen_template<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("<template>")?;
+  output.open("<div style=\"display: flex\">")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "<span>")?;
+        gen_interpolations(
+          output,
+          component,
+          state,
+          text.content.as_ref().unwrap(),
+        )?;
+        write!(output, "</span>")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        write!(output, "<input ")?;
+        let is_variable = <S as Stated<PropertiesResolverState>>::state(state)
+          .is_variable(component, &getter.ident);
+        let is_quantity = matches!(
+          formats
+            .format_of(component, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        if is_variable && creates_containers(component, state, getter) {
+          // bindings are read while rendering, the missing values are only
+          // created on input
+          write!(output, ":value=\"")?;
+          gen_getter(output, component, state, &SCOPE, false, getter)?;
+          write!(output, "\" @input=\"")?;
+          gen_getter(output, component, state, &SCOPE, true, getter)?;
+          let value = "($event.target as HTMLInputElement).value";
+          if is_quantity {
+            write!(output, " = Number({value})\"")?;
+          } else {
+            write!(output, " = {value}\"")?;
+          }
+        } else if is_variable {
+          if is_quantity {
+            write!(output, "v-model.number=\"")?;
+          } else {
+            write!(output, "v-model=\"")?;
+          }
+          gen_getter(output, component, state, &SCOPE, true, getter)?;
+          write!(output, "\"")?;
+        } else {
+          // properties can't be bound, their owner updates them
+          write!(output, ":value=\"")?;
+          gen_getter(output, component, state, &SCOPE, false, getter)?;
+          write!(output, "\" @input=\"")?;
+          gen_emit(
+            output,
+            getter,
+            "($event.target as HTMLInputElement).value",
+          )?;
+          write!(output, "\"")?;
+        }
+        write!(output, " />")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let properties = &r#extern.properties.as_ref().unwrap().values;
+        output
+          .open(&alloc::format!("<{}", to_upper_camelcase(&r#extern.id)))?;
+        for (key, value) in properties {
+          write!(output, ":{key}=\"")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &SCOPE,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          write!(output, "\"")?;
+          output.line()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "@")?;
+          write_event_name(output, updated_by)?;
+          write!(output, "=\"(value: ")?;
+          gen_format(
+            output,
+            &[],
+            formats.format_of(component, &notifier.getter).unwrap(),
+          )?;
+          write!(output, ") => ")?;
+          if <S as Stated<PropertiesResolverState>>::state(state)
+            .is_variable(component, &notifier.getter.ident)
+          {
+            write!(output, "(")?;
+            gen_getter(
+              output,
+              component,
+              state,
+              &SCOPE,
+              true,
+              &notifier.getter,
+            )?;
+            write!(output, " = value)")?;
+          } else {
+            gen_emit(output, &notifier.getter, "value")?;
+          }
+          write!(output, "\"")?;
+          output.line()?;
+        }
+        output.close("/>")?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("</div>")?;
+  output.line()?;
+  output.close("</template>")
+}
+
+fn gen_emit(
+  output: &mut CodeWriter,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result {
+  write!(output, "emit('")?;
+  write_event_name(output, getter)?;
+  write!(output, "', {value})")
+}
+
+// static parts are html text, dynamic parts are mustaches
+fn gen_interpolations<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  text: &RichText,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for part in &text.parts {
+    match part.rich_text_inner.as_ref().unwrap() {
+      RichTextInner::Static(part) => {
+        let mut chars = part.chars().peekable();
+        while let Some(c) = chars.next() {
+          match c {
+            '<' => write!(output, "&lt;")?,
+            '&' => write!(output, "&amp;")?,
+            '{' if chars.peek() == Some(&'{') => write!(output, "&#123;")?,
+            _ => output.write_char(c)?,
+          }
+        }
+      }
+      RichTextInner::Dynamic(expression) => {
+        write!(output, "{{{{ ")?;
+        gen_expressions(
+          output,
+          component,
+          state,
+          &SCOPE,
+          &[],
+          false,
+          expression,
+        )?;
+        write!(output, " }}}}")?;
+      }
+    }
+  }
+  Ok(())
+}
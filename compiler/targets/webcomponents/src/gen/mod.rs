@@ -0,0 +1,323 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+  format,
+  string::String,
+  vec::Vec,
+};
+use dropin_compiler_common::to_kebab_case;
+use dropin_compiler_recipes::ir::{
+  Component, ComponentChildInner, FormatInner, Getter, KeyFormat, Keys, Model,
+};
+use dropin_target_analysis::{
+  formats::FormatsState,
+  properties_resolver::PropertiesResolverState,
+  updated_listeners::{write_getter_name, UpdatedAndListenersState},
+  Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_format, gen_getter, gen_initial, gen_interfaces, is_undefined, Scope, Sub,
+};
+
+use crate::EXTENSION;
+
+use self::templates::gen_template;
+
+mod templates;
+
+// properties and variables are both fields of the element
+const SCOPE: Scope = Scope {
+  variables: "this.",
+  properties: "this.",
+  is_typed: true,
+};
+
+#[derive(Debug)]
+pub struct Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  sub: &'a S,
+}
+
+impl<'a, S> Gen<'a, S>
+where
+  S: Sub<'a>,
+{
+  pub fn new(sub: &'a S) -> Self {
+    Self { sub }
+  }
+
+  pub fn gen(
+    self,
+    ir: &'a Model,
+  ) -> Result<BTreeMap<String, String>, fmt::Error> {
+    let mut files = BTreeMap::new();
+    for component in &ir.components {
+      let mut file = CodeWriter::new(false, None);
+      gen_component(&mut file, self.sub, component)?;
+      let mut file_path =
+        String::with_capacity(component.id.len() + EXTENSION.len());
+      write!(&mut file_path, "{}{EXTENSION}", component.id)?;
+      files.insert(file_path, file.into_code());
+    }
+    Ok(files)
+  }
+}
+
+fn gen_component<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &Component,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let id = component.id.as_str();
+  let term = component.term.as_str();
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers =
+    <S as Stated<UpdatedAndListenersState>>::state(state).get_notifiers(id);
+
+  write!(output, "import {{ LitElement, css, html }} from \"lit\";")?;
+  output.line()?;
+  let mut decorators = Vec::from(["customElement"]);
+  if component.properties.is_some() {
+    decorators.push("property");
+  }
+  if component.variables.is_some() {
+    decorators.push("state");
+  }
+  write!(
+    output,
+    "import {{ {} }} from \"lit/decorators.js\";",
+    decorators.join(", ")
+  )?;
+  output.line()?;
+
+  // externs register their own elements
+  let mut externs = BTreeSet::new();
+  for child in &component.zone.as_ref().unwrap().blocks {
+    if let ComponentChildInner::Extern(r#extern) =
+      child.component_child_inner.as_ref().unwrap()
+    {
+      externs.insert(r#extern.id.as_str());
+    }
+  }
+  if !externs.is_empty() {
+    output.line()?;
+  }
+  for r#extern in &externs {
+    write!(output, "import \"./{extern}\";")?;
+    output.line()?;
+  }
+  gen_interfaces(output, id, state)?;
+  output.line()?;
+
+  let tag = tag_name(id);
+  write!(output, "@customElement(\"{tag}\")")?;
+  output.line()?;
+  output.open(&format!("export class {term} extends LitElement {{"))?;
+  output.open("static styles = css`")?;
+  output.open(":host {")?;
+  write!(output, "display: flex;")?;
+  output.line()?;
+  write!(output, "align-items: center;")?;
+  output.line()?;
+  write!(output, "gap: 8px;")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+  output.close("`;")?;
+  output.line()?;
+  output.line()?;
+
+  if let Some(properties) = &component.properties {
+    gen_fields(output, state, id, properties, true)?;
+  }
+  if let Some(variables) = &component.variables {
+    gen_fields(output, state, id, variables, false)?;
+  }
+  if component.properties.is_some() || component.variables.is_some() {
+    output.line()?;
+  }
+
+  output.open("render() {")?;
+  gen_template(output, id, state, component.zone.as_ref().unwrap())?;
+  output.close("}")?;
+  output.line()?;
+
+  // external properties are updated in place, then given back to the owner
+  for notifier in &notifiers {
+    if !notifier.is_external {
+      continue;
+    }
+    let format = formats.format_of(id, &notifier.getter).unwrap();
+    output.line()?;
+    write!(output, "private update")?;
+    write_getter_name(output, &notifier.getter)?;
+    write!(output, "(value: ")?;
+    gen_format(output, &[], format)?;
+    output.open(") {")?;
+    gen_set(output, id, state, &notifier.getter, "value")?;
+    output.open("this.dispatchEvent(")?;
+    write!(output, "new CustomEvent<")?;
+    gen_format(output, &[], format)?;
+    write!(output, ">(\"")?;
+    write_event_name(output, &notifier.getter)?;
+    output.open("\", {")?;
+    write!(output, "detail: value")?;
+    output.item()?;
+    write!(output, "bubbles: true")?;
+    output.item()?;
+    write!(output, "composed: true")?;
+    output.item()?;
+    output.close("}),")?;
+    output.line()?;
+    output.close(");")?;
+    output.line()?;
+    output.close("}")?;
+    output.line()?;
+  }
+  output.close("}")?;
+  output.line()?;
+  output.line()?;
+
+  output.open("declare global {")?;
+  output.open("interface HTMLElementTagNameMap {")?;
+  write!(output, "\"{tag}\": {term};")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()?;
+  output.close("}")?;
+  output.line()
+}
+
+// decorated fields, primitive properties are also reflected from attributes
+fn gen_fields<'a, S>(
+  output: &mut CodeWriter,
+  state: &S,
+  component: &str,
+  keys: &Keys,
+  is_property: bool,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for KeyFormat { key, format } in &keys.keys {
+    let format = format.as_ref().unwrap();
+    if is_property {
+      match format.format_inner.as_ref().unwrap() {
+        FormatInner::Text(_) => write!(output, "@property() ")?,
+        FormatInner::Quantity(_) => {
+          write!(output, "@property({{ type: Number }}) ")?
+        }
+        FormatInner::Boolean(_) => {
+          write!(output, "@property({{ type: Boolean }}) ")?
+        }
+        _ => write!(output, "@property({{ attribute: false }}) ")?,
+      }
+    } else {
+      write!(output, "@state() private ")?;
+    }
+    write!(output, "{key}")?;
+    // variables start empty when the recipe gives no default
+    let default = keys
+      .required
+      .get(key)
+      .filter(|default| !is_property || !is_undefined(default));
+    match default {
+      Some(_) => {}
+      // required properties are given by the owner, like in typescript
+      None if keys.required.contains_key(key) => write!(output, "!")?,
+      None => write!(output, "?")?,
+    }
+    write!(output, ": ")?;
+    gen_format(output, &[key], format)?;
+    if let Some(default) = default {
+      write!(output, " = ")?;
+      gen_initial(output, component, state, &SCOPE, &[key], format, default)?;
+    }
+    write!(output, ";")?;
+    output.line()?;
+  }
+  Ok(())
+}
+
+// nested updates aren't seen by lit, they request a render
+pub(crate) fn gen_set<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  gen_getter(output, component, state, &SCOPE, true, getter)?;
+  write!(output, " = {value};")?;
+  output.line()?;
+  if !getter.indexes.is_empty() {
+    write!(output, "this.requestUpdate();")?;
+    output.line()?;
+  }
+  Ok(())
+}
+
+// variables are set in place, properties go through their update method
+pub(crate) fn gen_update<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  getter: &Getter,
+  value: &str,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  if <S as Stated<PropertiesResolverState>>::state(state)
+    .is_variable(component, &getter.ident)
+  {
+    return gen_set(output, component, state, getter, value);
+  }
+  write!(output, "this.update")?;
+  write_getter_name(output, getter)?;
+  write!(output, "({value});")?;
+  output.line()
+}
+
+pub(crate) fn tag_name(component: &str) -> String {
+  format!("dropin-{}", to_kebab_case(component))
+}
+
+pub(crate) fn write_event_name(
+  output: &mut CodeWriter,
+  getter: &Getter,
+) -> fmt::Result {
+  let mut name = String::new();
+  write_getter_name(&mut name, getter)?;
+  write!(output, "{}-change", to_kebab_case(&name))
+}
@@ -0,0 +1,171 @@
+/*     _              _ _
+ *  __| |_ _ ___ _ __( |_)_ _
+ * / _` | '_/ _ \ '_ \/| | ' \
+ * \__,_|_| \___/ .__/ |_|_||_| dropin-compiler
+ *              |_|
+ * Copyright © 2019-2024 Blue Forest
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use alloc::fmt::{self, Write};
+use dropin_compiler_recipes::ir::{
+  ComponentChildInner, ComponentZone, FormatInner, RichText, RichTextInner,
+};
+use dropin_target_analysis::{
+  formats::FormatsState, updated_listeners::UpdatedAndListenersState, Stated,
+};
+use dropin_target_gen::CodeWriter;
+use dropin_target_typescript::gen::{
+  gen_expressions, gen_format, gen_getter, Sub,
+};
+
+use super::{gen_update, tag_name, write_event_name, SCOPE};
+
+pub fn gen_template<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  zone: &ComponentZone,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  let formats = <S as Stated<FormatsState>>::state(state);
+  let notifiers = <S as Stated<UpdatedAndListenersState>>::state(state)
+    .get_notifiers(component);
+  output.open("return html`")?;
+  for child in &zone.blocks {
+    match child.component_child_inner.as_ref().unwrap() {
+      ComponentChildInner::Text(text) => {
+        write!(output, "<span>")?;
+        gen_content(output, component, state, text.content.as_ref().unwrap())?;
+        write!(output, "</span>")?;
+      }
+      ComponentChildInner::Input(input) => {
+        let getter = input.on_change.as_ref().unwrap();
+        output.open("<input")?;
+        write!(output, ".value=${{")?;
+        gen_getter(output, component, state, &SCOPE, false, getter)?;
+        write!(output, " ?? \"\"}}")?;
+        output.line()?;
+        output.open("@input=${(event: Event) => {")?;
+        let is_quantity = matches!(
+          formats
+            .format_of(component, getter)
+            .and_then(|format| format.format_inner.as_ref()),
+          Some(FormatInner::Quantity(_))
+        );
+        let value = if is_quantity {
+          "Number((event.target as HTMLInputElement).value)"
+        } else {
+          "(event.target as HTMLInputElement).value"
+        };
+        gen_update(output, component, state, getter, value)?;
+        output.close("}}")?;
+        output.line()?;
+        output.close("/>")?;
+      }
+      ComponentChildInner::Extern(r#extern) => {
+        let tag = tag_name(&r#extern.id);
+        let properties = &r#extern.properties.as_ref().unwrap().values;
+        output.open(&alloc::format!("<{tag}"))?;
+        for (key, value) in properties {
+          write!(output, ".{key}=${{")?;
+          gen_expressions(
+            output,
+            component,
+            state,
+            &SCOPE,
+            &[key.as_str()],
+            false,
+            value,
+          )?;
+          write!(output, "}}")?;
+          output.line()?;
+        }
+        for notifier in &notifiers {
+          let Some(updated_by) = notifier.updated_by.get(r#extern.id.as_str())
+          else {
+            continue;
+          };
+          write!(output, "@")?;
+          write_event_name(output, updated_by)?;
+          write!(output, "=${{(event: CustomEvent<")?;
+          gen_format(
+            output,
+            &[],
+            formats.format_of(component, &notifier.getter).unwrap(),
+          )?;
+          output.open(">) => {")?;
+          gen_update(
+            output,
+            component,
+            state,
+            &notifier.getter,
+            "event.detail",
+          )?;
+          output.close("}}")?;
+          output.line()?;
+        }
+        output.close(&alloc::format!("></{tag}>"))?;
+      }
+    }
+    output.line()?;
+  }
+  output.close("`;")?;
+  output.line()
+}
+
+// static parts are html text, dynamic parts are escaped by lit
+fn gen_content<'a, S>(
+  output: &mut CodeWriter,
+  component: &str,
+  state: &S,
+  text: &RichText,
+) -> fmt::Result
+where
+  S: Sub<'a>,
+{
+  for part in &text.parts {
+    match part.rich_text_inner.as_ref().unwrap() {
+      RichTextInner::Static(part) => {
+        let mut chars = part.chars().peekable();
+        while let Some(c) = chars.next() {
+          match c {
+            '<' => write!(output, "&lt;")?,
+            '&' => write!(output, "&amp;")?,
+            '\\' | '`' => write!(output, "\\{c}")?,
+            '$' if chars.peek() == Some(&'{') => write!(output, "\\$")?,
+            _ => output.write_char(c)?,
+          }
+        }
+      }
+      RichTextInner::Dynamic(expression) => {
+        write!(output, "${{")?;
+        gen_expressions(
+          output,
+          component,
+          state,
+          &SCOPE,
+          &[],
+          false,
+          expression,
+        )?;
+        write!(output, "}}")?;
+      }
+    }
+  }
+  Ok(())
+}